@@ -1,11 +1,11 @@
 use crate::{
-    build_frontend, create_account, deploy_program_from_path, extract_recursive,
+    arch_pubkey_from_secp, build_frontend, create_account, deploy_program_from_path, extract_demo,
     find_key_name_by_pubkey, get_config_dir, get_keypair_from_name, get_pubkey_from_name,
-    key_name_exists, make_program_executable, setup_base_structure, Config, CreateAccountArgs,
-    DemoStartArgs, PROJECT_DIR,
+    get_rpc_url_with_fallback, key_name_exists, make_program_executable,
+    output::{detail, say},
+    setup_base_structure, Config, CreateAccountArgs, DemoStartArgs,
 };
 use anyhow::{Context, Result};
-use arch_program::pubkey::Pubkey;
 use colored::*;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -14,16 +14,16 @@ pub async fn setup_demo_environment(
     args: &DemoStartArgs,
     config: &Config,
 ) -> Result<(PathBuf, String, String, String)> {
-    println!("{}", "Setting up demo environment...".bold().green());
+    say("Setting up demo environment...".bold().green());
 
     // Get network type from config
     let network = config
         .get_string("bitcoin.network")
         .unwrap_or_else(|_| "regtest".to_string());
-    println!("Network type: {}", network);
+    detail(format!("Network type: {}", network));
 
-    let rpc_url = get_rpc_url_with_fallback(args.rpc_url.clone(), config).unwrap();
-    println!("Using RPC URL: {}", rpc_url);
+    let rpc_url = get_rpc_url_with_fallback(args.rpc_url.clone(), config)?;
+    detail(format!("Using RPC URL: {}", rpc_url));
 
     // Set up base structure
     let (_, projects_dir) = setup_base_structure(config)?;
@@ -31,15 +31,14 @@ pub async fn setup_demo_environment(
     // Set up demo project
     let demo_dir = projects_dir.join("demo");
     if !demo_dir.exists() {
-        println!(
+        say(format!(
             "  {} Demo directory not found. Creating it...",
             "ℹ".bold().blue()
-        );
+        ));
         fs::create_dir_all(&demo_dir)?;
 
         // Extract demo-specific files
-        let demo_files_dir = PROJECT_DIR.get_dir("app").unwrap();
-        extract_recursive(&demo_files_dir, &demo_dir.join("app"))?;
+        extract_demo(Some("app"), &demo_dir.join("app"))?;
 
         // Handle .env file
         let env_example_file = demo_dir.join("app/frontend/.env.example");
@@ -48,15 +47,12 @@ pub async fn setup_demo_environment(
         }
     }
 
-    // Change to the demo directory
-    std::env::set_current_dir(&demo_dir).context("Failed to change to demo directory")?;
-
     let env_file = demo_dir.join("app/frontend/.env");
-    println!(
+    detail(format!(
         "  {} Reading .env file from: {:?}",
         "ℹ".bold().blue(),
         env_file
-    );
+    ));
 
     // Read or create .env file
     let env_content = fs::read_to_string(&env_file).context("Failed to read .env file")?;
@@ -75,11 +71,17 @@ pub async fn setup_demo_environment(
         // Create new program account
         graffiti_key_name = create_unique_key_name(&keys_file)?;
 
-        println!("Creating account with name: {}", graffiti_key_name);
+        say(format!("Creating account with name: {}", graffiti_key_name));
         create_account(
             &CreateAccountArgs {
                 name: graffiti_key_name.clone(),
                 program_id: None,
+                space: None,
+                fund_from: None,
+                deterministic: false,
+                index: None,
+                no_fund: false,
+                fund_amount: None,
                 rpc_url: Some(rpc_url.clone()),
             },
             config,
@@ -89,12 +91,12 @@ pub async fn setup_demo_environment(
         program_pubkey = get_pubkey_from_name(&graffiti_key_name, &keys_file)?;
     } else {
         graffiti_key_name = find_key_name_by_pubkey(&keys_file, &program_pubkey)?;
-        println!("Using existing account with name: {}", graffiti_key_name);
+        say(format!("Using existing account with name: {}", graffiti_key_name));
     }
 
     // Deploy program
     let program_keypair = get_keypair_from_name(&graffiti_key_name, &keys_file)?;
-    let program_pubkey_bytes = Pubkey::from_slice(&program_keypair.public_key().serialize()[1..33]);
+    let program_pubkey_bytes = arch_pubkey_from_secp(&program_keypair.public_key());
 
     // Note: Using shared program directory for deployment
     // Clone the rpc_url before using it to avoid the "use of moved value" error
@@ -104,6 +106,8 @@ pub async fn setup_demo_environment(
         config,
         Some((program_keypair.clone(), program_pubkey_bytes)),
         rpc_url_clone,
+        false,
+        false,
     )
     .await?;
 
@@ -113,20 +117,26 @@ pub async fn setup_demo_environment(
 
     // Setup wall account
     let wall_pubkey = if key_name_exists(&keys_file, "graffiti_wall_state")? {
-        println!(
+        say(format!(
             "  {} Using existing graffiti_wall_state account",
             "ℹ".bold().blue()
-        );
+        ));
         get_pubkey_from_name("graffiti_wall_state", &keys_file)?
     } else {
-        println!(
+        say(format!(
             "  {} Creating new graffiti_wall_state account",
             "ℹ".bold().blue()
-        );
+        ));
         create_account(
             &CreateAccountArgs {
                 name: "graffiti_wall_state".to_string(),
                 program_id: Some(hex::encode(program_pubkey_bytes.serialize())),
+                space: None,
+                fund_from: None,
+                deterministic: false,
+                index: None,
+                no_fund: false,
+                fund_amount: None,
                 rpc_url: Some(rpc_url.clone()),
             },
             config,
@@ -160,18 +170,6 @@ fn get_rpc_url(args: &DemoStartArgs, config: &Config) -> Result<String> {
     get_rpc_url_with_fallback(args.rpc_url.clone(), config)
 }
 
-pub fn get_rpc_url_with_fallback(rpc_url: Option<String>, config: &Config) -> Result<String> {
-    println!(
-        "config.leader_rpc_endpoint: {}",
-        config.get_string("leader_rpc_endpoint").unwrap()
-    );
-    Ok(rpc_url.unwrap_or_else(|| {
-        config
-            .get_string("leader_rpc_endpoint")
-            .unwrap_or_else(|_| common::constants::NODE1_ADDRESS.to_string())
-    }))
-}
-
 fn update_demo_cargo_toml(demo_dir: &Path, base_dir: &Path) -> Result<()> {
     let cargo_path = demo_dir.join("Cargo.toml");
     let cargo_content = r#"[package]
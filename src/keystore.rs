@@ -0,0 +1,135 @@
+use anyhow::{anyhow, Context, Result};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use dialoguer::Password;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde_json::{json, Value};
+use std::sync::Mutex;
+
+use crate::settings::require_interactive;
+
+/// Environment variable holding the keys.json passphrase, so CI does not need a prompt
+pub const PASSPHRASE_ENV: &str = "ARCH_CLI_KEYS_PASSPHRASE";
+
+/// Top-level field marking an encrypted keys file
+const MARKER: &str = "arch_cli_keystore";
+const VERSION: u64 = 1;
+
+// scrypt cost parameters: N = 2^15, r = 8, p = 1
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+/// Passphrase entered during this run, so a read and the following write prompt only once
+static PASSPHRASE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Returns true when a parsed keys file is in the encrypted format
+pub fn is_encrypted(doc: &Value) -> bool {
+    doc.get(MARKER).is_some()
+}
+
+fn passphrase(prompt: &str, confirm: bool) -> Result<String> {
+    if let Some(passphrase) = PASSPHRASE.lock().unwrap().clone() {
+        return Ok(passphrase);
+    }
+
+    let passphrase = match std::env::var(PASSPHRASE_ENV) {
+        Ok(passphrase) if !passphrase.is_empty() => passphrase,
+        _ => {
+            require_interactive(&format!("Entering the keys.json passphrase (set {} instead)", PASSPHRASE_ENV))?;
+            let mut input = Password::new();
+            input.with_prompt(prompt);
+            if confirm {
+                input.with_confirmation("Repeat the passphrase", "The passphrases do not match");
+            }
+            input.interact()?
+        }
+    };
+
+    *PASSPHRASE.lock().unwrap() = Some(passphrase.clone());
+    Ok(passphrase)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], log_n: u8, r: u32, p: u32) -> Result<[u8; 32]> {
+    let params = scrypt::Params::new(log_n, r, p, 32)
+        .map_err(|e| anyhow!("Invalid scrypt parameters: {}", e))?;
+    let mut key = [0u8; 32];
+    scrypt::scrypt(passphrase.as_bytes(), salt, &params, &mut key)
+        .map_err(|e| anyhow!("Failed to derive the keys.json encryption key: {}", e))?;
+    Ok(key)
+}
+
+fn hex_field(header: &Value, name: &str) -> Result<Vec<u8>> {
+    let field = header[name]
+        .as_str()
+        .ok_or_else(|| anyhow!("Encrypted keys file is missing '{}'", name))?;
+    hex::decode(field).with_context(|| format!("Encrypted keys file has an invalid '{}'", name))
+}
+
+/// Encrypts a keys document, prompting for a new passphrase unless one was already given
+pub fn encrypt(keys: &Value) -> Result<Value> {
+    let passphrase = passphrase("New keys.json passphrase", true)?;
+
+    let mut salt = [0u8; 16];
+    let mut nonce = [0u8; 24];
+    OsRng.fill_bytes(&mut salt);
+    OsRng.fill_bytes(&mut nonce);
+
+    let key = derive_key(&passphrase, &salt, SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P)?;
+    let ciphertext = XChaCha20Poly1305::new(Key::from_slice(&key))
+        .encrypt(XNonce::from_slice(&nonce), serde_json::to_vec(keys)?.as_slice())
+        .map_err(|_| anyhow!("Failed to encrypt keys.json"))?;
+
+    Ok(json!({
+        MARKER: {
+            "version": VERSION,
+            "kdf": "scrypt",
+            "log_n": SCRYPT_LOG_N,
+            "r": SCRYPT_R,
+            "p": SCRYPT_P,
+            "salt": hex::encode(salt),
+            "cipher": "xchacha20poly1305",
+            "nonce": hex::encode(nonce),
+        },
+        "ciphertext": hex::encode(ciphertext),
+    }))
+}
+
+/// Decrypts a keys document in the encrypted format, prompting for the passphrase if needed
+pub fn decrypt(doc: &Value) -> Result<Value> {
+    let header = &doc[MARKER];
+    let version = header["version"].as_u64().unwrap_or(0);
+    if version != VERSION {
+        return Err(anyhow!("Unsupported encrypted keys file version {}", version));
+    }
+
+    let log_n = header["log_n"].as_u64().unwrap_or(u64::from(SCRYPT_LOG_N));
+    let r = header["r"].as_u64().unwrap_or(u64::from(SCRYPT_R));
+    let p = header["p"].as_u64().unwrap_or(u64::from(SCRYPT_P));
+    let salt = hex_field(header, "salt")?;
+    let nonce = hex_field(header, "nonce")?;
+    let ciphertext = hex_field(doc, "ciphertext")?;
+    if nonce.len() != 24 {
+        return Err(anyhow!("Encrypted keys file has an invalid nonce length"));
+    }
+
+    let passphrase = passphrase("keys.json passphrase", false)?;
+    let key = derive_key(
+        &passphrase,
+        &salt,
+        u8::try_from(log_n).context("Invalid scrypt cost")?,
+        u32::try_from(r).context("Invalid scrypt block size")?,
+        u32::try_from(p).context("Invalid scrypt parallelism")?,
+    )?;
+
+    let plaintext = XChaCha20Poly1305::new(Key::from_slice(&key))
+        .decrypt(XNonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| {
+            // Do not keep a wrong passphrase around for later reads
+            PASSPHRASE.lock().unwrap().take();
+            anyhow!("Failed to decrypt keys.json: wrong passphrase or corrupted file")
+        })?;
+
+    serde_json::from_slice(&plaintext).context("Decrypted keys.json is not valid JSON")
+}
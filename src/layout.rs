@@ -0,0 +1,250 @@
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use syn::{Expr, GenericArgument, Item, Lit, PathArguments, Type};
+
+/// Borsh layout of a struct declared in a program crate
+pub struct StructLayout {
+    pub name: String,
+    pub fields: Vec<FieldLayout>,
+    /// Whether the program deserializes its instruction data into this struct
+    pub instruction: bool,
+}
+
+pub struct FieldLayout {
+    pub name: String,
+    pub ty: String,
+    /// Serialized size in bytes, or None when it depends on the value
+    pub size: Option<usize>,
+    pub encoding: String,
+}
+
+impl StructLayout {
+    /// Serialized size in bytes, or None when any field has a variable size
+    pub fn size(&self) -> Option<usize> {
+        self.fields.iter().map(|field| field.size).sum()
+    }
+}
+
+/// Parses every Rust source file under `src_dir` and returns the layout of the
+/// structs deriving `BorshSerialize` or `BorshDeserialize`, in declaration order
+pub fn describe_borsh_structs(src_dir: &Path) -> Result<Vec<StructLayout>> {
+    let mut structs = Vec::new();
+    let mut sources = String::new();
+
+    for path in rust_sources(src_dir)? {
+        let source = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let file = syn::parse_file(&source)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+        structs.extend(file.items.into_iter().filter_map(|item| match item {
+            Item::Struct(item) if derives_borsh(&item.attrs) => Some(item),
+            _ => None,
+        }));
+        sources.push_str(&source);
+    }
+
+    // Structs may embed each other in any order, so resolve sizes until nothing changes
+    let mut known: HashMap<String, Option<usize>> = HashMap::new();
+    for _ in 0..=structs.len() {
+        let mut changed = false;
+        for item in &structs {
+            let size = item
+                .fields
+                .iter()
+                .map(|field| type_size(&field.ty, &known).0)
+                .sum::<Option<usize>>();
+            if known.insert(item.ident.to_string(), size) != Some(size) {
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let compact: String = sources.chars().filter(|c| !c.is_whitespace()).collect();
+    Ok(structs
+        .iter()
+        .map(|item| {
+            let name = item.ident.to_string();
+            let fields = item
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| {
+                    let (size, encoding) = type_size(&field.ty, &known);
+                    FieldLayout {
+                        name: field
+                            .ident
+                            .as_ref()
+                            .map_or_else(|| index.to_string(), |ident| ident.to_string()),
+                        ty: type_name(&field.ty),
+                        size,
+                        encoding,
+                    }
+                })
+                .collect();
+            StructLayout {
+                instruction: compact.contains(&format!("{}::try_from_slice(instruction_data", name))
+                    || compact.contains(&format!("{}::deserialize(&mutinstruction_data", name)),
+                name,
+                fields,
+            }
+        })
+        .collect())
+}
+
+fn rust_sources(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.extension().map_or(false, |ext| ext == "rs") {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn derives_borsh(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().filter(|attr| attr.path().is_ident("derive")).any(|attr| {
+        let mut borsh = false;
+        let _ = attr.parse_nested_meta(|meta| {
+            if let Some(segment) = meta.path.segments.last() {
+                let ident = segment.ident.to_string();
+                borsh |= ident == "BorshSerialize" || ident == "BorshDeserialize";
+            }
+            Ok(())
+        });
+        borsh
+    })
+}
+
+fn array_len(len: &Expr) -> Option<usize> {
+    match len {
+        Expr::Lit(expr) => match &expr.lit {
+            Lit::Int(int) => int.base10_parse().ok(),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn generic_args(arguments: &PathArguments) -> Vec<&Type> {
+    match arguments {
+        PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the Borsh size of a type, if fixed, and how it is encoded
+fn type_size(ty: &Type, known: &HashMap<String, Option<usize>>) -> (Option<usize>, String) {
+    match ty {
+        Type::Array(array) => {
+            let (elem_size, _) = type_size(&array.elem, known);
+            match (array_len(&array.len), elem_size) {
+                (Some(len), Some(elem_size)) => (
+                    Some(len * elem_size),
+                    format!("{} × {} bytes, no length prefix", len, elem_size),
+                ),
+                _ => (None, "array of variable-size elements, no length prefix".to_string()),
+            }
+        }
+        Type::Tuple(tuple) if tuple.elems.is_empty() => (Some(0), "empty".to_string()),
+        Type::Path(path) => {
+            let Some(segment) = path.path.segments.last() else {
+                return (None, "unknown type".to_string());
+            };
+            let ident = segment.ident.to_string();
+            let primitive = match ident.as_str() {
+                "u8" | "i8" => Some(1),
+                "bool" => return (Some(1), "1 byte, 0 or 1".to_string()),
+                "u16" | "i16" => Some(2),
+                "u32" | "i32" | "f32" => Some(4),
+                "u64" | "i64" | "f64" => Some(8),
+                "u128" | "i128" => Some(16),
+                "Pubkey" => return (Some(32), "32 bytes".to_string()),
+                _ => None,
+            };
+            if let Some(size) = primitive {
+                return (Some(size), format!("{} bytes, little-endian", size));
+            }
+
+            let args = generic_args(&segment.arguments);
+            match (ident.as_str(), args.as_slice()) {
+                ("String", _) => (None, "u32 little-endian length, then UTF-8 bytes".to_string()),
+                ("Vec", [elem]) => {
+                    let elem = match type_size(elem, known).0 {
+                        Some(size) => format!("{} bytes", size),
+                        None => "variable-size elements".to_string(),
+                    };
+                    (None, format!("u32 little-endian length, then n × {}", elem))
+                }
+                ("Option", [inner]) => {
+                    let inner = match type_size(inner, known).0 {
+                        Some(size) => format!("{} bytes", size),
+                        None => "the value".to_string(),
+                    };
+                    (None, format!("1-byte tag (0 = None, 1 = Some), then {} when present", inner))
+                }
+                ("Box", [inner]) => type_size(inner, known),
+                _ => match known.get(&ident) {
+                    Some(Some(size)) => (Some(*size), format!("nested {}, {} bytes", ident, size)),
+                    Some(None) => (None, format!("nested {}, variable size", ident)),
+                    None => (None, "type not declared with Borsh derives in this crate".to_string()),
+                },
+            }
+        }
+        _ => (None, "unsupported type".to_string()),
+    }
+}
+
+/// Renders a type the way it is usually written in source
+fn type_name(ty: &Type) -> String {
+    match ty {
+        Type::Array(array) => format!(
+            "[{}; {}]",
+            type_name(&array.elem),
+            array_len(&array.len).map_or_else(|| "_".to_string(), |len| len.to_string())
+        ),
+        Type::Tuple(tuple) => format!(
+            "({})",
+            tuple.elems.iter().map(type_name).collect::<Vec<_>>().join(", ")
+        ),
+        Type::Path(path) => path
+            .path
+            .segments
+            .iter()
+            .map(|segment| {
+                let args = generic_args(&segment.arguments);
+                if args.is_empty() {
+                    segment.ident.to_string()
+                } else {
+                    format!(
+                        "{}<{}>",
+                        segment.ident,
+                        args.into_iter().map(type_name).collect::<Vec<_>>().join(", ")
+                    )
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("::"),
+        Type::Reference(reference) => format!("&{}", type_name(&reference.elem)),
+        _ => "?".to_string(),
+    }
+}
@@ -1,45 +1,49 @@
 mod demo;
+mod keystore;
+mod layout;
+pub mod output;
+pub mod settings;
 use demo::{setup_demo_environment, build_frontend, get_cloud_run_url};
+use output::{detail, json_output, print_json, progress_event, progress_json, say, timing_phase, OutputFormat, ProgressFormat};
+use settings::{confirm, require_interactive, Preset};
 use anyhow::anyhow;
 use anyhow::{Context, Result};
 use arch_program::account::AccountMeta;
+use arch_program::entrypoint::MAX_PERMITTED_DATA_LENGTH;
 use arch_program::instruction::Instruction;
-use arch_program::message::Message;
 use arch_program::pubkey::Pubkey;
 use arch_program::system_instruction::SystemInstruction;
 use rand::{distributions::Alphanumeric, Rng};
 use bitcoin::key::UntweakedKeypair;
+use bitcoin::hashes::Hash;
 use bitcoin::Amount;
 use bitcoin::Network;
-use bitcoin::{Address, XOnlyPublicKey};
+use bitcoin::{Address, Txid, XOnlyPublicKey};
 use bitcoincore_rpc::jsonrpc::serde_json;
 use bitcoincore_rpc::{Client, RpcApi};
 use bitcoincore_rpc::json::EstimateMode;
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use colored::*;
 use common::constants::*;
 use common::helper::*;
 use common::helper::*;
-use common::runtime_transaction::RuntimeTransaction;
 use config::{Config, Environment, File};
 use dialoguer::theme::ColorfulTheme;
 use dialoguer::{Confirm, Input, Select};
 use dirs::home_dir;
-use bip322::{sign_message_bip322};
 use indicatif::{ProgressBar, ProgressStyle};
-use rand::rngs::OsRng;
 use secp256k1::Keypair;
 use secp256k1::{Secp256k1, SecretKey};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use webbrowser::open_browser;
+use std::cell::Cell;
 use std::collections::HashMap;
 use std::env;
 use regex::Regex;
 use std::fs;
 use std::fs::OpenOptions;
 use std::io;
-use std::io::BufReader;
 use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
@@ -47,16 +51,19 @@ use std::process::Command as ShellCommand;
 use std::process::Command;
 use std::str::FromStr;
 use std::time::Duration;
-use tokio::task;
 use toml_edit::{value, Document, Item, Array};
 use include_dir::{include_dir, Dir};
 
+use common::runtime_transaction::RUNTIME_TX_SIZE_LIMIT;
 use common::wallet_manager::*;
 
 static PROJECT_DIR: Dir = include_dir!("$CARGO_MANIFEST_DIR/templates/demo");
 static SAMPLE_DIR: Dir = include_dir!("$CARGO_MANIFEST_DIR/templates/sample");
 static TEMPLATES_DIR: Dir = include_dir!("$CARGO_MANIFEST_DIR/templates");
 
+/// Template directories that are shared libraries rather than project starters
+const SHARED_LIBRARIES: [&str; 3] = ["bip322", "common", "program"];
+
 #[derive(Deserialize)]
 pub struct ServiceConfig {
     #[allow(dead_code)]
@@ -89,20 +96,84 @@ pub struct Cli {
     #[clap(subcommand)]
     pub command: Commands,
 
-    /// Enable verbose output
-    #[clap(short, long, global = true)]
+    /// Print detailed progress, such as every file extracted
+    #[clap(short, long, global = true, conflicts_with = "quiet")]
     pub verbose: bool,
 
-    /// Specify the network to use (development, development2, testnet, mainnet)
-    #[clap(long, global = true, default_value = "development")]
-    pub network: String,
+    /// Specify the network to use (development, development2, testnet, mainnet).
+    /// Defaults to the network saved with 'config set-network', or development
+    #[clap(long, global = true)]
+    pub network: Option<String>,
+
+    /// Target an ad-hoc Arch node instead of a configured network
+    #[clap(
+        long,
+        global = true,
+        value_name = "RPC_URL",
+        help = "Synthesize an in-memory network for the Arch node at this RPC URL. Overrides --network"
+    )]
+    pub network_from_url: Option<String>,
+
+    /// Leader RPC endpoint to use instead of the selected network's
+    #[clap(
+        long,
+        global = true,
+        value_name = "RPC_URL",
+        conflicts_with = "network-from-url",
+        help = "Leader RPC endpoint to use instead of the selected network's leader_rpc_endpoint"
+    )]
+    pub leader_rpc: Option<String>,
+
+    /// Disable colored output (also honors NO_COLOR)
+    #[clap(long, global = true)]
+    pub no_color: bool,
+
+    /// Output format for commands that support machine-readable output
+    #[clap(long, global = true, value_enum, default_value = "text")]
+    pub output: OutputFormat,
+
+    /// Progress reporting style for long operations
+    #[clap(long, global = true, value_enum, default_value = "bar")]
+    pub progress: ProgressFormat,
+
+    /// Only print errors and final results
+    #[clap(short, long, global = true)]
+    pub quiet: bool,
+
+    /// Never prompt; fail or use defaults instead
+    #[clap(long, global = true)]
+    pub non_interactive: bool,
+
+    /// Preset for common environments: 'ci' implies --non-interactive --no-color --output json --quiet and strict timeouts
+    #[clap(long, global = true, value_enum)]
+    pub preset: Option<Preset>,
+
+    /// Configuration profile to use: reads config.<NAME>.toml from the config directory instead of config.toml
+    #[clap(long, global = true, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Hex seed for deterministic key generation (tests and reproducible demos only); also read from ARCH_CLI_KEY_SEED
+    #[clap(long, global = true, value_name = "HEX")]
+    pub seed: Option<String>,
+
+    /// Keep the temporary directory with the generated Dockerfile, cloudbuild.yaml and nginx.conf of GCP deployments, and print its path
+    #[clap(long, global = true)]
+    pub keep_temp: bool,
+
+    /// Print how long each phase of the command took when it finishes
+    #[clap(long, global = true)]
+    pub timings: bool,
+
+    /// Docker Compose project name, so several stacks can run side by side; defaults to docker.project_name in the config
+    #[clap(long, global = true, value_name = "NAME")]
+    pub project_name: Option<String>,
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
     /// Initialize a new Arch Network app
     #[clap(long_about = "Creates the project structure and configuration for a new Arch Network application.")]
-    Init,
+    Init(InitArgs),
 
     /// Manage the development server
     #[clap(subcommand)]
@@ -136,13 +207,21 @@ pub enum Commands {
     #[clap(subcommand)]
     Config(ConfigCommands),
 
+    /// Manage the bundled templates in the config directory
+    #[clap(subcommand)]
+    Templates(TemplatesCommands),
+
     /// Alias for 'server start'
     #[clap(alias = "up", hide = true)]
-    Start,
+    Start(ServerStartArgs),
 
     /// Alias for 'server stop'
     #[clap(alias = "down", hide = true)]
-    Stop,
+    Stop(ServerStopArgs),
+
+    /// Show the health of every stack started by arch-cli
+    #[clap(long_about = "Reports whether the server, validator, indexer and demo containers are running, with their ports and uptime.")]
+    Status,
 
     /// Manage the indexer
     #[clap(subcommand)]
@@ -151,17 +230,37 @@ pub enum Commands {
     /// Manage the validator
     #[clap(subcommand)]
     Validator(ValidatorCommands),
+
+    /// Query the Arch Network node
+    #[clap(subcommand)]
+    Network(NetworkCommands),
+
+    /// Inspect deployed programs
+    #[clap(subcommand)]
+    Program(ProgramCommands),
+
+    /// Check that the tools and configuration arch-cli needs are in place
+    #[clap(long_about = "Checks the required tools (Docker, Docker Compose, Node.js, Solana CLI, Cargo), the Docker daemon and the configuration file. With --output json, prints a report CI can assert on; exits with an error if any check fails.")]
+    Doctor,
+
+    /// Update arch-cli to the latest release
+    #[clap(long_about = "Checks the latest arch-cli release on GitHub and, after confirmation, reinstalls the CLI (and its bundled templates) at that release.")]
+    SelfUpdate(SelfUpdateArgs),
 }
 
 #[derive(Subcommand)]
 pub enum ServerCommands {
     /// Start the development server
     #[clap(long_about = "Starts the local development environment, including Bitcoin regtest network and Arch Network nodes.")]
-    Start,
+    Start(ServerStartArgs),
 
     /// Stop the development server
-    #[clap(long_about = "Stops all related Docker containers and services for the development environment.")]
-    Stop,
+    #[clap(long_about = "Stops and removes the Docker containers of the development environment. Their volumes, holding the Bitcoin and Arch chain state, are kept unless --purge is given.")]
+    Stop(ServerStopArgs),
+
+    /// Restart the development server, keeping its data
+    #[clap(long_about = "Restarts the containers of the development environment with 'docker compose restart', keeping their volumes and chain state. With --recreate, the stack is taken down and started again so compose file and environment changes apply.")]
+    Restart(ServerRestartArgs),
 
     /// Check the status of the development server
     #[clap(long_about = "Displays the current status of all services in the development environment.")]
@@ -170,9 +269,21 @@ pub enum ServerCommands {
     /// View logs for development server components
     #[clap(long_about = "Displays logs for specified services in the development environment.")]
     Logs {
-        /// Specify which service to show logs for (e.g., 'bitcoin', 'arch')
+        /// Service to show logs for: 'all', 'bitcoin', 'arch' or any container configured for the selected network (e.g. 'electrs', 'leader')
         #[clap(default_value = "all")]
         service: String,
+
+        /// Only show logs newer than this (e.g. '10m', '1h' or a timestamp) instead of the last 50 lines
+        #[clap(long)]
+        since: Option<String>,
+
+        /// Keep streaming new log lines from every container until Ctrl-C
+        #[clap(short, long)]
+        follow: bool,
+
+        /// Number of lines to show from the end of each container's log (default 50 without --since)
+        #[clap(long, value_name = "N")]
+        tail: Option<usize>,
     },
 
     /// Clean the project
@@ -180,6 +291,59 @@ pub enum ServerCommands {
     Clean,
 }
 
+#[derive(Args, Default)]
+pub struct ServerStartArgs {
+    /// Recreate the stack even if it is already running
+    #[clap(long, help = "Recreate the containers even if the stack is already running")]
+    force: bool,
+
+    /// Compose file to use instead of the network's configured one
+    #[clap(long, help = "Path to a Docker Compose file to start instead of the one configured for the selected network")]
+    compose_file: Option<PathBuf>,
+
+    /// Wait until the nodes accept RPC requests
+    #[clap(long, help = "Wait until the Arch leader and Bitcoin RPC endpoints respond before returning. Only use it with a compose file that runs both nodes")]
+    wait: bool,
+
+    /// How long to wait for the nodes
+    #[clap(long, value_name = "SECS", default_value = "180", help = "How long to wait for the nodes to respond before failing")]
+    wait_timeout: u64,
+
+    /// Start without checking the host ports first
+    #[clap(long, help = "Skip checking that the host ports published by the compose file are free before starting")]
+    skip_port_check: bool,
+}
+
+#[derive(Args, Default)]
+pub struct ServerRestartArgs {
+    /// Take the stack down and start it again instead of restarting the containers
+    #[clap(long, help = "Recreate the containers with a full down/up cycle, so changes to the compose file and environment apply. Volumes are kept")]
+    recreate: bool,
+
+    /// Compose file to use instead of the network's configured one
+    #[clap(long, help = "Path to the Docker Compose file the stack was started with, if not the one configured for the selected network")]
+    compose_file: Option<PathBuf>,
+
+    /// Wait until the nodes accept RPC requests
+    #[clap(long, help = "Wait until the Arch leader and Bitcoin RPC endpoints respond before returning. Only use it with a compose file that runs both nodes")]
+    wait: bool,
+
+    /// How long to wait for the nodes
+    #[clap(long, value_name = "SECS", default_value = "180", help = "How long to wait for the nodes to respond before failing")]
+    wait_timeout: u64,
+}
+
+#[derive(Args, Default)]
+pub struct ServerStopArgs {
+    /// Compose file to use instead of the network's configured one
+    #[clap(long, help = "Path to the Docker Compose file the stack was started with, if not the one configured for the selected network")]
+    compose_file: Option<PathBuf>,
+
+    /// Also delete the volumes
+    #[clap(long, help = "Also delete the stack's volumes, losing the local Bitcoin and Arch chain state and every funded account. Without it, the next 'server start' continues where the stack stopped")]
+    purge: bool,
+}
+
 #[derive(Subcommand)]
 pub enum ProjectCommands {
     /// Create a new project
@@ -189,6 +353,17 @@ pub enum ProjectCommands {
     /// Deploy a project
     #[clap(long_about = "Deploys the specified Arch Network project.")]
     Deploy,
+
+    /// List a project's built program binaries
+    #[clap(long_about = "Lists the .so files in the build output directories of a project's program crate, newest first, with their sizes and ages, to pick one for deploy --elf-path.")]
+    Artifacts(ProjectArtifactsArgs),
+}
+
+#[derive(Args)]
+pub struct ProjectArtifactsArgs {
+    /// Name of the project
+    #[clap(help = "Name of the project in the projects directory")]
+    name: String,
 }
 
 #[derive(Subcommand)]
@@ -201,6 +376,14 @@ pub enum IndexerCommands {
     #[clap(long_about = "Stops the arch-indexer using Docker Compose.")]
     Stop(IndexerStartArgs),
 
+    /// Check whether the indexer is running
+    #[clap(long_about = "Shows the state of the local indexer and its Postgres containers. For GCP, shows the indexer instance, its Cloud SQL database and the HTTPS endpoint.")]
+    Status(IndexerStatusArgs),
+
+    /// Show the indexer logs
+    #[clap(long_about = "Shows the logs of the local indexer container.")]
+    Logs(IndexerLogsArgs),
+
     /// Clean the indexer
     #[clap(long_about = "Removes the indexer data and configuration files.")]
     Clean,
@@ -226,9 +409,54 @@ pub struct IndexerStartArgs {
     #[clap(long, help = "GCP machine type")]
     gcp_machine_type: Option<String>,
 
+    /// Container registry for the built images (optional)
+    #[clap(
+        long,
+        value_name = "REGISTRY",
+        help = "Registry to push images to, e.g. '{region}-docker.pkg.dev/{project}/arch' for Artifact Registry. Defaults to gcp.registry in the config, then gcr.io/{project}"
+    )]
+    registry: Option<String>,
+
     /// RPC URL for connecting to the Arch Network
     #[clap(long, help = "RPC URL for the Arch Network node")]
     rpc_url: Option<String>,
+
+    /// arch-indexer version to run (optional)
+    #[clap(
+        long,
+        value_name = "REF",
+        help = "Tag, branch or commit of arch-indexer to check out instead of the latest main. Defaults to indexer.ref in the config"
+    )]
+    indexer_ref: Option<String>,
+}
+
+#[derive(Args)]
+pub struct IndexerStatusArgs {
+    /// Where the indexer runs (local or gcp)
+    #[clap(
+        long,
+        default_value = "local",
+        help = "Specifies where the indexer runs: local or gcp"
+    )]
+    target: String,
+
+    /// GCP configuration (required for the gcp target)
+    #[clap(long, help = "GCP project ID")]
+    gcp_project: Option<String>,
+
+    #[clap(long, help = "GCP region")]
+    gcp_region: Option<String>,
+}
+
+#[derive(Args)]
+pub struct IndexerLogsArgs {
+    /// Keep streaming new log lines
+    #[clap(short, long, help = "Stream new log lines as they are written")]
+    follow: bool,
+
+    /// Number of lines to show from the end (optional)
+    #[clap(long, value_name = "N", help = "Only show the last N lines")]
+    tail: Option<usize>,
 }
 
 #[derive(Subcommand)]
@@ -240,20 +468,188 @@ pub enum ValidatorCommands {
     /// Stop the validator
     #[clap(long_about = "Stops the local validator.")]
     Stop(ValidatorStartArgs),
+
+    /// Show the validator logs
+    #[clap(long_about = "Shows the logs of the local validator container, or the serial port output of the GCP validator instance.")]
+    Logs(ValidatorLogsArgs),
+
+    /// Show whether the validator is running and reachable
+    #[clap(long_about = "Shows the state of the local validator container, whether its RPC endpoint responds and how many peers it sees. For GCP, shows the instance status and its endpoints.")]
+    Status(ValidatorStatusArgs),
+}
+
+#[derive(Args)]
+pub struct ValidatorStatusArgs {
+    /// Where the validator runs (local or gcp)
+    #[clap(
+        long,
+        default_value = "local",
+        help = "Specifies where the validator runs: local or gcp"
+    )]
+    target: String,
+
+    /// GCP configuration (required for the gcp target)
+    #[clap(long, help = "GCP project ID")]
+    gcp_project: Option<String>,
+
+    #[clap(long, help = "GCP region")]
+    gcp_region: Option<String>,
+}
+
+#[derive(Args)]
+pub struct ValidatorLogsArgs {
+    /// Keep streaming new log lines
+    #[clap(short, long, help = "Stream new log lines as they are written")]
+    follow: bool,
+
+    /// Number of lines to show from the end (optional)
+    #[clap(long, value_name = "N", help = "Only show the last N lines")]
+    tail: Option<usize>,
+
+    /// Where the validator runs (local or gcp)
+    #[clap(
+        long,
+        default_value = "local",
+        help = "Specifies where the validator runs: local or gcp"
+    )]
+    target: String,
+
+    /// GCP configuration (required for the gcp target)
+    #[clap(long, help = "GCP project ID")]
+    gcp_project: Option<String>,
+
+    #[clap(long, help = "GCP region")]
+    gcp_region: Option<String>,
+}
+
+#[derive(Subcommand)]
+pub enum NetworkCommands {
+    /// Show the node's best block hash
+    #[clap(long_about = "Prints the best block hash reported by the Arch Network node. Useful for building transactions externally and as a quick connectivity check.")]
+    Blockhash(BlockhashArgs),
+}
+
+#[derive(Args)]
+pub struct BlockhashArgs {
+    /// RPC URL for connecting to the Arch Network
+    #[clap(long, help = "RPC URL for the Arch Network node")]
+    rpc_url: Option<String>,
+}
+
+#[derive(Subcommand)]
+pub enum ProgramCommands {
+    /// Show a deployed program
+    #[clap(long_about = "Prints the on-chain size, executable flag and owner of a deployed program.")]
+    Show(ShowProgramArgs),
+
+    /// Describe the Borsh layout of a program's structs
+    #[clap(long_about = "Parses the program crate of a project and prints the field layout, byte sizes and instruction data encoding of every struct deriving BorshSerialize or BorshDeserialize.")]
+    Describe(DescribeProgramArgs),
+
+    /// Redeploy a program under a new key
+    #[clap(long_about = "Generates a new program key, deploys the binary of an existing program under it and optionally reassigns accounts from keys.json to the new program. Clients still need to be pointed at the new program ID afterwards.")]
+    Rotate(RotateProgramArgs),
+}
+
+#[derive(Args)]
+pub struct RotateProgramArgs {
+    /// Program to replace
+    #[clap(long, help = "Hex-encoded ID or key name of the deployed program to replace")]
+    from: String,
+
+    /// Name for the new program key
+    #[clap(long, help = "Name to store the new program key under in keys.json")]
+    to: String,
+
+    /// Binary to deploy instead of the deployed one
+    #[clap(long, help = "Path to the program binary to deploy. Defaults to the bytes currently deployed at --from")]
+    elf_path: Option<String>,
+
+    /// Accounts to reassign to the new program
+    #[clap(long = "migrate-account", value_name = "NAME", help = "Reassign this account from keys.json to the new program. Can be repeated")]
+    migrate_accounts: Vec<String>,
+
+    /// RPC URL for connecting to the Arch Network
+    #[clap(long, help = "RPC URL for the Arch Network node")]
+    rpc_url: Option<String>,
+}
+
+#[derive(Args)]
+pub struct DescribeProgramArgs {
+    /// Project or program crate directory
+    #[clap(help = "Project directory or program crate to describe. Defaults to the current directory")]
+    directory: Option<String>,
+}
+
+#[derive(Args)]
+pub struct ShowProgramArgs {
+    /// Program ID or key name
+    #[clap(help = "Hex-encoded program ID or the name of a key in keys.json")]
+    program_id: String,
+
+    /// RPC URL for connecting to the Arch Network
+    #[clap(long, help = "RPC URL for the Arch Network node")]
+    rpc_url: Option<String>,
+}
+
+#[derive(Args)]
+pub struct SelfUpdateArgs {
+    /// Only report whether an update is available
+    #[clap(long, help = "Only check for a newer release without installing it")]
+    check: bool,
+}
+
+#[derive(Args)]
+pub struct DkgStartArgs {
+    /// Give up after this many seconds
+    #[clap(long, value_name = "SECS", help = "Fail if DKG has not occurred after this many seconds. Waits indefinitely by default")]
+    timeout: Option<u64>,
+
+    /// Give up after this many retries
+    #[clap(long, value_name = "N", help = "Fail after retrying this many times while the leader is down, not ready or DKG is still running")]
+    max_retries: Option<u32>,
+
+    /// Timeout of each request to the leader
+    #[clap(long, value_name = "SECS", default_value = "30", help = "Timeout of each HTTP request to the leader node")]
+    http_timeout: u64,
 }
 
 #[derive(Subcommand)]
 pub enum DkgCommands {
     /// Start the Distributed Key Generation (DKG) process
     #[clap(long_about = "Initiates the Distributed Key Generation process on the Arch Network.")]
-    Start,
+    Start(DkgStartArgs),
+
+    /// Show whether DKG has occurred
+    #[clap(long_about = "Asks the leader node once whether Distributed Key Generation has completed, is in progress, or the node is not ready for it yet. Fails when the leader cannot be reached or answers with an unrecognised error.")]
+    Status,
 }
 
 #[derive(Subcommand)]
 pub enum BitcoinCommands {
-    /// Send coins to an address on Regtest
-    #[clap(long_about = "Sends coins to a specified address on the Bitcoin Regtest network.")]
+    /// Send coins to an address
+    #[clap(long_about = "Sends coins from the configured wallet to an address on the configured Bitcoin network. On regtest, a block is mined afterwards to confirm the transaction.")]
     SendCoins(SendCoinsArgs),
+
+    /// List the wallet's unspent outputs
+    #[clap(long_about = "Lists the unspent outputs of the configured Bitcoin wallet, optionally filtered by address.")]
+    Utxos(UtxosArgs),
+
+    /// Show the wallet balance
+    #[clap(long_about = "Shows the configured Bitcoin wallet's trusted, untrusted pending and immature balances.")]
+    Balance(BitcoinFormatArgs),
+
+    /// Show the Bitcoin node and wallet status
+    #[clap(long_about = "Shows the chain, block height and sync progress of the Bitcoin node along with the configured wallet and its balance.")]
+    Info(BitcoinFormatArgs),
+
+    /// Mine blocks on Regtest
+    #[clap(long_about = "Mines blocks on the Bitcoin Regtest network to confirm pending transactions, paying the rewards to a new wallet address or the given one.")]
+    Mine(MineArgs),
+
+    /// Get a new wallet address
+    #[clap(long_about = "Prints a new receive address of the configured Bitcoin wallet, checked against the configured Bitcoin network.")]
+    Address,
 }
 
 #[derive(Subcommand)]
@@ -265,6 +661,10 @@ pub enum DemoCommands {
     /// Stop the demo application
     #[clap(long_about = "Stops the demo application.")]
     Stop,
+
+    /// Generate load against the graffiti wall
+    #[clap(long_about = "Creates and funds a set of signer accounts, then submits graffiti wall messages with the given concurrency and reports throughput and failures.")]
+    LoadTest(LoadTestArgs),
 }
 
 #[derive(Subcommand)]
@@ -275,7 +675,7 @@ pub enum AccountCommands {
 
     /// List all accounts
     #[clap(long_about = "Lists all accounts stored in the accounts file")]
-    List,
+    List(ListAccountsArgs),
 
     /// Delete an account
     #[clap(long_about = "Deletes an account from the accounts file")]
@@ -288,6 +688,60 @@ pub enum AccountCommands {
     /// Update account data
     #[clap(long_about = "Updates the account data from a file")]
     Update(UpdateAccountArgs),
+
+    /// Dump account data to a file
+    #[clap(long_about = "Reads the account's on-chain data and writes the raw bytes to a file. --offset and --length select a slice of large accounts")]
+    Dump(DumpAccountArgs),
+
+    /// Read account data back
+    #[clap(long_about = "Reads the account's on-chain data and prints it as a hex dump, or writes the raw bytes to a file with --out. --offset and --length select a slice of large accounts")]
+    Read(ReadAccountArgs),
+
+    /// Export a key for backup
+    #[clap(long_about = "Writes the hex-encoded secret key of a key in keys.json to a file or stdout. The output can be passed straight to deploy --program-key")]
+    Export(ExportAccountArgs),
+
+    /// Import an externally generated key
+    #[clap(long_about = "Stores an existing hex-encoded secret key in keys.json under a name, so deploy and the demo can find it")]
+    Import(ImportAccountArgs),
+
+    /// Rename a key in keys.json
+    #[clap(long_about = "Stores a key under a new name in keys.json. The key itself is left untouched")]
+    Rename(RenameAccountArgs),
+
+    /// Show an account's balances
+    #[clap(long_about = "Shows the Bitcoin balance of an account's address and the state of the account on the Arch Network, including the UTXO it is anchored to")]
+    Balance(AccountBalanceArgs),
+
+    /// Show everything about an account
+    #[clap(long_about = "Shows an account's key name, its state on the Arch Network (owner program, data length, executable flag and the UTXO it is anchored to) and the address and balance of its Bitcoin side")]
+    Info(AccountInfoArgs),
+
+    /// Encrypt keys.json with a passphrase
+    #[clap(long_about = "Encrypts an existing plaintext keys.json with a passphrase (scrypt and XChaCha20-Poly1305). Every command that reads or writes keys then asks for the passphrase, or reads it from ARCH_CLI_KEYS_PASSPHRASE")]
+    Encrypt,
+}
+
+#[derive(Subcommand)]
+pub enum TemplatesCommands {
+    /// Copy the bundled templates into the config directory
+    #[clap(long_about = "Compares the docker compose files and scripts in the config directory with the versions bundled in this release. By default only missing files are created; --force overwrites changed files after backing them up, and --diff previews the changes without writing anything.")]
+    Sync(TemplatesSyncArgs),
+}
+
+#[derive(Args)]
+pub struct TemplatesSyncArgs {
+    /// Only create missing files (the default)
+    #[clap(long, conflicts_with_all = &["force", "diff"], help = "Only create files that are missing. This is the default")]
+    only_missing: bool,
+
+    /// Overwrite every file that differs
+    #[clap(long, conflicts_with = "diff", help = "Overwrite every file that differs from the bundled version, keeping a backup of each")]
+    force: bool,
+
+    /// Preview the changes
+    #[clap(long, help = "Show how each file differs from the bundled version without changing anything")]
+    diff: bool,
 }
 
 #[derive(Subcommand)]
@@ -298,6 +752,29 @@ pub enum ConfigCommands {
     Edit,
     /// Reset configuration to default
     Reset,
+    /// Check the configuration for syntax errors, missing keys and invalid ports
+    Validate,
+    /// Save the network used when --network is omitted
+    SetNetwork {
+        /// Name of a network defined under [networks] in config.toml
+        name: String,
+    },
+    /// Show the network used when --network is omitted
+    GetNetwork,
+    /// List the configuration profiles in the config directory
+    List,
+    /// Print a single configuration value, such as networks.testnet.bitcoin_rpc_port
+    Get {
+        /// Dotted path of the key
+        key: String,
+    },
+    /// Set a single configuration value, creating missing tables
+    Set {
+        /// Dotted path of the key
+        key: String,
+        /// New value; parsed as a TOML value (number, boolean, array) unless the key already holds a string
+        value: String,
+    },
 }
 
 #[derive(Args)]
@@ -310,47 +787,45 @@ pub struct CreateAccountArgs {
     #[clap(long, help = "Specifies a custom name for the account")]
     name: String,
 
-    /// RPC URL for connecting to the Arch Network
-    #[clap(long, help = "RPC URL for the Arch Network node")]
-    rpc_url: Option<String>,
-}
-
-#[derive(Args)]
-pub struct DeleteAccountArgs {
-    /// Account ID or name to delete
-    #[clap(help = "Specifies the account ID or name to delete")]
-    identifier: String,
-}
+    /// Existing account to fund the new one from (optional)
+    #[clap(
+        long,
+        value_name = "ACCOUNT",
+        help = "Fund the new account from the Bitcoin held by this key's taproot address instead of the bitcoind wallet"
+    )]
+    fund_from: Option<String>,
 
-#[derive(Args)]
-pub struct CreateProjectArgs {
-    /// Name of the project
-    #[clap(short, long)]
-    pub name: Option<String>,
-}
+    /// Data length to allocate up front (optional)
+    #[clap(long, help = "Number of zeroed data bytes to allocate when the account is created")]
+    space: Option<usize>,
 
-#[derive(Args, Clone, Debug)]
-pub struct DeployArgs {
-    /// Path to the compiled ELF binary (optional)
+    /// Derive the key from the global seed instead of generating a random one
     #[clap(
         long,
-        help = "Path to the compiled ELF binary file. If not provided, will compile from source"
+        help = "Derive the account key from --seed (or ARCH_CLI_KEY_SEED) and --index, so re-running creates the same account. Does nothing if it already exists"
     )]
-    elf_path: Option<String>,
+    deterministic: bool,
 
-    /// Directory containing the program source (optional if elf_path is provided)
+    /// Derivation index for --deterministic (optional)
+    #[clap(long, requires = "deterministic", help = "Index of the key to derive with --deterministic. Default is 0")]
+    index: Option<u32>,
+
+    /// Amount to fund the account with (optional)
     #[clap(
         long,
-        help = "Directory containing your Arch Network program source"
+        value_name = "SATS",
+        help = "Satoshis sent to the account's Bitcoin address to back it. Defaults to bitcoin.funding_amount in the config, then 5000"
     )]
-    directory: Option<String>,
+    fund_amount: Option<u64>,
 
-    /// Path to the program key file (optional)
+    /// Only save the key and print its deposit address
     #[clap(
         long,
-        help = "Path to a file containing hex-encoded private key for deployment"
+        alias = "no-wait",
+        conflicts_with_all = &["fund-from", "space", "program-id"],
+        help = "Save the key and print the Bitcoin deposit address of the account without funding or creating it, so it can be funded from another wallet. Run the command again without this flag to create the account once the deposit confirms"
     )]
-    program_key: Option<String>,
+    no_fund: bool,
 
     /// RPC URL for connecting to the Arch Network
     #[clap(long, help = "RPC URL for the Arch Network node")]
@@ -358,51 +833,378 @@ pub struct DeployArgs {
 }
 
 #[derive(Args)]
-pub struct SendCoinsArgs {
-    /// Address to send coins to
-    #[clap(long, help = "Specifies the address to send coins to")]
-    address: String,
-    /// Amount to send
-    #[clap(long, help = "Specifies the amount of coins to send")]
-    amount: u64,
-}
+pub struct ListAccountsArgs {
+    /// Enrich the listing with on-chain state
+    #[clap(long, help = "Query the node for each account's existence, owner and data length")]
+    on_chain: bool,
 
-#[derive(Args)]
-pub struct DemoStartArgs {
-    /// Deployment target (local or gcp)
+    /// Only show accounts owned by this program
     #[clap(
         long,
-        default_value = "local",
-        help = "Specifies where to deploy the demo: local or gcp"
+        help = "Only list accounts owned by the given program ID or key name. Implies --on-chain"
     )]
-    target: String,
-
-    /// GCP configuration (required for GCP deployment)
-    #[clap(long, help = "GCP project ID")]
-    gcp_project: Option<String>,
-
-    #[clap(long, help = "GCP region")]
-    gcp_region: Option<String>,
+    owner: Option<String>,
 
     /// RPC URL for connecting to the Arch Network
     #[clap(long, help = "RPC URL for the Arch Network node")]
     rpc_url: Option<String>,
+}
 
-    /// Skip cleanup of existing containers and network
-    #[clap(long, help = "Skip stopping existing containers and removing network")]
-    skip_cleanup: bool,
+#[derive(Args)]
+pub struct DeleteAccountArgs {
+    /// Account ID or name to delete
+    #[clap(help = "Specifies the account ID or name to delete")]
+    identifier: String,
 }
 
 #[derive(Args)]
-pub struct ValidatorStartArgs {
-    /// Network to use (testnet or mainnet)
-    #[clap(
+pub struct ExportAccountArgs {
+    /// Account ID or name to export
+    #[clap(help = "Specifies the account ID or name to export")]
+    identifier: String,
+
+    /// Path to write the secret key to
+    #[clap(long, help = "Path of the file to write the hex-encoded secret key to. Prints to stdout if omitted")]
+    out: Option<PathBuf>,
+
+    /// Overwrite an existing file
+    #[clap(long, help = "Overwrite the file given with --out if it already exists")]
+    force: bool,
+}
+
+#[derive(Args)]
+pub struct RenameAccountArgs {
+    /// Current name or account ID
+    #[clap(help = "Current name or public key of the account")]
+    old: String,
+
+    /// New name
+    #[clap(help = "New name for the account")]
+    new: String,
+}
+
+#[derive(Args)]
+pub struct AccountBalanceArgs {
+    /// Account ID or name
+    #[clap(help = "Specifies the account ID or name to show the balances of")]
+    identifier: String,
+
+    /// RPC URL for connecting to the Arch Network
+    #[clap(long, help = "RPC URL for the Arch Network node")]
+    rpc_url: Option<String>,
+}
+
+#[derive(Args)]
+pub struct AccountInfoArgs {
+    /// Account ID or name
+    #[clap(help = "Specifies the account ID or name to show")]
+    identifier: String,
+
+    /// RPC URL for connecting to the Arch Network
+    #[clap(long, help = "RPC URL for the Arch Network node")]
+    rpc_url: Option<String>,
+}
+
+#[derive(Args)]
+pub struct ImportAccountArgs {
+    /// Name to store the key under
+    #[clap(long, help = "Name to store the key under in keys.json")]
+    name: String,
+
+    /// Secret key, inline or in a file
+    #[clap(long, value_name = "HEX|PATH", help = "Hex-encoded secret key, or the path of a file containing one")]
+    secret_key: String,
+}
+
+#[derive(Args)]
+pub struct InitArgs {
+    /// Only set up the configuration and shared libraries
+    #[clap(
+        long,
+        help = "Only create the config, arch-data directory and shared libraries, skipping the demo extraction and build"
+    )]
+    minimal: bool,
+
+    /// Bundled template to scaffold
+    #[clap(
+        long = "with",
+        default_value = "demo",
+        help = "Bundled template to scaffold into the projects directory (see --list-templates)"
+    )]
+    template: String,
+
+    /// List the bundled templates
+    #[clap(long, help = "List the bundled templates available to --with and exit")]
+    list_templates: bool,
+}
+
+#[derive(Args)]
+pub struct CreateProjectArgs {
+    /// Name of the project
+    #[clap(short, long)]
+    pub name: Option<String>,
+
+    /// Skip creating the frontend and installing npm packages
+    #[clap(long, help = "Only scaffold the files. The npm commands that need network access are printed instead of run")]
+    pub no_install: bool,
+
+    /// Only create the program sources, without a frontend
+    #[clap(
+        long,
+        conflicts_with = "no-install",
+        help = "Create a backend-only project: only the program sources are extracted and npm is never run, so Node.js is not needed"
+    )]
+    pub no_frontend: bool,
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct DeployArgs {
+    /// Path to the compiled ELF binary (optional)
+    #[clap(
+        long,
+        help = "Path to the compiled ELF binary file. If not provided, will compile from source"
+    )]
+    elf_path: Option<String>,
+
+    /// URL to download the compiled ELF binary from (optional)
+    #[clap(
+        long,
+        conflicts_with_all = &["elf-path", "directory"],
+        help = "URL of a compiled ELF binary to download and deploy instead of a local path or source"
+    )]
+    elf_url: Option<String>,
+
+    /// Expected SHA-256 of the downloaded ELF binary (optional)
+    #[clap(
+        long,
+        requires = "elf-url",
+        help = "Hex-encoded SHA-256 checksum the binary downloaded with --elf-url must match"
+    )]
+    elf_sha256: Option<String>,
+
+    /// Directory containing the program source (optional if elf_path is provided)
+    #[clap(
+        long,
+        help = "Directory containing your Arch Network program source"
+    )]
+    directory: Option<String>,
+
+    /// Program crate location relative to the project (optional)
+    #[clap(
+        long,
+        help = "Path of the program crate relative to the selected project. If not provided, the program crate is detected automatically"
+    )]
+    program_dir: Option<String>,
+
+    /// Path to the program key file (optional)
+    #[clap(
+        long,
+        help = "Path to a file containing hex-encoded private key for deployment"
+    )]
+    program_key: Option<String>,
+
+    /// RPC URL for connecting to the Arch Network
+    #[clap(long, help = "RPC URL for the Arch Network node")]
+    rpc_url: Option<String>,
+
+    /// Only compare a local binary with an already deployed program
+    #[clap(
+        long,
+        value_name = "PROGRAM_ID",
+        help = "Compare the local ELF binary (--elf-path or --elf-url) with the program deployed at this ID or key name, without deploying"
+    )]
+    verify_only: Option<String>,
+
+    /// Check the uploaded bytes after deploying
+    #[clap(
+        long,
+        help = "After uploading, read the program account back and compare its length and hash with the local binary, reporting the byte ranges that did not land"
+    )]
+    verify: bool,
+
+    /// Upper bound for the whole deployment (optional)
+    #[clap(
+        long,
+        value_name = "SECS",
+        help = "Fail the deployment if it has not finished after this many seconds, reporting the phase that was in progress"
+    )]
+    deploy_timeout: Option<u64>,
+
+    /// Report what the deployment would take without sending anything
+    #[clap(
+        long,
+        conflicts_with_all = &["verify-only", "resume", "verify"],
+        help = "Compile or read the ELF and print the program ID, the number of transactions and the projected Bitcoin fees, without funding or sending anything"
+    )]
+    dry_run: bool,
+
+    /// Continue an interrupted upload instead of starting over
+    #[clap(
+        long,
+        help = "Skip the program chunks already on chain, as recorded in the .deploy-state file next to the ELF, and upload only the rest"
+    )]
+    resume: bool,
+
+    /// Redeploy over an existing program account
+    #[clap(
+        long,
+        help = "Upload the binary to the already deployed program at the selected key, keeping its program ID, and make it executable again"
+    )]
+    upgrade: bool,
+
+    /// File to write the program ID to (optional)
+    #[clap(
+        long,
+        value_name = "PATH",
+        help = "Write the hex-encoded program ID to this file. Defaults to program-id.txt in the deployed project"
+    )]
+    id_out: Option<PathBuf>,
+
+    /// Priority fee to attach to deployment transactions (optional)
+    #[clap(
+        long,
+        help = "Priority fee for deployment transactions. Not yet honored by any Arch Network runtime"
+    )]
+    priority_fee: Option<u64>,
+}
+
+#[derive(Args)]
+pub struct SendCoinsArgs {
+    /// Address to send coins to
+    #[clap(long, help = "Specifies the address to send coins to")]
+    address: String,
+    /// Amount to send
+    #[clap(long, help = "Specifies the amount of coins to send")]
+    amount: u64,
+
+    /// Confirmation target for the fee estimate, in blocks
+    #[clap(long, default_value = "1", help = "Number of blocks the fee is estimated to get the transaction confirmed within")]
+    conf_target: u32,
+
+    /// Fee estimation mode
+    #[clap(long, value_enum, default_value = "economical", help = "Fee estimation mode passed to the wallet")]
+    fee_mode: FeeMode,
+
+    /// Signal replace-by-fee (the default)
+    #[clap(long, overrides_with = "no-rbf", help = "Mark the transaction as replaceable (BIP 125). This is the default")]
+    rbf: bool,
+
+    /// Do not signal replace-by-fee
+    #[clap(long, overrides_with = "rbf", help = "Send the transaction without marking it as replaceable")]
+    no_rbf: bool,
+
+    /// Do not mine a block after sending on regtest
+    #[clap(long, help = "Do not mine a block to confirm the transaction. Blocks are only ever mined on regtest")]
+    no_generate: bool,
+}
+
+/// Fee estimation mode for `bitcoin send-coins`
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeeMode {
+    /// Lower fees that respond faster to short-term drops in demand
+    Economical,
+    /// Higher fees that are less likely to be too low
+    Conservative,
+}
+
+#[derive(Args)]
+pub struct MineArgs {
+    /// Number of blocks to mine
+    #[clap(long, default_value = "1", help = "Number of blocks to mine")]
+    blocks: u64,
+
+    /// Address receiving the block rewards
+    #[clap(long, help = "Address to pay the block rewards to. Defaults to a new address of the configured wallet")]
+    address: Option<String>,
+}
+
+#[derive(Args)]
+pub struct UtxosArgs {
+    /// Only list outputs paying to this address
+    #[clap(long, help = "Only list unspent outputs for this address")]
+    address: Option<String>,
+}
+
+/// How `bitcoin balance` and `bitcoin info` print amounts
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AmountFormat {
+    /// Satoshis
+    Sat,
+    /// Bitcoin, with 8 decimals
+    Btc,
+    /// A JSON object with satoshi amounts
+    Json,
+}
+
+#[derive(Args)]
+pub struct BitcoinFormatArgs {
+    /// Amount format
+    #[clap(long, value_enum, default_value = "btc", help = "Show amounts in satoshis or BTC, or print the result as JSON")]
+    format: AmountFormat,
+}
+
+#[derive(Args)]
+pub struct DemoStartArgs {
+    /// Deployment target (local or gcp)
+    #[clap(
+        long,
+        default_value = "local",
+        help = "Specifies where to deploy the demo: local or gcp"
+    )]
+    target: String,
+
+    /// GCP configuration (required for GCP deployment)
+    #[clap(long, help = "GCP project ID")]
+    gcp_project: Option<String>,
+
+    #[clap(long, help = "GCP region")]
+    gcp_region: Option<String>,
+
+    /// Container registry for the built images (optional)
+    #[clap(
+        long,
+        value_name = "REGISTRY",
+        help = "Registry to push images to, e.g. '{region}-docker.pkg.dev/{project}/arch' for Artifact Registry. Defaults to gcp.registry in the config, then gcr.io/{project}"
+    )]
+    registry: Option<String>,
+
+    /// RPC URL for connecting to the Arch Network
+    #[clap(long, help = "RPC URL for the Arch Network node")]
+    rpc_url: Option<String>,
+
+    /// Skip cleanup of existing containers and network
+    #[clap(long, help = "Skip stopping existing containers and removing network")]
+    skip_cleanup: bool,
+}
+
+#[derive(Args)]
+pub struct LoadTestArgs {
+    /// Number of signer accounts to create
+    #[clap(long, default_value = "5", help = "Number of signer accounts to create and fund")]
+    count: usize,
+
+    /// Messages to submit per signer
+    #[clap(long, default_value = "10", help = "Number of graffiti messages each signer submits")]
+    messages: usize,
+
+    /// Maximum number of in-flight transactions
+    #[clap(long, default_value = "4", help = "Maximum number of transactions in flight at once")]
+    concurrency: usize,
+
+    /// Wall account to write to (optional)
+    #[clap(
         long,
-        default_value = "development",
-        help = "Specifies the network to use: development, development2, testnet, or mainnet"
+        help = "Hex-encoded public key of the graffiti wall account. Defaults to the graffiti_wall_state key created by 'demo start'"
     )]
-    network: String,
+    wall_account: Option<String>,
+
+    /// RPC URL for connecting to the Arch Network
+    #[clap(long, help = "RPC URL for the Arch Network node")]
+    rpc_url: Option<String>,
+}
 
+#[derive(Args)]
+pub struct ValidatorStartArgs {
     /// Deployment target (local or gcp)
     #[clap(
         long,
@@ -420,6 +1222,23 @@ pub struct ValidatorStartArgs {
 
     #[clap(long, help = "GCP machine type")]
     gcp_machine_type: Option<String>,
+
+    /// Container registry for the built images (optional)
+    #[clap(
+        long,
+        value_name = "REGISTRY",
+        help = "Registry to push images to, e.g. '{region}-docker.pkg.dev/{project}/arch' for Artifact Registry. Defaults to gcp.registry in the config, then gcr.io/{project}"
+    )]
+    registry: Option<String>,
+
+    /// RPC endpoint of a validator that is already running
+    #[clap(
+        long,
+        alias = "attach-existing",
+        value_name = "RPC_URL",
+        help = "Use the validator already serving this RPC endpoint instead of managing a container. With 'validator stop', detaches from it"
+    )]
+    attach: Option<String>,
 }
 
 #[derive(Args)]
@@ -447,16 +1266,114 @@ pub struct UpdateAccountArgs {
     #[clap(long, help = "Path to file containing the account data bytes")]
     data_file: PathBuf,
 
+    /// Byte offset to write the data at
+    #[clap(long, default_value = "0", help = "Byte offset within the account data to write the file at. Bytes past the end of the current data extend the account")]
+    offset: u32,
+
+    /// Key files to sign with instead of keys.json
+    #[clap(long, value_name = "FILES", value_delimiter = ',', help = "Comma-separated files holding hex-encoded secret keys, as written by account export, to sign with instead of the key in keys.json. Must cover exactly the signers the instruction requires")]
+    signers: Vec<PathBuf>,
+
+    /// RPC URL for connecting to the Arch Network
+    #[clap(long, help = "RPC URL for the Arch Network node")]
+    rpc_url: Option<String>,
+}
+
+#[derive(Args)]
+pub struct ReadAccountArgs {
+    /// Account name or ID to read
+    #[clap(help = "Name or ID of the account to read")]
+    identifier: String,
+
+    /// Path to write the data to (optional)
+    #[clap(long, help = "Write the raw bytes to this file instead of printing a hex dump")]
+    out: Option<PathBuf>,
+
+    #[clap(flatten)]
+    range: AccountDataRange,
+
+    /// RPC URL for connecting to the Arch Network
+    #[clap(long, help = "RPC URL for the Arch Network node")]
+    rpc_url: Option<String>,
+}
+
+#[derive(Args)]
+pub struct DumpAccountArgs {
+    /// Account name or ID to dump
+    #[clap(help = "Name or ID of the account to dump")]
+    identifier: String,
+
+    /// Path to write the data to
+    #[clap(long, help = "Path of the file to write the account data bytes to")]
+    out: PathBuf,
+
+    #[clap(flatten)]
+    range: AccountDataRange,
+
     /// RPC URL for connecting to the Arch Network
     #[clap(long, help = "RPC URL for the Arch Network node")]
     rpc_url: Option<String>,
 }
 
-pub async fn init() -> Result<()> {
-    println!("{}", "Initializing new Arch Network app...".bold().green());
+/// Slice of the account data selected by `account dump` and `account read`
+#[derive(Args)]
+pub struct AccountDataRange {
+    /// Byte offset to start reading at
+    #[clap(long, default_value = "0", help = "Byte offset in the account data to start reading at")]
+    offset: usize,
+
+    /// Number of bytes to read (optional)
+    #[clap(long, help = "Number of bytes to read. Defaults to the rest of the data")]
+    length: Option<usize>,
+}
+
+/// Disables colored output when requested with `--no-color` or `NO_COLOR`, or when stdout is not a terminal
+pub fn configure_color_output(no_color: bool) {
+    use std::io::IsTerminal;
+
+    let no_color_env = env::var_os("NO_COLOR").map_or(false, |value| !value.is_empty());
+    if no_color || no_color_env || !io::stdout().is_terminal() {
+        colored::control::set_override(false);
+    }
+}
+
+/// Returns the names of the bundled project templates
+fn available_templates() -> Vec<String> {
+    let mut templates: Vec<String> = TEMPLATES_DIR
+        .dirs()
+        .filter_map(|dir| dir.path().file_name())
+        .map(|name| name.to_string_lossy().into_owned())
+        .filter(|name| !SHARED_LIBRARIES.contains(&name.as_str()))
+        .collect();
+    templates.sort();
+    templates
+}
+
+pub async fn init(args: &InitArgs) -> Result<()> {
+    let templates = available_templates();
+
+    if args.list_templates {
+        println!("{}", "Available templates:".bold().green());
+        for template in &templates {
+            println!("  {} {}", "→".bold().blue(), template.yellow());
+        }
+        return Ok(());
+    }
+
+    if !templates.contains(&args.template) {
+        return Err(anyhow!(
+            "Unknown template '{}'. Available templates: {}",
+            args.template,
+            templates.join(", ")
+        ));
+    }
+
+    say("Initializing new Arch Network app...".bold().green());
 
     // Check dependencies
-    check_dependencies()?;
+    timing_phase("checking dependencies");
+    check_dependencies(!args.minimal)?;
+    timing_phase("setting up the project");
 
     // Ensure default config exists
     ensure_default_config()?;
@@ -476,53 +1393,68 @@ pub async fn init() -> Result<()> {
     let arch_data_dir = config_dir.join("arch-data");
     fs::create_dir_all(&arch_data_dir)?;
 
-    println!(
+    say(format!(
         "  {} Created arch-data directory at {:?}",
         "✓".bold().green(),
         arch_data_dir
-    );
+    ));
 
     let config = Config::builder()
         .add_source(File::with_name(config_path.to_str().unwrap()))
         .build()?;
-    let (_, _projects_dir) = setup_base_structure(&config)?;
+    let (base_dir, _projects_dir) = setup_base_structure(&config)?;
+    project_dir = base_dir;
 
-    // Create the 'demo' folder within the project directory if it doesn't exist
-    let demo_dir = project_dir.join("projects/demo");
-    if !demo_dir.exists() {
-        // Create the 'demo' folder within the project directory
-        fs::create_dir_all(&demo_dir)?;
-        println!(
-            "  {} Created demo directory at {:?}",
+    if args.minimal {
+        say(format!(
+            "  {} Minimal Arch Network environment initialized at {:?}",
             "✓".bold().green(),
-            demo_dir
-        );
+            project_dir
+        ));
+        return Ok(());
+    }
 
-        // Extract project files from binary
-        extract_recursive(&PROJECT_DIR, &demo_dir)?;
+    // Create the template folder within the project directory if it doesn't exist
+    let template_dir = project_dir.join("projects").join(&args.template);
+    if !template_dir.exists() {
+        // Create the template folder within the project directory
+        fs::create_dir_all(&template_dir)?;
+        say(format!(
+            "  {} Created {} directory at {:?}",
+            "✓".bold().green(),
+            args.template,
+            template_dir
+        ));
+
+        // Extract project files from binary, or from a local demo checkout when overridden
+        if args.template == "demo" {
+            extract_demo(None, &template_dir)?;
+        } else {
+            let template_files = TEMPLATES_DIR
+                .get_dir(&args.template)
+                .ok_or_else(|| anyhow!("Template directory '{}' not found", args.template))?;
+            extract_recursive(template_files, &template_dir)?;
+        }
 
         // Rename the .env.example file to .env
-        let env_example_file = PathBuf::from(&demo_dir).join("app/frontend/.env.example");
+        let env_example_file = template_dir.join("app/frontend/.env.example");
         if env_example_file.exists() {
-            fs::rename(&env_example_file, PathBuf::from(&demo_dir).join("app/frontend/.env"))?;
+            fs::rename(&env_example_file, template_dir.join("app/frontend/.env"))?;
         }
 
-        // Change to the demo directory
-        std::env::set_current_dir(&project_dir)?;
-
         // Build the program
-        println!("{}", "Building Arch Network program...".bold().blue());
+        say("Building Arch Network program...".bold().blue());
         let build_result = ShellCommand::new("cargo")
-            .current_dir("program")
+            .current_dir(project_dir.join("program"))
             .arg("build-sbf")
             .output();
 
         match build_result {
             Ok(output) if output.status.success() => {
-                println!(
+                say(format!(
                     "  {} Arch Network program built successfully",
                     "✓".bold().green()
-                );
+                ));
             }
             Ok(output) => {
                 println!(
@@ -541,10 +1473,10 @@ pub async fn init() -> Result<()> {
         }
     }
 
-    println!(
+    say(format!(
         "  {} New Arch Network app initialized successfully!",
         "✓".bold().green()
-    );
+    ));
     Ok(())
 }
 
@@ -650,10 +1582,10 @@ fn update_config_with_project_dir(config_path: &Path, project_dir: &Path) -> Res
     // Write the updated config back to the file
     fs::write(config_path, doc.to_string())?;
 
-    println!(
+    say(format!(
         "  {} Updated configuration with project directory",
         "✓".bold().green()
-    );
+    ));
 
     Ok(())
 }
@@ -674,6 +1606,11 @@ fn get_default_project_dir() -> PathBuf {
 }
 
 fn prompt_for_project_dir(default_dir: &Path) -> Result<PathBuf> {
+    if settings::non_interactive() {
+        println!("  {} Using project directory {}", "ℹ".bold().blue(), default_dir.display());
+        return Ok(default_dir.to_path_buf());
+    }
+
     println!("Where would you like to create your Arch Network project?");
     println!("Default: {}", default_dir.display());
     println!("⚠️  📝 Note: Please provide a full path (e.g., /home/user/projects or C:\\Users\\user\\Projects) 📝 ⚠️");
@@ -707,18 +1644,32 @@ fn prompt_for_project_dir(default_dir: &Path) -> Result<PathBuf> {
 }
 
 pub async fn create_project(args: &CreateProjectArgs, config: &Config) -> Result<()> {
-    println!("{}", "Creating new project...".bold().green());
+    say("Creating new project...".bold().green());
+
+    // Fail before writing anything when the frontend cannot be created
+    if !args.no_frontend && !args.no_install {
+        let (name, commands, error_message) = DEPENDENCIES
+            .iter()
+            .find(|(name, _, _)| *name == "node")
+            .expect("node is a listed dependency");
+        if let Some(error) = check_dependency(name, commands, error_message).error {
+            return Err(anyhow!("{} Pass --no-frontend to create a backend-only project.", error));
+        }
+    }
 
     // Set up base structure
     let (base_dir, projects_dir) = setup_base_structure(config)?;
 
     // Get project name
-    let project_name = args.name.clone().unwrap_or_else(|| {
-        Input::<String>::new()
-            .with_prompt("Enter a name for your project")
-            .interact()
-            .unwrap()
-    });
+    let project_name = match args.name.clone() {
+        Some(name) => name,
+        None => {
+            require_interactive("Choosing a project name")?;
+            Input::<String>::new()
+                .with_prompt("Enter a name for your project")
+                .interact()?
+        }
+    };
 
     // Create project directory within projects directory
     let project_dir = projects_dir.join(&project_name);
@@ -733,26 +1684,26 @@ pub async fn create_project(args: &CreateProjectArgs, config: &Config) -> Result
         match entry {
             include_dir::DirEntry::File(file) => {
                 let target_path = project_dir.join(file.path().file_name().unwrap());
-                println!("    {} Extracting file: {:?}", "📄".bold().blue(), target_path);
+                detail(format!("    {} Extracting file: {:?}", "📄".bold().blue(), target_path));
                 fs::write(target_path, file.contents())?;
             }
             include_dir::DirEntry::Dir(dir) => {
                 let dir_name = dir.path().file_name().unwrap();
                 let target_path = project_dir.join(dir_name);
-                println!("    {} Extracting directory: {:?}", "📁".bold().blue(), target_path);
+                detail(format!("    {} Extracting directory: {:?}", "📁".bold().blue(), target_path));
 
                 fs::create_dir_all(&target_path)?;
                 for subentry in dir.entries() {
                     match subentry {
                         include_dir::DirEntry::File(file) => {
                             let file_path = target_path.join(file.path().file_name().unwrap());
-                            println!("      {} Extracting file: {:?}", "📄".bold().blue(), file_path);
+                            detail(format!("      {} Extracting file: {:?}", "📄".bold().blue(), file_path));
                             fs::write(file_path, file.contents())?;
                         }
                         include_dir::DirEntry::Dir(subdir) => {
                             let subdir_name = subdir.path().file_name().unwrap();
                             let subdir_path = target_path.join(subdir_name);
-                            println!("      {} Extracting directory: {:?}", "📁".bold().blue(), subdir_path);
+                            detail(format!("      {} Extracting directory: {:?}", "📁".bold().blue(), subdir_path));
 
                             // Create subdirectory and copy its contents directly
                             fs::create_dir_all(&subdir_path)?;
@@ -780,11 +1731,92 @@ pub async fn create_project(args: &CreateProjectArgs, config: &Config) -> Result
         }
     }
 
-    // Create Vite app using npm
-    println!("Creating Vite application...");
+    if args.no_frontend {
+        say(format!("  {} Skipped the frontend (--no-frontend)", "ℹ".bold().blue()));
+    } else {
+        scaffold_frontend(&project_dir.join("app"), args.no_install, config)?;
+    }
+
+    say("Project created successfully! 🎉".bold().green());
+    say(format!("Project location: {:?}", project_dir));
+
+    say(format!("\n{}", "Next steps:".bold().yellow()));
+    say(format!(
+        "  1. Navigate to {} to find the Rust program template",
+        project_dir.join("app").join("program").display().to_string().yellow()
+    ));
+    say("  2. Edit the source code to implement your program logic");
+    say(format!("  3. When ready, run {} to compile and deploy your program to the network", "arch-cli deploy".cyan()));
+    say(format!("\n{}", "Need help? Check out our documentation at https://docs.arch.network".italic()));
+
+    Ok(())
+}
+
+/// Versions the frontend is created with, known to work together with the bundled
+/// templates; each can be overridden in the `[frontend]` section of the config
+const FRONTEND_DEFAULTS: [(&str, &str); 4] = [
+    ("create_vite_version", "5.5.2"),
+    ("vite_template", "react"),
+    ("arch_sdk_version", "0.0.5"),
+    ("sats_connect_version", "2.8.0"),
+];
+
+/// Template and package versions used to scaffold the frontend
+struct FrontendVersions {
+    /// `npm create` initializer, such as vite@5.5.2 for create-vite 5.5.2
+    create_vite: String,
+    template: String,
+    /// Packages the frontend needs on top of the Vite template, as `name@version` specs
+    packages: Vec<String>,
+}
+
+impl FrontendVersions {
+    fn from_config(config: &Config) -> Self {
+        let get = |key: &str| {
+            config
+                .get_string(&format!("frontend.{}", key))
+                .ok()
+                .filter(|value| !value.trim().is_empty())
+                .unwrap_or_else(|| {
+                    FRONTEND_DEFAULTS
+                        .iter()
+                        .find(|(name, _)| *name == key)
+                        .map(|(_, default)| default.to_string())
+                        .unwrap_or_default()
+                })
+        };
+        FrontendVersions {
+            create_vite: format!("vite@{}", get("create_vite_version")),
+            template: get("vite_template"),
+            packages: vec![
+                format!("sats-connect@{}", get("sats_connect_version")),
+                format!("@saturnbtcio/arch-sdk@{}", get("arch_sdk_version")),
+            ],
+        }
+    }
+}
+
+/// Creates the Vite frontend in `app_dir/frontend` and installs its dependencies. With
+/// `no_install`, nothing is downloaded and the commands to run later are printed instead.
+fn scaffold_frontend(app_dir: &Path, no_install: bool, config: &Config) -> Result<()> {
+    let versions = FrontendVersions::from_config(config);
+
+    if no_install {
+        say(format!(
+            "  {} Skipped creating the frontend. When you are online, run:",
+            "ℹ".bold().blue()
+        ));
+        say(format!("      cd {}", app_dir.display().to_string().yellow()));
+        say(format!("      npm create {} frontend -- --template {}", versions.create_vite, versions.template));
+        say(format!("      cd frontend && npm install && npm install --save-exact {}", versions.packages.join(" ")));
+        return Ok(());
+    }
+
+    // Create Vite app using the pinned create-vite release
+    say(format!("Creating Vite application with {}...", versions.create_vite));
     let create_vite_output = std::process::Command::new("npm")
-        .args(["create", "vite@latest", "frontend", "--", "--template", "react"])
-        .current_dir(&project_dir.join("app"))
+        .args(["create", &versions.create_vite, "frontend", "--", "--template", &versions.template])
+        .current_dir(app_dir)
         .output()
         .context("Failed to create Vite application")?;
 
@@ -792,10 +1824,10 @@ pub async fn create_project(args: &CreateProjectArgs, config: &Config) -> Result
         return Err(anyhow!("Failed to create Vite application: {}",
             String::from_utf8_lossy(&create_vite_output.stderr)));
     }
-    println!("  {} Created Vite application", "✓".bold().green());
+    say(format!("  {} Created Vite application", "✓".bold().green()));
 
     // Change to frontend directory and install base dependencies
-    let frontend_dir = project_dir.join("app/frontend");
+    let frontend_dir = app_dir.join("frontend");
     let install_output = std::process::Command::new("npm")
         .arg("install")
         .current_dir(&frontend_dir)
@@ -806,13 +1838,12 @@ pub async fn create_project(args: &CreateProjectArgs, config: &Config) -> Result
         return Err(anyhow!("Failed to install base dependencies: {}",
             String::from_utf8_lossy(&install_output.stderr)));
     }
-    println!("  {} Installed base dependencies", "✓".bold().green());
+    say(format!("  {} Installed base dependencies", "✓".bold().green()));
 
     // Install additional packages
-    let additional_packages = ["sats-connect", "@saturnbtcio/arch-sdk"];
     let install_additional_output = std::process::Command::new("npm")
-        .arg("install")
-        .args(&additional_packages)
+        .args(["install", "--save-exact"])
+        .args(&versions.packages)
         .current_dir(&frontend_dir)
         .output()
         .context("Failed to install additional packages")?;
@@ -821,19 +1852,11 @@ pub async fn create_project(args: &CreateProjectArgs, config: &Config) -> Result
         return Err(anyhow!("Failed to install additional packages: {}",
             String::from_utf8_lossy(&install_additional_output.stderr)));
     }
-    println!("  {} Installed additional packages", "✓".bold().green());
-
-    println!("{}", "Project created successfully! 🎉".bold().green());
-    println!("Project location: {:?}", project_dir);
-
-    println!("\n{}", "Next steps:".bold().yellow());
-    println!(
-        "  1. Navigate to {} to find the Rust program template",
-        project_dir.join("app").join("program").display().to_string().yellow()
-    );
-    println!("  2. Edit the source code to implement your program logic");
-    println!("  3. When ready, run {} to compile and deploy your program to the network", "arch-cli deploy".cyan());
-    println!("\n{}", "Need help? Check out our documentation at https://docs.arch.network".italic());
+    say(format!(
+        "  {} Installed {}",
+        "✓".bold().green(),
+        versions.packages.join(", ")
+    ));
 
     Ok(())
 }
@@ -855,19 +1878,136 @@ fn extract_recursive(dir: &Dir, target_path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Environment variable pointing at a local demo checkout to use instead of the bundled one
+pub const DEMO_SRC_ENV: &str = "ARCH_CLI_DEMO_SRC";
+
+/// Extracts the demo template (or its `subdir`) into `target_path`. When `ARCH_CLI_DEMO_SRC`
+/// is set, the files are copied from that directory instead of the copy embedded in the binary.
+pub(crate) fn extract_demo(subdir: Option<&str>, target_path: &Path) -> Result<()> {
+    match env::var_os(DEMO_SRC_ENV).filter(|value| !value.is_empty()) {
+        Some(source) => {
+            let source = PathBuf::from(source);
+            let source = subdir.map_or_else(|| source.clone(), |subdir| source.join(subdir));
+            if !source.is_dir() {
+                return Err(anyhow!("{} points to {:?}, which is not a directory", DEMO_SRC_ENV, source));
+            }
+            say(format!(
+                "  {} Copying demo files from {} ({})",
+                "ℹ".bold().blue(),
+                source.display().to_string().yellow(),
+                DEMO_SRC_ENV
+            ));
+            copy_recursive(&source, target_path)
+        }
+        None => {
+            let dir = match subdir {
+                Some(subdir) => PROJECT_DIR
+                    .get_dir(subdir)
+                    .ok_or_else(|| anyhow!("Bundled demo has no '{}' directory", subdir))?,
+                None => &PROJECT_DIR,
+            };
+            fs::create_dir_all(target_path)?;
+            extract_recursive(dir, target_path)
+        }
+    }
+}
+
+/// Copies a directory tree, skipping build output and installed packages
+fn copy_recursive(source: &Path, target_path: &Path) -> Result<()> {
+    fs::create_dir_all(target_path)?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let target = target_path.join(&name);
+        if entry.file_type()?.is_dir() {
+            if name == "target" || name == "node_modules" {
+                continue;
+            }
+            copy_recursive(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)
+                .with_context(|| format!("Failed to copy {:?} to {:?}", entry.path(), target))?;
+        }
+    }
+    Ok(())
+}
+
+/// Checks that `dir` can be created and written to
+fn check_dir_writable(dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir).with_context(|| format!("cannot create {:?}", dir))?;
+    tempfile::NamedTempFile::new_in(dir).with_context(|| format!("cannot write to {:?}", dir))?;
+    Ok(())
+}
+
+/// Returns a usable project directory, offering to reconfigure `project.directory`
+/// when the configured path cannot be created or written to
+fn ensure_writable_project_dir(mut base_dir: PathBuf) -> Result<PathBuf> {
+    loop {
+        let error = match check_dir_writable(&base_dir) {
+            Ok(()) => return Ok(base_dir),
+            Err(e) => e,
+        };
+
+        println!(
+            "  {} The configured project.directory {:?} is not usable: {:#}",
+            "⚠".bold().yellow(),
+            base_dir,
+            error
+        );
+
+        let reconfigure = !settings::non_interactive()
+            && confirm("Do you want to choose a different project directory?", true)?;
+
+        if !reconfigure {
+            return Err(anyhow!(
+                "project.directory {:?} is not writable: {:#}. Update it with 'arch-cli config edit'",
+                base_dir,
+                error
+            ));
+        }
+
+        base_dir = prompt_for_project_dir(&get_default_project_dir())?;
+        update_config_with_project_dir(&get_config_path()?, &base_dir)?;
+    }
+}
+
+/// Returns `project.directory` from the config. When it is unset, asks for a directory
+/// and saves it, or tells non-interactive runs to run `arch-cli init` first
+fn get_project_dir(config: &Config) -> Result<PathBuf> {
+    match config.get_string("project.directory") {
+        Ok(dir) if !dir.trim().is_empty() => return Ok(PathBuf::from(dir)),
+        _ => {}
+    }
+
+    if settings::non_interactive() {
+        return Err(anyhow!(
+            "project.directory is not set. Run 'arch-cli init' first, or set it with 'arch-cli config set project.directory <path>'"
+        ));
+    }
+
+    say(format!(
+        "  {} project.directory is not set; 'arch-cli init' has not been run yet",
+        "ℹ".bold().blue()
+    ));
+    let project_dir = prompt_for_project_dir(&get_default_project_dir())?;
+    update_config_with_project_dir(&get_config_path()?, &project_dir)?;
+    Ok(project_dir)
+}
+
 pub fn setup_base_structure(config: &Config) -> Result<(PathBuf, PathBuf)> {
     // Get base project directory from config
-    let base_dir = PathBuf::from(config.get_string("project.directory")?);
+    let configured_dir = get_project_dir(config)?;
+    let existed = configured_dir.exists();
 
-    // Create base directory if it doesn't exist
-    if !base_dir.exists() {
-        fs::create_dir_all(&base_dir)?;
-        println!("  {} Created base directory at {:?}", "✓".bold().green(), base_dir);
+    // Create the base directory, making sure it is writable before extracting anything
+    let base_dir = ensure_writable_project_dir(configured_dir)?;
+    if !existed {
+        say(format!("  {} Created base directory at {:?}", "✓".bold().green(), base_dir));
     }
 
     // Create shared libraries at base directory level
-    println!("  {} Setting up shared libraries...", "ℹ".bold().blue());
-    for lib in &["bip322", "common", "program"] {
+    say(format!("  {} Setting up shared libraries...", "ℹ".bold().blue()));
+    for lib in &SHARED_LIBRARIES {
         let source_dir = TEMPLATES_DIR.get_dir(lib)
             .ok_or_else(|| anyhow!("Template directory '{}' not found", lib))?;
         let lib_dir = base_dir.join(lib);
@@ -890,13 +2030,13 @@ pub fn setup_base_structure(config: &Config) -> Result<(PathBuf, PathBuf)> {
             }
         }
 
-        println!("  {} Created shared library {} at {:?}", "✓".bold().green(), lib, lib_dir);
+        detail(format!("  {} Created shared library {} at {:?}", "✓".bold().green(), lib, lib_dir));
     }
 
     // Create projects directory
     let projects_dir = base_dir.join("projects");
     fs::create_dir_all(&projects_dir)?;
-    println!("  {} Created projects directory at {:?}", "✓".bold().green(), projects_dir);
+    say(format!("  {} Created projects directory at {:?}", "✓".bold().green(), projects_dir));
 
     Ok((base_dir, projects_dir))
 }
@@ -907,12 +2047,32 @@ fn get_config_path() -> Result<PathBuf> {
         .unwrap_or_else(|_| {
             let mut default_path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
             default_path.push("arch-cli");
-            default_path.push("config.toml");
+            default_path.push(config_file_name(settings::config_profile().as_deref()));
             default_path
         });
     Ok(config_path)
 }
 
+/// File name of a configuration profile: config.toml by default, config.<name>.toml otherwise
+fn config_file_name(profile: Option<&str>) -> String {
+    match profile {
+        Some(name) => format!("config.{}.toml", name),
+        None => "config.toml".to_string(),
+    }
+}
+
+/// Returns the profile name of a file in the config directory, if it is a configuration file
+fn config_profile_of(file_name: &str) -> Option<String> {
+    if file_name == "config.toml" {
+        return Some("default".to_string());
+    }
+    file_name
+        .strip_prefix("config.")
+        .and_then(|rest| rest.strip_suffix(".toml"))
+        .filter(|name| !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+        .map(str::to_string)
+}
+
 fn get_docker_compose_command() -> (&'static str, &'static [&'static str]) {
     if Command::new("docker-compose")
         .arg("--version")
@@ -925,65 +2085,80 @@ fn get_docker_compose_command() -> (&'static str, &'static [&'static str]) {
     }
 }
 
-fn check_dependencies() -> Result<()> {
-    println!("{}", "Checking required dependencies...".bold().blue());
+/// Outcome of one environment check, such as a required tool being installed
+struct EnvironmentCheck {
+    name: String,
+    version: Option<String>,
+    /// Why the check failed, None when it passed
+    error: Option<String>,
+}
+
+static DEPENDENCIES: &[(&str, &[&[&str]], &str)] = &[
+    ("docker", &[&["docker", "--version"]], "Docker is not installed. Please install Docker."),
+    (
+        "docker-compose",
+        &[
+            &["docker-compose", "--version"],
+            &["docker", "compose", "--version"],
+        ],
+        "Neither docker-compose nor docker compose is available. Please install Docker Compose."
+    ),
+    ("node", &[&["node", "--version"]], "Node.js is not installed or version is below 19. Please install Node.js version 19 or higher."),
+    ("solana", &[&["solana", "--version"]], "Solana CLI is not installed. Please install Solana CLI."),
+    ("cargo", &[&["cargo", "--version"]], "Rust and Cargo are not installed. Please install Rust and Cargo."),
+];
+
+/// Runs the version command of a required tool, trying each alternative in turn
+fn check_dependency(name: &str, commands: &[&[&str]], error_message: &str) -> EnvironmentCheck {
+    let version = commands.iter().find_map(|command| {
+        match Command::new(command[0]).args(&command[1..]).output() {
+            Ok(output) if output.status.success() => {
+                Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+            }
+            _ => None,
+        }
+    });
 
-    static DEPENDENCIES: &[(&str, &[&[&str]], &str)] = &[
-        ("docker", &[&["docker", "--version"]], "Docker is not installed. Please install Docker."),
-        (
-            "docker-compose",
-            &[
-                &["docker-compose", "--version"],
-                &["docker", "compose", "--version"],
-            ],
-            "Neither docker-compose nor docker compose is available. Please install Docker Compose."
-        ),
-        ("node", &[&["node", "--version"]], "Node.js is not installed or version is below 19. Please install Node.js version 19 or higher."),
-        ("solana", &[&["solana", "--version"]], "Solana CLI is not installed. Please install Solana CLI."),
-        ("cargo", &[&["cargo", "--version"]], "Rust and Cargo are not installed. Please install Rust and Cargo."),
-    ];
+    // Additional check for Node.js version
+    let too_old = name == "node"
+        && version.as_deref().map_or(false, |version| {
+            let version_str = version.split('v').nth(1).unwrap_or("").trim();
+            let major_version = version_str
+                .split('.')
+                .next()
+                .unwrap_or("0")
+                .parse::<u32>()
+                .unwrap_or(0);
+            major_version < 19
+        });
+
+    EnvironmentCheck {
+        name: name.to_string(),
+        error: (version.is_none() || too_old).then(|| error_message.to_string()),
+        version,
+    }
+}
 
-    for (name, commands, error_message) in DEPENDENCIES.iter() {
+/// Checks the required tools, stopping at the first missing one. Node.js is only
+/// required when a frontend will be scaffolded.
+fn check_dependencies(frontend: bool) -> Result<()> {
+    println!("{}", "Checking required dependencies...".bold().blue());
+
+    for (name, commands, error_message) in DEPENDENCIES.iter().filter(|(name, _, _)| frontend || *name != "node") {
         print!("  {} Checking {}...", "→".bold().blue(), name);
         io::stdout().flush()?;
 
-        let mut success = false;
-        let mut version = String::new();
-
-        for command in *commands {
-            match Command::new(command[0]).args(&command[1..]).output() {
-                Ok(output) if output.status.success() => {
-                    version = String::from_utf8_lossy(&output.stdout).to_string();
-                    success = true;
-                    break;
-                }
-                _ => continue,
+        let check = check_dependency(name, commands, error_message);
+        match check.error {
+            None => {
+                println!(" {}", "✓".bold().green());
+                println!("    Detected version: {}", check.version.unwrap_or_default());
             }
-        }
-
-        if success {
-            println!(" {}", "✓".bold().green());
-            println!("    Detected version: {}", version.trim());
-
-            // Additional check for Node.js version
-            if *name == "node" {
-                let version_str = version.split('v').nth(1).unwrap_or("").trim();
-                let major_version = version_str
-                    .split('.')
-                    .next()
-                    .unwrap_or("0")
-                    .parse::<u32>()
-                    .unwrap_or(0);
-                if major_version < 19 {
-                    println!(" {}", "✗".bold().red());
-                    println!("{}", error_message);
-                    return Err(anyhow::Error::msg(error_message));
-                }
+            Some(error_message) => {
+                println!(" {}", "✗".bold().red());
+                println!("{}", error_message);
+                return Err(anyhow::Error::msg(error_message));
             }
-        } else {
-            println!(" {}", "✗".bold().red());
-            println!("{}", error_message);
-            return Err(anyhow::Error::msg(error_message));
         }
     }
 
@@ -994,18 +2169,93 @@ fn check_dependencies() -> Result<()> {
     Ok(())
 }
 
+/// Checks the required tools, the Docker daemon and the configuration file, reporting every
+/// problem instead of stopping at the first one. With `--output json`, prints one entry per check.
+pub async fn doctor() -> Result<()> {
+    let mut checks: Vec<EnvironmentCheck> = DEPENDENCIES
+        .iter()
+        .map(|(name, commands, error_message)| check_dependency(name, commands, error_message))
+        .collect();
+
+    // The docker CLI can be installed while the daemon is not running
+    let daemon = Command::new("docker")
+        .args(["info", "--format", "{{.ServerVersion}}"])
+        .output();
+    checks.push(match daemon {
+        Ok(output) if output.status.success() => EnvironmentCheck {
+            name: "docker-daemon".to_string(),
+            version: Some(String::from_utf8_lossy(&output.stdout).trim().to_string()),
+            error: None,
+        },
+        _ => EnvironmentCheck {
+            name: "docker-daemon".to_string(),
+            version: None,
+            error: Some("The Docker daemon is not running or not reachable. Start Docker.".to_string()),
+        },
+    });
+
+    let config_path = get_config_path()?;
+    let problems = if config_path.exists() { config_problems(&config_path) } else { Vec::new() };
+    checks.push(EnvironmentCheck {
+        name: "config".to_string(),
+        version: None,
+        error: (!problems.is_empty()).then(|| problems.join("; ")),
+    });
+
+    let failed = checks.iter().filter(|check| check.error.is_some()).count();
+
+    if json_output() {
+        let report: serde_json::Map<String, Value> = checks
+            .into_iter()
+            .map(|check| {
+                let mut entry = json!({ "ok": check.error.is_none() });
+                if let Some(version) = check.version {
+                    entry["version"] = json!(version);
+                }
+                if let Some(error) = check.error {
+                    entry["error"] = json!(error);
+                }
+                (check.name, entry)
+            })
+            .collect();
+        print_json(&Value::Object(report))?;
+    } else {
+        println!("{}", "Checking the environment...".bold().blue());
+        for check in &checks {
+            match &check.error {
+                None => println!(
+                    "  {} {}{}",
+                    "✓".bold().green(),
+                    check.name,
+                    check.version.as_ref().map_or_else(String::new, |version| format!(": {}", version))
+                ),
+                Some(error) => println!("  {} {}: {}", "✗".bold().red(), check.name, error),
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(anyhow!("{} environment check(s) failed", failed));
+    }
+    if !json_output() {
+        println!("{}", "Everything looks good.".bold().green());
+    }
+    Ok(())
+}
+
 fn _start_or_create_services(service_name: &str, service_config: &ServiceConfig) -> Result<()> {
-    println!(
+    say(format!(
         "  {} Starting {}...",
         "→".bold().blue(),
         service_name.yellow()
-    );
+    ));
 
     let mut all_containers_exist = true;
     let mut all_containers_running = true;
 
     for container in &service_config.services {
         let ps_output = Command::new("docker-compose")
+            .args(settings::compose_project_args("server"))
             .args([
                 "-f",
                 &service_config.docker_compose_file,
@@ -1042,18 +2292,19 @@ fn _start_or_create_services(service_name: &str, service_config: &ServiceConfig)
 
     if all_containers_exist {
         if all_containers_running {
-            println!(
+            say(format!(
                 "  {} All {} containers are already running.",
                 "✓".bold().green(),
                 service_name.yellow()
-            );
+            ));
         } else {
-            println!(
+            say(format!(
                 "  {} Existing {} containers found. Starting them...",
                 "→".bold().blue(),
                 service_name.yellow()
-            );
+            ));
             let start_output = Command::new("docker-compose")
+                .args(settings::compose_project_args("server"))
                 .args(["-f", &service_config.docker_compose_file, "start"])
                 .output()
                 .context(format!(
@@ -1071,20 +2322,21 @@ fn _start_or_create_services(service_name: &str, service_config: &ServiceConfig)
                 );
                 return Err(anyhow!("Failed to start some {} containers", service_name));
             } else {
-                println!(
+                say(format!(
                     "  {} {} containers started successfully.",
                     "✓".bold().green(),
                     service_name.yellow()
-                );
+                ));
             }
         }
     } else {
-        println!(
+        say(format!(
             "  {} Some or all {} containers are missing. Creating and starting new ones...",
             "ℹ".bold().blue(),
             service_name.yellow()
-        );
+        ));
         let up_output = Command::new("docker-compose")
+            .args(settings::compose_project_args("server"))
             .args([
                 "--progress",
                 "auto",
@@ -1127,125 +2379,641 @@ fn _start_or_create_services(service_name: &str, service_config: &ServiceConfig)
                 service_name
             ));
         } else {
-            println!(
+            say(format!(
                 "  {} {} containers created and started successfully.",
                 "✓".bold().green(),
                 service_name.yellow()
-            );
+            ));
         }
     }
 
     Ok(())
 }
 
-pub async fn server_start(config: &Config) -> Result<()> {
-    println!("{}", "Starting the server...".bold().green());
+/// Returns the Docker Compose project name used for a server compose file: the server stack's
+/// project derived from `--project-name` or `docker.project_name`, otherwise its normalized directory name
+fn compose_project_name(compose_file: &Path) -> String {
+    if let Some(name) = settings::compose_project_name("server") {
+        return name;
+    }
+    compose_file
+        .parent()
+        .and_then(|dir| dir.file_name())
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect()
+}
 
-    let arch_data_dir = get_arch_data_dir(config)?;
-    env::set_var("ARCH_DATA_DIR", arch_data_dir.to_str().unwrap());
+/// Lists running Compose-managed containers as (name, project, config files)
+fn running_compose_containers() -> Result<Vec<(String, String, String)>> {
+    let output = Command::new("docker")
+        .args([
+            "ps",
+            "--filter",
+            "label=com.docker.compose.project",
+            "--format",
+            "{{.Names}}\t{{.Label \"com.docker.compose.project\"}}\t{{.Label \"com.docker.compose.project.config_files\"}}",
+        ])
+        .output()
+        .context("Failed to list running containers")?;
 
-    let selected_network = config.get_string("selected_network")
-        .unwrap_or_else(|_| "development".to_string());
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Failed to list running containers: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
 
-    set_env_vars(config, &selected_network)?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            Some((
+                fields.next()?.to_string(),
+                fields.next()?.to_string(),
+                fields.next().unwrap_or_default().to_string(),
+            ))
+        })
+        .collect())
+}
 
-    let docker_compose_file = config.get_string(&format!("networks.{}.docker_compose_file", selected_network))?;
-    let docker_compose_file = format!("{}/{}", config.get_string("config_dir")?, docker_compose_file);
+/// A container belonging to one of the stacks managed by arch-cli
+struct StackContainer {
+    name: String,
+    service: String,
+    state: String,
+    status: String,
+    ports: String,
+}
 
-    let (docker_compose_cmd, docker_compose_args) = get_docker_compose_command();
+/// Reports the containers of the server, validator, indexer and demo stacks in one overview
+pub async fn status(config: &Config) -> Result<()> {
+    let output = Command::new("docker")
+        .args([
+            "ps",
+            "-a",
+            "--format",
+            "{{.Names}}\t{{.State}}\t{{.Status}}\t{{.Ports}}\t{{.Label \"com.docker.compose.service\"}}\t{{.Label \"com.docker.compose.project\"}}\t{{.Label \"com.docker.compose.project.config_files\"}}",
+        ])
+        .output()
+        .context("Failed to list containers")?;
 
-    println!("  {} Starting services...", "→".bold().blue());
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Failed to list containers: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
 
-    let output = Command::new(docker_compose_cmd)
-        .args(docker_compose_args)
-        .args(["-f", &docker_compose_file, "up", "-d"])
-        .env("ARCH_DATA_DIR", arch_data_dir.to_str().unwrap())
-        .status()?;
+    // Services started by `server start` come from the network and arch service lists
+    let server_services: Vec<String> = ["services", "arch.services"]
+        .iter()
+        .filter_map(|key| config.get_array(key).ok())
+        .flatten()
+        .map(|value| value.to_string())
+        .collect();
 
-    if !output.success() {
-        return Err(anyhow!("Failed to start services"));
+    // Without a project name, Compose names the indexer's project after its directory
+    let indexer_project = settings::compose_project_name("indexer").unwrap_or_else(|| "arch-indexer".to_string());
+
+    let stack_names = ["server", "validator", "indexer", "demo"];
+    let mut stacks: Vec<Vec<StackContainer>> = stack_names.iter().map(|_| Vec::new()).collect();
+
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let field = |index: usize| fields.get(index).copied().unwrap_or_default().to_string();
+        let container = StackContainer {
+            name: field(0),
+            state: field(1),
+            status: field(2),
+            ports: field(3),
+            service: field(4),
+        };
+        let (project, config_files) = (field(5), field(6));
+
+        let stack = if container.name == "local_validator" {
+            1
+        } else if project == indexer_project {
+            2
+        } else if config_files.contains("demo-docker-compose.yml") {
+            3
+        } else if server_services.contains(&container.service) || server_services.contains(&container.name) {
+            0
+        } else {
+            continue;
+        };
+        stacks[stack].push(container);
+    }
+
+    if json_output() {
+        let mut overview = serde_json::Map::new();
+        for (name, containers) in stack_names.iter().zip(&stacks) {
+            let services: Vec<Value> = containers
+                .iter()
+                .map(|container| {
+                    json!({
+                        "name": container.name,
+                        "service": container.service,
+                        "running": container.state == "running",
+                        "state": container.state,
+                        "status": container.status,
+                        "ports": container.ports,
+                    })
+                })
+                .collect();
+            overview.insert(
+                name.to_string(),
+                json!({
+                    "running": !containers.is_empty() && containers.iter().all(|c| c.state == "running"),
+                    "services": services,
+                }),
+            );
+        }
+        return print_json(&Value::Object(overview));
     }
 
-    println!(
-        "  {} Development server started successfully.",
-        "✓".bold().green()
-    );
+    println!("{}", "Arch Network status:".bold().green());
+    for (name, containers) in stack_names.iter().zip(&stacks) {
+        println!("  {} {}", "→".bold().blue(), name.bold());
+        if containers.is_empty() {
+            println!("    {} not started", "ℹ".bold().blue());
+            continue;
+        }
+        for container in containers {
+            let marker = if container.state == "running" {
+                "✓".bold().green()
+            } else {
+                "✗".bold().red()
+            };
+            println!("    {} {} ({})", marker, container.name, container.status);
+            if !container.ports.is_empty() {
+                println!("      Ports: {}", container.ports);
+            }
+        }
+    }
 
     Ok(())
 }
 
-pub async fn server_stop(config: &Config) -> Result<()> {
-    println!("{}", "Stopping the development server...".bold().green());
-
-    let selected_network = config.get_string("selected_network")
-        .unwrap_or_else(|_| "development".to_string());
-
-    set_env_vars(config, &selected_network)?;
+/// Returns the compose file for the server stack: the `--compose-file` override after
+/// checking that Docker Compose accepts it, or the one configured for the network
+fn resolve_compose_file(override_file: Option<&Path>, config: &Config, selected_network: &str) -> Result<String> {
+    let compose_file = match override_file {
+        Some(path) => path,
+        None => {
+            let docker_compose_file = config.get_string(&format!("networks.{}.docker_compose_file", selected_network))?;
+            return Ok(format!("{}/{}", config.get_string("config_dir")?, docker_compose_file));
+        }
+    };
 
-    let docker_compose_file = config.get_string(&format!("networks.{}.docker_compose_file", selected_network))?;
-    let docker_compose_file = format!("{}/{}", config.get_string("config_dir")?, docker_compose_file);
+    if !compose_file.is_file() {
+        return Err(anyhow!("Compose file not found: {}", compose_file.display()));
+    }
+    let compose_file = compose_file
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", compose_file.display()))?;
+    let compose_file = compose_file.to_string_lossy().into_owned();
 
     let (docker_compose_cmd, docker_compose_args) = get_docker_compose_command();
-
-    println!("  {} Stopping services...", "→".bold().blue());
-
     let output = Command::new(docker_compose_cmd)
         .args(docker_compose_args)
-        .args(["-f", &docker_compose_file, "down", "-v", "--remove-orphans"])
-        .status()?;
-
-    if !output.success() {
-        return Err(anyhow!("Failed to stop services"));
+        .args(settings::compose_project_args("server"))
+        .args(["-f", &compose_file, "config", "--quiet"])
+        .output()
+        .context("Failed to run docker compose to validate the compose file")?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Invalid compose file {}: {}",
+            compose_file,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
     }
 
-    println!(
-        "  {} Development server stopped successfully.",
+    say(format!("  {} Using compose file {}", "ℹ".bold().blue(), compose_file.yellow()));
+    Ok(compose_file)
+}
+
+pub async fn server_start(args: &ServerStartArgs, config: &Config) -> Result<()> {
+    say("Starting the server...".bold().green());
+
+    let arch_data_dir = get_arch_data_dir(config)?;
+    env::set_var("ARCH_DATA_DIR", arch_data_dir.to_str().unwrap());
+
+    let selected_network = selected_network(config);
+
+    set_env_vars(config, &selected_network)?;
+
+    let docker_compose_file = resolve_compose_file(args.compose_file.as_deref(), config, &selected_network)?;
+
+    let (docker_compose_cmd, docker_compose_args) = get_docker_compose_command();
+
+    // Check whether our stack is already up before creating any containers
+    let compose_path = Path::new(&docker_compose_file);
+    let project_name = compose_project_name(compose_path);
+    let compose_file_name = compose_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let running = running_compose_containers()?;
+
+    let (ours, others): (Vec<_>, Vec<_>) = running
+        .into_iter()
+        .filter(|(_, _, config_files)| config_files.contains(&compose_file_name))
+        .partition(|(_, project, _)| *project == project_name);
+
+    for (name, project, config_files) in &others {
+        println!(
+            "  {} Container {} from compose project '{}' ({}) is also running and may conflict on ports",
+            "⚠".bold().yellow(),
+            name.yellow(),
+            project,
+            config_files
+        );
+    }
+
+    if !ours.is_empty() && !args.force {
+        say(format!("  {} The development server is already running:", "ℹ".bold().blue()));
+        for (name, _, _) in &ours {
+            say(format!("    {} {}", "✓".bold().green(), name.yellow()));
+        }
+        say(format!(
+            "  {} Use 'arch-cli server start --force' to recreate the containers",
+            "→".bold().blue()
+        ));
+        return Ok(());
+    }
+
+    // Our own containers hold their ports when recreating, so only check a stack that is down
+    timing_phase("checking host ports");
+    if !args.skip_port_check && ours.is_empty() {
+        check_host_ports(&docker_compose_file)?;
+    }
+
+    say(format!("  {} Starting services...", "→".bold().blue()));
+    timing_phase("starting services");
+
+    // Start the services one at a time only when progress is reported per service, otherwise
+    // let compose bring the whole stack up and order it by its dependencies
+    if progress_json() {
+        let services_output = Command::new(docker_compose_cmd)
+            .args(docker_compose_args)
+            .args(settings::compose_project_args("server"))
+            .args(["-f", &docker_compose_file, "config", "--services"])
+            .env("ARCH_DATA_DIR", arch_data_dir.to_str().unwrap())
+            .output()
+            .context("Failed to list compose services")?;
+        if !services_output.status.success() {
+            return Err(anyhow!(
+                "Failed to list compose services: {}",
+                String::from_utf8_lossy(&services_output.stderr)
+            ));
+        }
+        let services: Vec<String> = String::from_utf8_lossy(&services_output.stdout)
+            .lines()
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect();
+
+        for (index, service) in services.iter().enumerate() {
+            progress_event("server-start", index, services.len(), &format!("Starting {}", service));
+            say(format!("    {} Starting {}...", "→".bold().blue(), service.yellow()));
+
+            let mut up_args = vec!["-f", docker_compose_file.as_str(), "up", "-d"];
+            if args.force {
+                up_args.push("--force-recreate");
+            }
+            up_args.push(service.as_str());
+
+            let output = Command::new(docker_compose_cmd)
+                .args(docker_compose_args)
+                .args(settings::compose_project_args("server"))
+                .args(&up_args)
+                .env("ARCH_DATA_DIR", arch_data_dir.to_str().unwrap())
+                .status()?;
+
+            if !output.success() {
+                return Err(anyhow!("Failed to start service {}", service));
+            }
+        }
+        progress_event("server-start", services.len(), services.len(), "Services started");
+    } else {
+        let mut up_args = vec!["-f", docker_compose_file.as_str(), "up", "-d"];
+        if args.force {
+            up_args.push("--force-recreate");
+        }
+
+        let status = Command::new(docker_compose_cmd)
+            .args(docker_compose_args)
+            .args(settings::compose_project_args("server"))
+            .args(&up_args)
+            .env("ARCH_DATA_DIR", arch_data_dir.to_str().unwrap())
+            .status()?;
+
+        if !status.success() {
+            return Err(anyhow!("Failed to start the development server services"));
+        }
+    }
+
+    if args.wait {
+        timing_phase("waiting for the nodes");
+        wait_for_nodes(config, Duration::from_secs(args.wait_timeout)).await?;
+    }
+
+    say(format!(
+        "  {} Development server started successfully.",
         "✓".bold().green()
+    ));
+
+    Ok(())
+}
+
+/// Polls the Arch leader RPC and the Bitcoin RPC until both respond, so commands run right after
+/// `server start` do not race the nodes' startup. Fails naming the services that never came up.
+async fn wait_for_nodes(config: &Config, timeout: Duration) -> Result<()> {
+    let leader_rpc = get_rpc_url_with_fallback(None, config)?;
+    let bitcoin_rpc = format!(
+        "http://{}:{}",
+        config.get_string("bitcoin_rpc_endpoint")?,
+        config.get_string("bitcoin_rpc_port")?
     );
+    let bitcoin_client = Client::new(
+        &bitcoin_rpc,
+        bitcoincore_rpc::Auth::UserPass(
+            config.get_string("bitcoin_rpc_user")?,
+            config.get_string("bitcoin_rpc_password")?,
+        ),
+    )
+    .context("Failed to create the Bitcoin RPC client")?;
+
+    say(format!("  {} Waiting for the nodes to accept RPC requests...", "→".bold().blue()));
+
+    let deadline = tokio::time::Instant::now() + timeout;
+    let mut leader_error = Some(String::new());
+    let mut bitcoin_error = Some(String::new());
+
+    loop {
+        if leader_error.is_some() {
+            leader_error = probe_rpc_url(&leader_rpc).await.err().map(|e| e.to_string());
+            if leader_error.is_none() {
+                say(format!("    {} Arch leader RPC is ready at {}", "✓".bold().green(), leader_rpc.yellow()));
+            }
+        }
+        if bitcoin_error.is_some() {
+            bitcoin_error = bitcoin_client.get_blockchain_info().err().map(|e| e.to_string());
+            if bitcoin_error.is_none() {
+                say(format!("    {} Bitcoin RPC is ready at {}", "✓".bold().green(), bitcoin_rpc.yellow()));
+            }
+        }
+
+        if leader_error.is_none() && bitcoin_error.is_none() {
+            return Ok(());
+        }
+
+        if tokio::time::Instant::now() >= deadline {
+            let pending: Vec<String> = [
+                leader_error.map(|e| format!("Arch leader RPC at {} ({})", leader_rpc, e)),
+                bitcoin_error.map(|e| format!("Bitcoin RPC at {} ({})", bitcoin_rpc, e)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+            return Err(anyhow!(
+                "Timed out after {}s waiting for {}. Check 'arch-cli server logs', or run without --wait to skip this check",
+                timeout.as_secs(),
+                pending.join(" and ")
+            ));
+        }
+
+        tokio::time::sleep(Duration::from_secs(2)).await;
+    }
+}
+
+pub async fn server_stop(args: &ServerStopArgs, config: &Config) -> Result<()> {
+    say("Stopping the development server...".bold().green());
+
+    let selected_network = selected_network(config);
+
+    set_env_vars(config, &selected_network)?;
+
+    let docker_compose_file = resolve_compose_file(args.compose_file.as_deref(), config, &selected_network)?;
+
+    let (docker_compose_cmd, docker_compose_args) = get_docker_compose_command();
+
+    let mut down_args = vec!["-f", docker_compose_file.as_str(), "down", "--remove-orphans"];
+    if args.purge {
+        say(format!("  {} Stopping services and deleting their volumes...", "→".bold().blue()));
+        down_args.push("-v");
+    } else {
+        say(format!("  {} Stopping services...", "→".bold().blue()));
+    }
+
+    let output = Command::new(docker_compose_cmd)
+        .args(docker_compose_args)
+        .args(settings::compose_project_args("server"))
+        .args(&down_args)
+        .status()?;
+
+    if !output.success() {
+        return Err(anyhow!("Failed to stop services"));
+    }
+
+    if !args.purge {
+        say(format!(
+            "  {} Volumes were kept. Use 'arch-cli server stop --purge' to delete the chain state",
+            "ℹ".bold().blue()
+        ));
+    }
+
+    say(format!(
+        "  {} Development server stopped successfully.",
+        "✓".bold().green()
+    ));
+
+    Ok(())
+}
+
+pub async fn server_restart(args: &ServerRestartArgs, config: &Config) -> Result<()> {
+    say("Restarting the development server...".bold().green());
+
+    let arch_data_dir = get_arch_data_dir(config)?;
+    env::set_var("ARCH_DATA_DIR", arch_data_dir.to_str().unwrap());
+
+    let selected_network = selected_network(config);
+    set_env_vars(config, &selected_network)?;
+
+    let docker_compose_file = resolve_compose_file(args.compose_file.as_deref(), config, &selected_network)?;
+    let (docker_compose_cmd, docker_compose_args) = get_docker_compose_command();
+
+    if args.recreate {
+        say(format!("  {} Taking the services down, keeping their volumes...", "→".bold().blue()));
+        timing_phase("stopping services");
+        let status = Command::new(docker_compose_cmd)
+            .args(docker_compose_args)
+            .args(settings::compose_project_args("server"))
+            .args(["-f", &docker_compose_file, "down", "--remove-orphans"])
+            .status()?;
+        if !status.success() {
+            return Err(anyhow!("Failed to stop services"));
+        }
+
+        let start_args = ServerStartArgs {
+            compose_file: args.compose_file.clone(),
+            wait: args.wait,
+            wait_timeout: args.wait_timeout,
+            ..Default::default()
+        };
+        return server_start(&start_args, config).await;
+    }
+
+    // `docker compose restart` succeeds without doing anything when the stack is down
+    let compose_path = Path::new(&docker_compose_file);
+    let project_name = compose_project_name(compose_path);
+    let compose_file_name = compose_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let running = running_compose_containers()?
+        .into_iter()
+        .any(|(_, project, config_files)| project == project_name && config_files.contains(&compose_file_name));
+    if !running {
+        return Err(anyhow!(
+            "The development server is not running. Start it with 'arch-cli server start'"
+        ));
+    }
+
+    say(format!("  {} Restarting services...", "→".bold().blue()));
+    timing_phase("restarting services");
+    let status = Command::new(docker_compose_cmd)
+        .args(docker_compose_args)
+        .args(settings::compose_project_args("server"))
+        .args(["-f", &docker_compose_file, "restart"])
+        .status()?;
+    if !status.success() {
+        return Err(anyhow!("Failed to restart services"));
+    }
+
+    if args.wait {
+        timing_phase("waiting for the nodes");
+        wait_for_nodes(config, Duration::from_secs(args.wait_timeout)).await?;
+    }
+
+    say(format!(
+        "  {} Development server restarted successfully.",
+        "✓".bold().green()
+    ));
 
     Ok(())
 }
 
 pub async fn deploy(args: &DeployArgs, config: &Config) -> Result<()> {
-    println!("{}", "Deploying program...".bold().green());
+    if let Some(program_id) = &args.verify_only {
+        return verify_deployed_program(args, program_id, config).await;
+    }
+
+    if args.dry_run {
+        say("Estimating program deployment (dry run)...".bold().green());
+    } else {
+        say("Deploying program...".bold().green());
+    }
+
+    let phase = Cell::new("starting");
+    let secs = match args.deploy_timeout {
+        Some(secs) => secs,
+        None => return run_deploy(args, config, &phase).await,
+    };
+
+    // Dropping the deployment future on expiry cancels whatever step is awaiting,
+    // including a running compiler, which is spawned with kill_on_drop
+    match tokio::time::timeout(Duration::from_secs(secs), run_deploy(args, config, &phase)).await {
+        Ok(result) => result,
+        Err(_) => Err(anyhow!(
+            "Deployment timed out after {}s while {}",
+            secs,
+            phase.get()
+        )),
+    }
+}
+
+/// Records the deployment step in progress for timeout reporting and `--timings`
+fn enter_phase(phase: &Cell<&'static str>, name: &'static str) {
+    phase.set(name);
+    timing_phase(name);
+}
+
+/// Runs the deployment steps, recording the current one in `phase` for timeout reporting
+async fn run_deploy(args: &DeployArgs, config: &Config, phase: &Cell<&'static str>) -> Result<()> {
+
+    // The Arch runtime has no compute-budget instruction yet, so there is nothing to
+    // prepend to the deployment transactions. Refuse rather than silently ignoring it.
+    if let Some(fee) = args.priority_fee.filter(|fee| *fee > 0) {
+        return Err(anyhow!(
+            "--priority-fee {} is not supported: the Arch Network runtime does not expose a compute-budget instruction yet",
+            fee
+        ));
+    }
+
+    // Keeps a downloaded binary on disk until the deployment is done
+    let mut downloaded_elf = None;
+
+    enter_phase(phase, "locating the program binary");
+
+    // Project the program ID is written to after deploying, when deploying from source
+    let mut project_root = None;
 
     // Find the program binary or compile from source
-    let program_path = if let Some(dir) = &args.directory {
-        PathBuf::from(dir)
+    let program_path = if let Some(url) = &args.elf_url {
+        let elf_file = download_elf(url, args.elf_sha256.as_deref()).await?;
+        let path = elf_file.path().to_path_buf();
+        downloaded_elf = Some(elf_file);
+        path
+    } else if let Some(dir) = &args.directory {
+        let path = PathBuf::from(dir);
+        if path.is_dir() {
+            project_root = Some(path.clone());
+        }
+        path
     } else {
         // Get project directory from config
-        let project_dir = PathBuf::from(config.get_string("project.directory")?);
+        let project_dir = get_project_dir(config)?;
         let projects_dir = project_dir.join("projects");
 
-        // Get list of projects
-        let projects: Vec<_> = fs::read_dir(&projects_dir)?
+        // Get list of projects along with their program crate
+        let mut projects: Vec<(String, PathBuf)> = fs::read_dir(&projects_dir)?
             .filter_map(|entry| {
-                entry.ok().and_then(|e| {
-                    let path = e.path();
-                    if path.is_dir() && path.join("app/program").exists() {
-                        Some(path.file_name().unwrap().to_string_lossy().into_owned())
-                    } else {
-                        None
-                    }
-                })
+                let path = entry.ok()?.path();
+                if !path.is_dir() {
+                    return None;
+                }
+                let program_dir = match &args.program_dir {
+                    Some(relative) => Some(path.join(relative)).filter(|dir| dir.join("Cargo.toml").exists()),
+                    None => find_program_crate(&path),
+                }?;
+                Some((path.file_name()?.to_string_lossy().into_owned(), program_dir))
             })
             .collect();
+        projects.sort();
 
         if projects.is_empty() {
-            return Err(anyhow!("No deployable projects found. Make sure your projects have an 'app/program' folder."));
+            return Err(anyhow!("No deployable projects found. Make sure your projects contain an Arch program crate, or point at it with --program-dir."));
         }
 
         // Ask user to select a project
+        require_interactive("Selecting a project to deploy (pass --directory instead)")?;
+        let names: Vec<&String> = projects.iter().map(|(name, _)| name).collect();
         let selection = Select::new()
             .with_prompt("Select a project to deploy")
-            .items(&projects)
+            .items(&names)
             .interact()?;
 
-        let selected_project = &projects[selection];
-        projects_dir.join(selected_project).join("app/program")
+        let (name, program_dir) = projects.swap_remove(selection);
+        project_root = Some(projects_dir.join(name));
+        program_dir
     };
 
     // Handle program key selection
+    enter_phase(phase, "selecting the program key");
     let secp = Secp256k1::new();
     let keys_file = get_config_dir()?.join("keys.json");
 
@@ -1264,7 +3032,7 @@ pub async fn deploy(args: &DeployArgs, config: &Config) -> Result<()> {
     } else {
         // Show key selection menu
         let mut keys: Value = if keys_file.exists() {
-            serde_json::from_str(&fs::read_to_string(&keys_file)?)?
+            load_keys(&keys_file)?
         } else {
             json!({})
         };
@@ -1277,129 +3045,972 @@ pub async fn deploy(args: &DeployArgs, config: &Config) -> Result<()> {
         &XOnlyPublicKey::from_keypair(&program_keypair).0.serialize()
     );
 
-    println!("Program ID: {}", program_pubkey);
-
-    // Set up Bitcoin RPC client and handle funding
-    let wallet_manager = WalletManager::new(config)?;
-    ensure_wallet_balance(&wallet_manager.client).await?;
-
-    // Deploy the program
-    let rpc_url = get_rpc_url_with_fallback(args.rpc_url.clone(), config).unwrap();
-    println!("Using RPC URL: {}", rpc_url);
+    say(format!("Program ID: {}", program_pubkey));
 
     // Get the program binary path
     let elf_path = if program_path.is_file() {
         program_path
     } else {
         // Compile from source
-        println!("  {} Compiling program...", "→".bold().blue());
-        let status = Command::new("cargo")
+        enter_phase(phase, "compiling the program");
+        say(format!("  {} Compiling program...", "→".bold().blue()));
+        let status = tokio::process::Command::new("cargo")
             .current_dir(&program_path)
             .arg("build-sbf")
+            .kill_on_drop(true)
             .status()
+            .await
             .context("Failed to run cargo build-sbf")?;
 
-        if !status.success() {
-            return Err(anyhow!("Failed to compile program"));
+        if !status.success() {
+            return Err(anyhow!("Failed to compile program"));
+        }
+
+        // Find the compiled binary. Workspace members build into the workspace's target directory
+        program_path
+            .ancestors()
+            .map(|dir| dir.join("target/deploy"))
+            .filter_map(|target_dir| fs::read_dir(target_dir).ok())
+            .flat_map(|entries| entries.filter_map(Result::ok))
+            .find(|entry| entry.path().extension().map_or(false, |ext| ext == "so"))
+            .ok_or_else(|| anyhow!("No .so file found in target/deploy directory"))?
+            .path()
+    };
+
+    if args.dry_run {
+        return print_deploy_estimate(&elf_path, &program_pubkey, config);
+    }
+
+    // Resolve the RPC URL and make sure the node is reachable before funding anything
+    enter_phase(phase, "checking the RPC node");
+    let rpc_url = get_rpc_url_with_fallback(args.rpc_url.clone(), config)?;
+    say(format!("Using RPC URL: {}", rpc_url));
+    probe_rpc_url(&rpc_url).await?;
+
+    // Keeps a zero-padded copy of the binary on disk when an upgrade shrinks the program
+    let mut padded_elf = None;
+    let elf_path = if args.upgrade {
+        enter_phase(phase, "checking the deployed program");
+        match prepare_upgrade(&rpc_url, &program_pubkey, &elf_path).await? {
+            Some(file) => {
+                let path = file.path().to_path_buf();
+                padded_elf = Some(file);
+                path
+            }
+            None => elf_path,
+        }
+    } else {
+        elf_path
+    };
+
+    // Set up Bitcoin RPC client and handle funding
+    enter_phase(phase, "funding the deployer wallet");
+    let wallet_manager = WalletManager::new(config)?;
+    ensure_wallet_balance(&wallet_manager.client, funding_amount(config)?).await?;
+
+    // Deploy the program
+    enter_phase(phase, "creating the program account and uploading chunks");
+    let txids = deploy_program_from_path(
+        &elf_path,
+        config,
+        Some((program_keypair.clone(), program_pubkey)),
+        rpc_url.clone(),
+        args.verify,
+        args.resume,
+    ).await?;
+
+    // Make the program executable
+    enter_phase(phase, "making the program executable");
+    make_program_executable(&program_keypair, &program_pubkey, &rpc_url).await?;
+
+    drop(downloaded_elf);
+    drop(padded_elf);
+
+    say("Program deployed successfully!".bold().green());
+
+    let program_hex = hex::encode(program_pubkey.serialize());
+    let id_path = args
+        .id_out
+        .clone()
+        .or_else(|| project_root.map(|root| root.join("program-id.txt")));
+    if let Some(id_path) = &id_path {
+        fs::write(id_path, format!("{}\n", program_hex))
+            .with_context(|| format!("Failed to write the program ID to {}", id_path.display()))?;
+        say(format!("  {} Program ID written to {}", "✓".bold().green(), id_path.display()));
+    }
+
+    run_post_deploy_hook(config, &program_pubkey, &txids)?;
+
+    if json_output() {
+        return print_json(&json!({
+            "program_id": program_hex,
+            "txids": txids,
+            "id_file": id_path,
+        }));
+    }
+    // A stable marker scripts can grep for
+    println!("PROGRAM_ID={}", program_hex);
+    Ok(())
+}
+
+/// Checks that `program_pubkey` holds a program account `deploy --upgrade` can overwrite. The
+/// runtime cannot shrink accounts, so when the new binary is shorter than the deployed one a
+/// copy padded with zeros to the deployed length is returned to upload instead.
+async fn prepare_upgrade(
+    rpc_url: &str,
+    program_pubkey: &Pubkey,
+    elf_path: &Path,
+) -> Result<Option<tempfile::NamedTempFile>> {
+    let program_hex = hex::encode(program_pubkey.serialize());
+    let account = read_account_info_async(rpc_url, *program_pubkey)
+        .await
+        .with_context(|| format!("No program account found at {}. Deploy it without --upgrade first", program_hex))?;
+
+    // Program accounts stay owned by the system program; anything else belongs to a program
+    if account.owner != Pubkey::system_program() {
+        return Err(anyhow!(
+            "{} is owned by {}, not the system program, so it is not a program account that can be upgraded",
+            program_hex,
+            hex::encode(account.owner.serialize())
+        ));
+    }
+    // A fresh key created without --program-id is system-owned too, but was never deployed
+    if !account.is_executable {
+        return Err(anyhow!(
+            "{} is not executable, so it holds account data rather than a deployed program. Deploy it without --upgrade instead",
+            program_hex
+        ));
+    }
+
+    let elf = fs::read(elf_path)
+        .with_context(|| format!("Failed to read ELF binary at {}", elf_path.display()))?;
+    say(format!(
+        "  {} Upgrading {}: {} bytes deployed, {} bytes in the new binary",
+        "ℹ".bold().blue(),
+        program_hex.yellow(),
+        account.data.len(),
+        elf.len()
+    ));
+    if elf.len() >= account.data.len() {
+        return Ok(None);
+    }
+
+    say(format!(
+        "  {} The new binary is {} bytes shorter. Accounts cannot shrink, so the remaining bytes are zeroed",
+        "⚠".bold().yellow(),
+        account.data.len() - elf.len()
+    ));
+    let mut padded = tempfile::Builder::new().suffix(".so").tempfile()?;
+    padded.write_all(&elf)?;
+    padded.write_all(&vec![0u8; account.data.len() - elf.len()])?;
+    padded.flush()?;
+    Ok(Some(padded))
+}
+
+/// Runs the `hooks.post_deploy` command template from the configuration, if one is set.
+/// `{program_id}` and `{txids}` (comma-separated) are substituted before the command runs.
+fn run_post_deploy_hook(config: &Config, program_pubkey: &Pubkey, txids: &[String]) -> Result<()> {
+    let template = match config.get_string("hooks.post_deploy") {
+        Ok(template) if !template.trim().is_empty() => template,
+        _ => return Ok(()),
+    };
+
+    let command = template
+        .replace("{program_id}", &hex::encode(program_pubkey.serialize()))
+        .replace("{txids}", &txids.join(","));
+
+    say(format!("  {} Running post-deploy hook: {}", "→".bold().blue(), command.yellow()));
+
+    let status = if cfg!(target_os = "windows") {
+        Command::new("cmd").args(["/C", &command]).status()
+    } else {
+        Command::new("sh").args(["-c", &command]).status()
+    }
+    .context("Failed to run the post-deploy hook")?;
+
+    if status.success() {
+        say(format!("  {} Post-deploy hook finished successfully", "✓".bold().green()));
+    } else {
+        // The program is already deployed at this point, so a failing hook is reported but not fatal
+        say(format!(
+            "  {} Post-deploy hook exited with {}",
+            "⚠".bold().yellow(),
+            status
+                .code()
+                .map_or_else(|| "no exit code".to_string(), |code| format!("status {}", code))
+        ));
+    }
+
+    Ok(())
+}
+
+/// Compares a local program binary with the bytes deployed at `program_id`
+async fn verify_deployed_program(args: &DeployArgs, program_id: &str, config: &Config) -> Result<()> {
+    say("Verifying deployed program...".bold().green());
+
+    let elf = match (&args.elf_path, &args.elf_url) {
+        (Some(path), _) => fs::read(path).with_context(|| format!("Failed to read ELF binary at {}", path))?,
+        (None, Some(url)) => {
+            let elf_file = download_elf(url, args.elf_sha256.as_deref()).await?;
+            fs::read(elf_file.path())?
+        }
+        (None, None) => return Err(anyhow!("--verify-only needs the local binary to compare, pass --elf-path or --elf-url")),
+    };
+
+    // Accept either a hex-encoded program ID or a key name from keys.json
+    let program_hex = resolve_pubkey_hex(program_id)?;
+    let program_pubkey = Pubkey::from_slice(&hex::decode(&program_hex)?);
+
+    let rpc_url = get_rpc_url_with_fallback(args.rpc_url.clone(), config)?;
+    probe_rpc_url(&rpc_url).await?;
+
+    let account = read_account_info_async(&rpc_url, program_pubkey)
+        .await
+        .with_context(|| format!("Failed to read program account {}", program_hex))?;
+
+    say(format!("  {} Program ID: {}", "ℹ".bold().blue(), program_hex.yellow()));
+    say(format!("  {} Local binary: {} bytes", "ℹ".bold().blue(), elf.len()));
+    say(format!("  {} Deployed data: {} bytes", "ℹ".bold().blue(), account.data.len()));
+    say(format!("  {} Executable: {}", "ℹ".bold().blue(), account.is_executable));
+
+    let first_difference = elf
+        .iter()
+        .zip(account.data.iter())
+        .position(|(local, deployed)| local != deployed)
+        .or_else(|| (elf.len() != account.data.len()).then(|| elf.len().min(account.data.len())));
+
+    match first_difference {
+        None => {
+            say(format!("  {} Deployed program matches the local binary", "✓".bold().green()));
+            Ok(())
+        }
+        Some(offset) => Err(anyhow!(
+            "Deployed program does not match the local binary: first difference at offset {} (0x{:x})",
+            offset,
+            offset
+        )),
+    }
+}
+
+/// Downloads a program binary to a temporary file, verifying its checksum when one is given
+async fn download_elf(url: &str, expected_sha256: Option<&str>) -> Result<tempfile::NamedTempFile> {
+    if !url.starts_with("https://") && !url.starts_with("http://") {
+        return Err(anyhow!("--elf-url must be an http(s) URL: {}", url));
+    }
+
+    say(format!("  {} Downloading program binary from {}...", "→".bold().blue(), url.yellow()));
+
+    let client = reqwest::Client::builder()
+        .user_agent(format!("arch-cli/{}", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(300))
+        .build()?;
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .with_context(|| format!("Failed to download {}", url))?;
+    if !response.status().is_success() {
+        return Err(anyhow!("Failed to download {}: HTTP {}", url, response.status()));
+    }
+    let bytes = response.bytes().await?;
+
+    let digest = bitcoin::hashes::sha256::Hash::hash(&bytes).to_string();
+    match expected_sha256 {
+        Some(expected) if !digest.eq_ignore_ascii_case(expected.trim()) => {
+            return Err(anyhow!(
+                "Checksum mismatch for {}: expected {}, got {}",
+                url,
+                expected.trim(),
+                digest
+            ));
+        }
+        Some(_) => say(format!("  {} Checksum verified", "✓".bold().green())),
+        None => say(format!(
+            "  {} Downloaded {} bytes (sha256 {}). Pass --elf-sha256 to verify the binary",
+            "ℹ".bold().blue(),
+            bytes.len(),
+            digest
+        )),
+    }
+
+    let mut elf_file = tempfile::Builder::new().suffix(".so").tempfile()?;
+    elf_file.write_all(&bytes)?;
+    elf_file.flush()?;
+
+    Ok(elf_file)
+}
+
+pub fn create_unique_key_name(keys_file: &Path) -> Result<String> {
+    let mut counter = 1;
+    let base_name = "program_key";
+    
+    let existing_keys: Value = if keys_file.exists() {
+        load_keys(keys_file)?
+    } else {
+        json!({})
+    };
+
+    while existing_keys.get(&format!("{}{}", base_name, counter)).is_some() {
+        counter += 1;
+    }
+
+    Ok(format!("{}{}", base_name, counter))
+}
+
+pub async fn send_coins(args: &SendCoinsArgs, config: &Config) -> Result<()> {
+    let network = config
+        .get_string("bitcoin.network")
+        .unwrap_or_else(|_| "regtest".to_string());
+    let bitcoin_network =
+        Network::from_str(&network).context("Invalid Bitcoin network specified in config")?;
+
+    // Initialize the WalletManager
+    let wallet_manager = WalletManager::new(config)?;
+
+    // Check wallet balance before sending
+    let balance = wallet_manager.client.get_balance(None, None)?;
+
+    if balance < Amount::from_sat(args.amount) {
+        return Err(anyhow!(
+            "Insufficient balance. Available: {}, Required: {}",
+            balance.to_string().yellow(),
+            Amount::from_sat(args.amount).to_string().yellow()
+        ));
+    }
+
+    // Parse the destination address
+    let address = Address::from_str(&args.address)
+        .with_context(|| format!("Invalid Bitcoin address: {}", args.address))?;
+    say(format!(
+        "  {} Sending {} satoshis to address: {} ({})",
+        "ℹ".bold().blue(),
+        args.amount.to_string().yellow(),
+        args.address.yellow(),
+        network
+    ));
+
+    let address_networked = address
+        .require_network(bitcoin_network)
+        .with_context(|| format!("Address is not valid on the configured Bitcoin network ({})", network))?;
+
+    let estimate_mode = match args.fee_mode {
+        FeeMode::Economical => EstimateMode::Economical,
+        FeeMode::Conservative => EstimateMode::Conservative,
+    };
+
+    // Send the coins
+    let txid = wallet_manager.client.send_to_address(
+        &address_networked,
+        Amount::from_sat(args.amount),
+        None,                    // comment
+        None,                    // comment_to
+        Some(false),            // subtract_fee
+        Some(!args.no_rbf),     // replaceable
+        Some(args.conf_target), // confirmation_target (blocks)
+        Some(estimate_mode)     // estimate_mode
+    )?;
+
+    // Generate 1 block to confirm the transaction, only possible on regtest
+    if bitcoin_network == Network::Regtest && !args.no_generate {
+        wallet_manager
+            .client
+            .generate_to_address(1, &address_networked)?;
+    }
+
+    // Print success message
+    println!(
+        "{} Coins sent successfully! Transaction ID: {}",
+        "✓".bold().green(),
+        txid.to_string().yellow()
+    );
+
+    // Close the wallet if needed
+    wallet_manager.close_wallet()?;
+
+    Ok(())
+}
+
+pub async fn bitcoin_utxos(args: &UtxosArgs, config: &Config) -> Result<()> {
+    let wallet_manager = WalletManager::new(config)?;
+
+    let network = config
+        .get_string("bitcoin.network")
+        .unwrap_or_else(|_| "regtest".to_string());
+    let bitcoin_network =
+        Network::from_str(&network).context("Invalid Bitcoin network specified in config")?;
+
+    let address = match &args.address {
+        Some(address) => Some(
+            Address::from_str(address)
+                .context("Invalid address")?
+                .require_network(bitcoin_network)
+                .context("Address does not match the configured Bitcoin network")?,
+        ),
+        None => None,
+    };
+    let addresses = address.as_ref().map(|address| vec![address]);
+
+    // Include unconfirmed outputs, they are often the reason funding seems stuck
+    let mut utxos = wallet_manager.client.list_unspent(
+        Some(0),
+        None,
+        addresses.as_deref(),
+        None,
+        None,
+    )?;
+    utxos.sort_by(|a, b| b.confirmations.cmp(&a.confirmations));
+
+    // Immature coinbase outputs are not listed, but explain most "no funds" surprises on regtest
+    let immature = wallet_manager
+        .client
+        .get_balances()
+        .map(|balances| balances.mine.immature)
+        .unwrap_or(Amount::ZERO);
+
+    if json_output() {
+        let entries: Vec<Value> = utxos
+            .iter()
+            .map(|utxo| {
+                json!({
+                    "txid": utxo.txid.to_string(),
+                    "vout": utxo.vout,
+                    "address": utxo.address.as_ref().map(|address| address.clone().assume_checked().to_string()),
+                    "amount_sat": utxo.amount.to_sat(),
+                    "confirmations": utxo.confirmations,
+                    "spendable": utxo.spendable,
+                })
+            })
+            .collect();
+        return print_json(&json!({ "utxos": entries, "immature_sat": immature.to_sat() }));
+    }
+
+    println!("{}", "Wallet unspent outputs:".bold().green());
+    if utxos.is_empty() {
+        println!("  {} No unspent outputs found", "ℹ".bold().blue());
+    }
+    for utxo in &utxos {
+        println!(
+            "  {} {}:{}  {}  {} confirmations{}",
+            "→".bold().blue(),
+            utxo.txid.to_string().yellow(),
+            utxo.vout,
+            utxo.amount,
+            utxo.confirmations,
+            if utxo.spendable { "" } else { " (not spendable)" }
+        );
+    }
+
+    if immature > Amount::ZERO {
+        println!(
+            "  {} {} is still immature coinbase. Mining rewards need 100 confirmations before they can be spent",
+            "ℹ".bold().blue(),
+            immature
+        );
+    }
+
+    Ok(())
+}
+
+pub async fn bitcoin_mine(args: &MineArgs, config: &Config) -> Result<()> {
+    let network = config
+        .get_string("bitcoin.network")
+        .unwrap_or_else(|_| "regtest".to_string());
+    let bitcoin_network =
+        Network::from_str(&network).context("Invalid Bitcoin network specified in config")?;
+    if bitcoin_network != Network::Regtest {
+        return Err(anyhow!(
+            "Blocks can only be mined on regtest, but the configured Bitcoin network is {}",
+            network
+        ));
+    }
+    if args.blocks == 0 {
+        return Err(anyhow!("--blocks must be at least 1"));
+    }
+
+    let wallet_manager = WalletManager::new(config)?;
+
+    let address = match &args.address {
+        Some(address) => Address::from_str(address).context("Invalid address")?,
+        None => wallet_manager
+            .client
+            .get_new_address(None, None)
+            .context("Failed to get a new wallet address")?,
+    }
+    .require_network(bitcoin_network)
+    .context("Address does not match the configured Bitcoin network")?;
+
+    let hashes = wallet_manager
+        .client
+        .generate_to_address(args.blocks, &address)
+        .context("Failed to mine blocks")?;
+    let height = wallet_manager.client.get_block_count()?;
+
+    if json_output() {
+        return print_json(&json!({
+            "address": address.to_string(),
+            "height": height,
+            "blocks": hashes.iter().map(|hash| hash.to_string()).collect::<Vec<_>>(),
+        }));
+    }
+
+    println!(
+        "  {} Mined {} block(s) to {}",
+        "✓".bold().green(),
+        hashes.len(),
+        address.to_string().yellow()
+    );
+    for hash in &hashes {
+        println!("    {} {}", "→".bold().blue(), hash);
+    }
+    println!("  {} Block height is now {}", "ℹ".bold().blue(), height);
+
+    Ok(())
+}
+
+fn format_amount(amount: Amount, format: AmountFormat) -> String {
+    match format {
+        AmountFormat::Sat => format!("{} sat", amount.to_sat()),
+        _ => format!("{:.8} BTC", amount.to_btc()),
+    }
+}
+
+pub async fn bitcoin_balance(args: &BitcoinFormatArgs, config: &Config) -> Result<()> {
+    let wallet_manager = WalletManager::new(config)?;
+    let balances = wallet_manager
+        .client
+        .get_balances()
+        .context("Failed to get the wallet balances")?;
+    let mine = &balances.mine;
+    let total = mine.trusted + mine.untrusted_pending + mine.immature;
+
+    wallet_manager.close_wallet()?;
+
+    if args.format == AmountFormat::Json || json_output() {
+        return print_json(&json!({
+            "trusted_sat": mine.trusted.to_sat(),
+            "untrusted_pending_sat": mine.untrusted_pending.to_sat(),
+            "immature_sat": mine.immature.to_sat(),
+            "total_sat": total.to_sat(),
+        }));
+    }
+
+    println!("{}", "Wallet balance:".bold().green());
+    println!("  {} Trusted: {}", "ℹ".bold().blue(), format_amount(mine.trusted, args.format).yellow());
+    println!("  {} Untrusted pending: {}", "ℹ".bold().blue(), format_amount(mine.untrusted_pending, args.format));
+    println!("  {} Immature: {}", "ℹ".bold().blue(), format_amount(mine.immature, args.format));
+    println!("  {} Total: {}", "ℹ".bold().blue(), format_amount(total, args.format));
+
+    Ok(())
+}
+
+pub async fn bitcoin_address(config: &Config) -> Result<()> {
+    let network = config
+        .get_string("bitcoin.network")
+        .unwrap_or_else(|_| "regtest".to_string());
+    let bitcoin_network =
+        Network::from_str(&network).context("Invalid Bitcoin network specified in config")?;
+
+    let wallet_manager = WalletManager::new(config)?;
+    let address = wallet_manager
+        .client
+        .get_new_address(None, None)
+        .context("Failed to get a new wallet address")?
+        .require_network(bitcoin_network)
+        .context("The wallet returned an address for another Bitcoin network")?;
+
+    wallet_manager.close_wallet()?;
+
+    if json_output() {
+        return print_json(&json!({ "address": address.to_string(), "network": network }));
+    }
+
+    println!("  {} New address: {}", "✓".bold().green(), address.to_string().yellow());
+
+    Ok(())
+}
+
+pub async fn bitcoin_info(args: &BitcoinFormatArgs, config: &Config) -> Result<()> {
+    let wallet_manager = WalletManager::new(config)?;
+    let client = &wallet_manager.client;
+
+    let chain = client
+        .get_blockchain_info()
+        .context("Failed to get blockchain info from the Bitcoin node")?;
+    let wallet = client
+        .get_wallet_info()
+        .context("Failed to get wallet info from the Bitcoin node")?;
+    let balance = client.get_balances().context("Failed to get the wallet balances")?.mine.trusted;
+
+    if args.format == AmountFormat::Json || json_output() {
+        return print_json(&json!({
+            "chain": chain.chain.to_string(),
+            "blocks": chain.blocks,
+            "headers": chain.headers,
+            "best_block_hash": chain.best_block_hash.to_string(),
+            "verification_progress": chain.verification_progress,
+            "wallet": wallet.wallet_name,
+            "tx_count": wallet.tx_count,
+            "balance_sat": balance.to_sat(),
+        }));
+    }
+
+    println!("{}", "Bitcoin node:".bold().green());
+    println!("  {} Chain: {}", "ℹ".bold().blue(), chain.chain.to_string().yellow());
+    println!("  {} Blocks: {} (headers: {})", "ℹ".bold().blue(), chain.blocks, chain.headers);
+    println!("  {} Best block: {}", "ℹ".bold().blue(), chain.best_block_hash);
+    println!("  {} Sync progress: {:.2}%", "ℹ".bold().blue(), chain.verification_progress * 100.0);
+    println!("{}", "Wallet:".bold().green());
+    println!("  {} Name: {}", "ℹ".bold().blue(), wallet.wallet_name.yellow());
+    println!("  {} Transactions: {}", "ℹ".bold().blue(), wallet.tx_count);
+    println!("  {} Balance: {}", "ℹ".bold().blue(), format_amount(balance, args.format).yellow());
+
+    Ok(())
+}
+
+pub async fn program_show(args: &ShowProgramArgs, config: &Config) -> Result<()> {
+    let program_hex = resolve_pubkey_hex(&args.program_id)?;
+    let program_pubkey = Pubkey::from_slice(&hex::decode(&program_hex)?);
+
+    let rpc_url = get_rpc_url_with_fallback(args.rpc_url.clone(), config)?;
+    probe_rpc_url(&rpc_url).await?;
+
+    let account = read_account_info_async(&rpc_url, program_pubkey)
+        .await
+        .with_context(|| format!("No account found for program {}", program_hex))?;
+    // The node only serves accounts it considers programs through get_program
+    let is_program = get_program_async(&rpc_url, program_hex.clone()).await.is_ok();
+
+    if json_output() {
+        return print_json(&json!({
+            "program_id": program_hex,
+            "owner": hex::encode(account.owner.serialize()),
+            "size": account.data.len(),
+            "is_executable": account.is_executable,
+            "is_program": is_program,
+        }));
+    }
+
+    println!("{}", "Program:".bold().green());
+    println!("  {} Program ID: {}", "ℹ".bold().blue(), program_hex.yellow());
+    println!("  {} Owner: {}", "ℹ".bold().blue(), hex::encode(account.owner.serialize()));
+    println!("  {} Size: {} bytes", "ℹ".bold().blue(), account.data.len());
+    println!("  {} Executable: {}", "ℹ".bold().blue(), account.is_executable);
+    if !account.is_executable || !is_program {
+        println!(
+            "  {} This account is not an executable program yet. Accounts owned by it cannot be used until it is",
+            "⚠".bold().yellow()
+        );
+    }
+
+    Ok(())
+}
+
+pub async fn program_rotate(args: &RotateProgramArgs, config: &Config) -> Result<()> {
+    let keys_file = get_config_dir()?.join("keys.json");
+    if key_name_exists(&keys_file, &args.to)? {
+        return Err(anyhow!("A key named '{}' already exists in keys.json. Choose another name", args.to));
+    }
+
+    let old_hex = resolve_pubkey_hex(&args.from)?;
+    let old_pubkey = Pubkey::from_slice(&hex::decode(&old_hex)?);
+
+    // Resolve the accounts up front so a typo does not leave a half-done rotation
+    let migrate_accounts = args
+        .migrate_accounts
+        .iter()
+        .map(|name| {
+            let keypair = get_keypair_from_name(name, &keys_file)?;
+            let pubkey = Pubkey::from_slice(&XOnlyPublicKey::from_keypair(&keypair).0.serialize());
+            Ok((name.as_str(), keypair, pubkey))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let rpc_url = get_rpc_url_with_fallback(args.rpc_url.clone(), config)?;
+    probe_rpc_url(&rpc_url).await?;
+
+    say("Rotating program key...".bold().green());
+
+    // Deploy the same binary unless another one is given
+    let mut elf_file = tempfile::Builder::new().suffix(".so").tempfile()?;
+    let elf_path = match &args.elf_path {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let account = read_account_info_async(&rpc_url, old_pubkey)
+                .await
+                .with_context(|| format!("No account found for program {}", old_hex))?;
+            if !account.is_executable {
+                return Err(anyhow!(
+                    "{} is not an executable program. Pass --elf-path to deploy a binary of your own",
+                    old_hex
+                ));
+            }
+            elf_file.write_all(&account.data)?;
+            elf_file.flush()?;
+            say(format!(
+                "  {} Using the {} bytes deployed at {}",
+                "ℹ".bold().blue(),
+                account.data.len(),
+                old_hex.yellow()
+            ));
+            elf_file.path().to_path_buf()
         }
-
-        // Find the compiled binary
-        let target_dir = program_path.join("target/deploy");
-        fs::read_dir(&target_dir)?
-            .filter_map(Result::ok)
-            .find(|entry| entry.path().extension().map_or(false, |ext| ext == "so"))
-            .ok_or_else(|| anyhow!("No .so file found in target/deploy directory"))?
-            .path()
     };
 
-    // Deploy the program
-    let rpc_url = get_rpc_url_with_fallback(args.rpc_url.clone(), config).unwrap();
+    // Save the key before deploying, so a failed deployment can be resumed with it
+    let (new_keypair, new_pubkey) = generate_new_keypair()?;
+    save_keypair_to_json(&keys_file, &new_keypair, &new_pubkey, &args.to)?;
+    let new_hex = hex::encode(new_pubkey.serialize());
+    say(format!(
+        "  {} New program key '{}' saved: {}",
+        "✓".bold().green(),
+        args.to,
+        new_hex.yellow()
+    ));
+
+    let wallet_manager = WalletManager::new(config)?;
+    ensure_wallet_balance(&wallet_manager.client, funding_amount(config)?).await?;
 
-    // Deploy the program
     deploy_program_from_path(
         &elf_path,
         config,
-        Some((program_keypair.clone(), program_pubkey)),
+        Some((new_keypair, new_pubkey)),
         rpc_url.clone(),
-    ).await?;
+        false,
+        false,
+    )
+    .await?;
+    make_program_executable(&new_keypair, &new_pubkey, &rpc_url).await?;
 
-    // Make the program executable
-    make_program_executable(&program_keypair, &program_pubkey, &rpc_url).await?;
+    let mut migrated = Vec::new();
+    let mut failed = Vec::new();
+    for (name, keypair, pubkey) in &migrate_accounts {
+        let owner = read_account_info_async(&rpc_url, *pubkey)
+            .await
+            .map(|account| account.owner);
+        let result = match owner {
+            Ok(owner) if owner != old_pubkey => Err(anyhow!(
+                "owned by {}, not by the old program",
+                hex::encode(owner.serialize())
+            )),
+            Ok(_) => transfer_account_ownership(keypair, pubkey, &new_pubkey, rpc_url.clone()).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => {
+                say(format!("  {} Reassigned '{}' to the new program", "✓".bold().green(), name));
+                migrated.push(name.to_string());
+            }
+            Err(e) => {
+                println!("  {} Could not reassign '{}': {}", "✗".bold().red(), name, e);
+                failed.push(name.to_string());
+            }
+        }
+    }
+
+    drop(elf_file);
+
+    if json_output() {
+        print_json(&json!({
+            "old_program_id": old_hex,
+            "new_program_id": new_hex,
+            "key_name": args.to,
+            "migrated_accounts": migrated,
+            "failed_accounts": failed,
+        }))?;
+    } else {
+        println!("{}", "Program rotated successfully!".bold().green());
+        println!("  {} Old program ID: {}", "ℹ".bold().blue(), old_hex);
+        println!("  {} New program ID: {}", "ℹ".bold().blue(), new_hex.yellow());
+        println!("  {} Remaining steps:", "→".bold().blue());
+        println!("    - Point clients, frontends and .env files at the new program ID");
+        println!("    - Recreate accounts whose keys are not in keys.json under the new program");
+        println!("    - Copy any state the program keeps in its accounts; reassigning does not change account data");
+    }
+
+    if !failed.is_empty() {
+        return Err(anyhow!("{} account(s) could not be reassigned", failed.len()));
+    }
 
-    println!("{}", "Program deployed successfully!".bold().green());
     Ok(())
 }
 
-pub fn create_unique_key_name(keys_file: &Path) -> Result<String> {
-    let mut counter = 1;
-    let base_name = "program_key";
-    
-    let existing_keys: Value = if keys_file.exists() {
-        serde_json::from_str(&fs::read_to_string(keys_file)?)?
+pub async fn program_describe(args: &DescribeProgramArgs) -> Result<()> {
+    let directory = PathBuf::from(args.directory.as_deref().unwrap_or("."));
+    let program_dir = if is_program_crate(&directory) {
+        directory
     } else {
-        json!({})
+        find_program_crate(&directory)
+            .ok_or_else(|| anyhow!("No Arch program crate found in {}", directory.display()))?
     };
 
-    while existing_keys.get(&format!("{}{}", base_name, counter)).is_some() {
-        counter += 1;
+    let structs = layout::describe_borsh_structs(&program_dir.join("src"))?;
+
+    if json_output() {
+        return print_json(&json!({
+            "program_dir": program_dir.display().to_string(),
+            "structs": structs.iter().map(|layout| {
+                let mut offset = Some(0);
+                json!({
+                    "name": layout.name,
+                    "instruction": layout.instruction,
+                    "size": layout.size(),
+                    "fields": layout.fields.iter().map(|field| {
+                        let field_offset = offset;
+                        offset = offset.zip(field.size).map(|(offset, size)| offset + size);
+                        json!({
+                            "name": field.name,
+                            "type": field.ty,
+                            "offset": field_offset,
+                            "size": field.size,
+                            "encoding": field.encoding,
+                        })
+                    }).collect::<Vec<_>>(),
+                })
+            }).collect::<Vec<_>>(),
+        }));
     }
 
-    Ok(format!("{}{}", base_name, counter))
+    println!("{}", format!("Borsh layout of {}:", program_dir.display()).bold().green());
+    if structs.is_empty() {
+        println!("  {} No structs deriving BorshSerialize or BorshDeserialize found", "ℹ".bold().blue());
+        return Ok(());
+    }
+
+    for layout in &structs {
+        let size = layout
+            .size()
+            .map_or_else(|| "variable size".to_string(), |size| format!("{} bytes", size));
+        let role = if layout.instruction { ", instruction data" } else { "" };
+        println!();
+        println!("  {} ({}{})", layout.name.bold(), size, role);
+
+        // Offsets are only known up to the first variable-size field
+        let mut offset = Some(0);
+        for field in &layout.fields {
+            let position = offset.map_or_else(|| "?".to_string(), |offset| offset.to_string());
+            println!(
+                "    {:>5}  {:<12} {:<22} {}",
+                position,
+                field.name,
+                field.ty.yellow(),
+                field.encoding
+            );
+            offset = offset.zip(field.size).map(|(offset, size)| offset + size);
+        }
+
+        if layout.instruction {
+            println!(
+                "  {} Instruction data is the Borsh serialization of {}: fields back to back in declaration order, without padding",
+                "→".bold().blue(),
+                layout.name
+            );
+        }
+    }
+
+    Ok(())
 }
 
-pub async fn send_coins(args: &SendCoinsArgs, config: &Config) -> Result<()> {
-    // Initialize the WalletManager
-    let wallet_manager = WalletManager::new(config)?;
+pub async fn network_blockhash(args: &BlockhashArgs, config: &Config) -> Result<()> {
+    let rpc_url = get_rpc_url_with_fallback(args.rpc_url.clone(), config)?;
+    println!("  {} Querying {}...", "→".bold().blue(), rpc_url.yellow());
 
-    // Check wallet balance before sending
-    let balance = wallet_manager.client.get_balance(None, None)?;
+    let block_hash = get_best_block_hash_async(&rpc_url)
+        .await
+        .with_context(|| format!("Failed to fetch the best block hash from {}", rpc_url))?;
 
-    if balance < Amount::from_sat(args.amount) {
-        return Err(anyhow!(
-            "Insufficient balance. Available: {}, Required: {}",
-            balance.to_string().yellow(),
-            Amount::from_sat(args.amount).to_string().yellow()
-        ));
+    println!("  {} Best block hash: {}", "✓".bold().green(), block_hash.yellow());
+
+    Ok(())
+}
+
+const RELEASES_API_URL: &str = "https://api.github.com/repos/Arch-Network/arch-cli/releases/latest";
+const REPOSITORY_URL: &str = "https://github.com/Arch-Network/arch-cli";
+
+/// Parses a release tag such as `v0.1.6` into comparable version components
+fn parse_version(version: &str) -> Option<Vec<u64>> {
+    version
+        .trim()
+        .trim_start_matches('v')
+        .split('.')
+        .map(|part| part.parse().ok())
+        .collect()
+}
+
+pub async fn self_update(args: &SelfUpdateArgs) -> Result<()> {
+    say("Checking for updates...".bold().green());
+
+    let current_version = env!("CARGO_PKG_VERSION");
+    let client = reqwest::Client::builder()
+        .user_agent(format!("arch-cli/{}", current_version))
+        .timeout(Duration::from_secs(30))
+        .build()?;
+
+    let response = client
+        .get(RELEASES_API_URL)
+        .send()
+        .await
+        .context("Failed to query the latest arch-cli release")?;
+    if !response.status().is_success() {
+        return Err(anyhow!("Failed to query the latest arch-cli release: HTTP {}", response.status()));
     }
+    let release: Value = serde_json::from_str(&response.text().await?)?;
+    let latest_tag = release["tag_name"]
+        .as_str()
+        .ok_or_else(|| anyhow!("Latest release has no tag name"))?
+        .to_string();
 
-    // Parse the destination address
-    let address = Address::from_str(&args.address)?;
-    println!(
-        "  {} Sending {} satoshis to address: {}",
-        "ℹ".bold().blue(),
-        args.amount.to_string().yellow(),
-        args.address.yellow()
-    );
+    let latest = parse_version(&latest_tag)
+        .ok_or_else(|| anyhow!("Unrecognized release version: {}", latest_tag))?;
+    let current = parse_version(current_version)
+        .ok_or_else(|| anyhow!("Unrecognized current version: {}", current_version))?;
 
-    let address_networked = address.require_network(Network::Regtest)?;
+    println!("  {} Installed version: {}", "ℹ".bold().blue(), current_version.yellow());
+    println!("  {} Latest release: {}", "ℹ".bold().blue(), latest_tag.yellow());
 
-    // Send the coins
-    let txid = wallet_manager.client.send_to_address(
-        &address_networked,
-        Amount::from_sat(args.amount),
-        None,                    // comment
-        None,                    // comment_to
-        Some(false),            // subtract_fee
-        Some(true),             // replaceable (enable RBF)
-        Some(1),                // confirmation_target (blocks)
-        Some(EstimateMode::Economical) // estimate_mode
-    )?;
+    if latest <= current {
+        println!("  {} arch-cli is up to date", "✓".bold().green());
+        return Ok(());
+    }
 
-    // Generate 1 block to confirm the transaction
-    wallet_manager
-        .client
-        .generate_to_address(1, &address_networked)?;
+    if args.check {
+        println!(
+            "  {} A newer release is available. Run 'arch-cli self-update' to install it",
+            "→".bold().blue()
+        );
+        return Ok(());
+    }
 
-    // Print success message
-    println!(
-        "{} Coins sent successfully! Transaction ID: {}",
-        "✓".bold().green(),
-        txid.to_string().yellow()
-    );
+    // The update is built from source, so fail before asking when there is no toolchain
+    let cargo = check_dependency("cargo", &[&["cargo", "--version"]], "cargo is not installed");
+    if cargo.error.is_some() {
+        return Err(anyhow!(
+            "arch-cli {} is available, but self-update installs it with cargo, which was not found. Install Rust from https://rustup.rs or download the release from {}/releases",
+            latest_tag,
+            REPOSITORY_URL
+        ));
+    }
 
-    // Close the wallet if needed
-    wallet_manager.close_wallet()?;
+    let confirmed = confirm(
+        &format!("Install arch-cli {} now? This replaces the current binary.", latest_tag),
+        false,
+    )?;
+    if !confirmed {
+        say(format!("  {} Update cancelled", "ℹ".bold().blue()));
+        return Ok(());
+    }
 
+    // Reinstalling from the release tag also refreshes the templates embedded in the binary
+    say(format!("  {} Installing arch-cli {}...", "→".bold().blue(), latest_tag.yellow()));
+    let status = Command::new("cargo")
+        .args(["install", "--git", REPOSITORY_URL, "--tag", &latest_tag, "--force", "--locked"])
+        .status()
+        .context("Failed to run cargo install")?;
+
+    if !status.success() {
+        return Err(anyhow!("Failed to install arch-cli {}", latest_tag));
+    }
+
+    say(format!("  {} arch-cli updated to {}", "✓".bold().green(), latest_tag.yellow()));
     Ok(())
 }
 
@@ -1407,11 +4018,11 @@ fn stop_all_related_containers() -> Result<()> {
     let container_prefixes = vec!["arch-cli", "bitcoin", "electrs", "btc-rpc-explorer"];
 
     for prefix in container_prefixes {
-        println!(
+        say(format!(
             "  {} Stopping {} containers...",
             "→".bold().blue(),
             prefix.yellow()
-        );
+        ));
 
         // List all running containers with the given prefix
         let output = Command::new("docker")
@@ -1438,18 +4049,18 @@ fn stop_all_related_containers() -> Result<()> {
                     error_message.red()
                 );
             } else {
-                println!(
+                say(format!(
                     "  {} {} containers stopped successfully.",
                     "✓".bold().green(),
                     prefix.yellow()
-                );
+                ));
             }
         } else {
-            println!(
+            say(format!(
                 "  {} No running {} containers found to stop.",
                 "ℹ".bold().blue(),
                 prefix.yellow()
-            );
+            ));
         }
     }
 
@@ -1457,34 +4068,66 @@ fn stop_all_related_containers() -> Result<()> {
 }
 
 pub async fn server_status(config: &Config) -> Result<()> {
-    println!("{}", "Checking development server status...".bold().blue());
+    if !json_output() {
+        println!("{}", "Checking development server status...".bold().blue());
+    }
 
     let network_type = config
         .get_string("network.type")
         .context("Failed to get network type from configuration")?;
 
-    if network_type == "development" {
-        let bitcoin_config: ServiceConfig = config
-            .get("bitcoin")
-            .context("Failed to get Bitcoin configuration")?;
-        check_service_status("Bitcoin regtest network", &bitcoin_config)?;
-
-        let arch_config: ServiceConfig = config
-            .get("arch")
-            .context("Failed to get Arch Network configuration")?;
-        check_service_status("Arch Network nodes", &arch_config)?;
-    } else {
+    if network_type != "development" {
+        if json_output() {
+            return print_json(&json!({ "network_type": network_type, "managed": false, "services": [] }));
+        }
         println!(
             "  {} Using existing network configuration for: {}",
-            "��".bold().blue(),
+            "ℹ".bold().blue(),
             network_type.yellow()
         );
+        return Ok(());
+    }
+
+    let groups = [
+        ("bitcoin", "Bitcoin regtest network", "Failed to get Bitcoin configuration"),
+        ("arch", "Arch Network nodes", "Failed to get Arch Network configuration"),
+    ];
+    let mut services = Vec::new();
+    for (key, service_name, error_message) in groups {
+        let service_config: ServiceConfig = config.get(key).context(error_message)?;
+        let statuses = check_service_status(service_name, &service_config)?;
+        services.extend(statuses.into_iter().map(|(container, status)| {
+            json!({
+                "group": key,
+                "name": container,
+                "created": !status.is_empty(),
+                "running": status.starts_with("Up"),
+                "status": status,
+            })
+        }));
     }
 
+    if json_output() {
+        return print_json(&json!({ "network_type": network_type, "managed": true, "services": services }));
+    }
     Ok(())
 }
 
-fn fetch_service_logs(service_name: &str, services: &[String]) -> Result<()> {
+/// Arguments selecting which part of a container log `docker logs` prints
+fn log_window_args(since: Option<&str>, tail: Option<usize>) -> Vec<String> {
+    let mut args = Vec::new();
+    if let Some(since) = since {
+        args.extend(["--since".to_string(), since.to_string()]);
+    }
+    match (tail, since) {
+        (Some(tail), _) => args.extend(["--tail".to_string(), tail.to_string()]),
+        (None, None) => args.extend(["--tail".to_string(), "50".to_string()]),
+        (None, Some(_)) => {}
+    }
+    args
+}
+
+fn fetch_service_logs(service_name: &str, services: &[String], since: Option<&str>, tail: Option<usize>) -> Result<()> {
     println!(
         "  {} Fetching logs for {}...",
         "→".bold().blue(),
@@ -1494,7 +4137,9 @@ fn fetch_service_logs(service_name: &str, services: &[String]) -> Result<()> {
     for container in services {
         println!("    Logs for {}:", container.bold());
         let log_output = Command::new("docker")
-            .args(["logs", "--tail", "50", container])
+            .arg("logs")
+            .args(log_window_args(since, tail))
+            .arg(container)
             .output()
             .context(format!("Failed to fetch logs for container {}", container))?;
 
@@ -1504,13 +4149,68 @@ fn fetch_service_logs(service_name: &str, services: &[String]) -> Result<()> {
     Ok(())
 }
 
-fn check_service_status(service_name: &str, service_config: &ServiceConfig) -> Result<()> {
-    println!(
-        "  {} Checking {} status...",
-        "→".bold().blue(),
-        service_name.yellow()
-    );
+/// Streams `docker logs --follow` of every container to stdout, prefixing each line with the
+/// container name. Runs until all the log streams end or the user presses Ctrl-C.
+fn follow_service_logs(containers: &[String], since: Option<&str>, tail: Option<usize>) -> Result<()> {
+    use std::io::{BufRead, BufReader};
+    use std::process::Stdio;
+
+    let width = containers.iter().map(|c| c.len()).max().unwrap_or(0);
+    let mut children = Vec::new();
+    let mut readers = Vec::new();
+
+    for container in containers {
+        let mut child = Command::new("docker")
+            .args(["logs", "--follow"])
+            .args(log_window_args(since, tail))
+            .arg(container)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context(format!("Failed to follow logs for container {}", container))?;
+
+        let prefix = format!("{:width$} |", container, width = width);
+        let streams: [Box<dyn std::io::Read + Send>; 2] = [
+            Box::new(child.stdout.take().expect("piped stdout")),
+            Box::new(child.stderr.take().expect("piped stderr")),
+        ];
+        for stream in streams {
+            let prefix = prefix.clone();
+            readers.push(std::thread::spawn(move || {
+                for line in BufReader::new(stream).lines().map_while(|line| line.ok()) {
+                    let mut stdout = std::io::stdout().lock();
+                    // Stop quietly once stdout is gone, e.g. when piped into head
+                    if writeln!(stdout, "{} {}", prefix.bold(), line).is_err() {
+                        break;
+                    }
+                }
+            }));
+        }
+        children.push(child);
+    }
+
+    for reader in readers {
+        let _ = reader.join();
+    }
+    for mut child in children {
+        let _ = child.wait();
+    }
+
+    Ok(())
+}
+
+/// Looks up the Docker status of every container of a service, printing it unless JSON output
+/// is selected. Returns (container, status) pairs, with an empty status for missing containers.
+fn check_service_status(service_name: &str, service_config: &ServiceConfig) -> Result<Vec<(String, String)>> {
+    if !json_output() {
+        println!(
+            "  {} Checking {} status...",
+            "→".bold().blue(),
+            service_name.yellow()
+        );
+    }
 
+    let mut statuses = Vec::new();
     for container in &service_config.services {
         let status_output = Command::new("docker")
             .args([
@@ -1528,59 +4228,100 @@ fn check_service_status(service_name: &str, service_config: &ServiceConfig) -> R
             .trim()
             .to_string();
 
-        if status.starts_with("Up") {
-            println!("    {} {} is running", "✓".bold().green(), container);
-        } else if status.is_empty() {
-            println!("    {} {} is not created", "✗".bold().red(), container);
-        } else {
-            println!(
-                "    {} {} is not running (status: {})",
-                "✗".bold().red(),
-                container,
-                status
-            );
+        if !json_output() {
+            if status.starts_with("Up") {
+                println!("    {} {} is running", "✓".bold().green(), container);
+            } else if status.is_empty() {
+                println!("    {} {} is not created", "✗".bold().red(), container);
+            } else {
+                println!(
+                    "    {} {} is not running (status: {})",
+                    "✗".bold().red(),
+                    container,
+                    status
+                );
+            }
         }
+        statuses.push((container.clone(), status));
     }
 
-    Ok(())
+    Ok(statuses)
 }
 
-pub async fn server_logs(service: &str, config: &Config) -> Result<()> {
+pub async fn server_logs(
+    service: &str,
+    since: Option<&str>,
+    follow: bool,
+    tail: Option<usize>,
+    config: &Config,
+) -> Result<()> {
     println!("{}", format!("Fetching logs for {}...", service).bold().blue());
 
-    let network_type = config.get_string("selected_network").unwrap_or_else(|_| "development".to_string());
+    let network_type = selected_network(config);
 
     if network_type != "development" && network_type != "development2" {
         println!("  {} Logs are not available for non-development networks", "ℹ".bold().blue());
         return Ok(());
     }
 
-    let services_to_fetch = match service {
-        "all" => vec!["bitcoin", "arch"],
-        s if s == "bitcoin" || s == "arch" => vec![s],
-        _ => return Err(anyhow!("Invalid service specified")),
-    };
-
-    for &s in &services_to_fetch {
-        let config_key = if s == "bitcoin" {
-            format!("networks.{}.services", network_type)
-        } else {
-            "arch.services".to_string()
-        };
+    // Containers of the selected network, grouped the way `server status` reports them
+    let mut groups: Vec<(&str, Vec<String>)> = Vec::new();
+    for (group, config_key) in [
+        ("bitcoin", format!("networks.{}.services", network_type)),
+        ("arch", "arch.services".to_string()),
+    ] {
+        match config.get_array(&config_key) {
+            Ok(services) => groups.push((
+                group,
+                services.into_iter().filter_map(|v| v.into_string().ok()).collect(),
+            )),
+            Err(_) => println!("  {} Failed to get services for {}", "⚠".bold().yellow(), group),
+        }
+    }
 
-        if let Ok(services) = config.get_array(&config_key) {
-            let service_names: Vec<String> = services.iter()
-                .filter_map(|v| Some(v.to_string()))
+    let selected: Vec<(String, Vec<String>)> = match service {
+        "all" => groups
+            .iter()
+            .map(|(group, services)| (format!("{} services", group), services.clone()))
+            .collect(),
+        group if group == "bitcoin" || group == "arch" => groups
+            .iter()
+            .filter(|(name, _)| *name == group)
+            .map(|(group, services)| (format!("{} services", group), services.clone()))
+            .collect(),
+        name if groups.iter().any(|(_, services)| services.iter().any(|s| s == name)) => {
+            vec![(name.to_string(), vec![name.to_string()])]
+        }
+        name => {
+            let available: Vec<&str> = ["all", "bitcoin", "arch"]
+                .into_iter()
+                .chain(groups.iter().flat_map(|(_, services)| services.iter().map(String::as_str)))
                 .collect();
+            return Err(anyhow!(
+                "Unknown service '{}'. Available services: {}",
+                name,
+                available.join(", ")
+            ));
+        }
+    };
 
-            if !service_names.is_empty() {
-                fetch_service_logs(&format!("{} services", s), &service_names)?;
-            } else {
-                println!("  {} No services defined for {}", "ℹ".bold().blue(), s);
-            }
+    let mut followed = Vec::new();
+    for (label, services) in selected {
+        if follow {
+            followed.extend(services);
+        } else if !services.is_empty() {
+            fetch_service_logs(&label, &services, since, tail)?;
         } else {
-            println!("  {} Failed to get services for {}", "⚠".bold().yellow(), s);
+            println!("  {} No services defined for {}", "ℹ".bold().blue(), label);
+        }
+    }
+
+    if follow {
+        if followed.is_empty() {
+            return Err(anyhow!("No containers configured for {}", service));
         }
+        println!("  {} Following logs, press Ctrl-C to stop", "ℹ".bold().blue());
+        follow_service_logs(&followed, since, tail)?;
     }
 
     Ok(())
@@ -1588,16 +4329,18 @@ pub async fn server_logs(service: &str, config: &Config) -> Result<()> {
 
 pub fn start_existing_containers(compose_file: &str) -> Result<()> {
     let output = Command::new("docker-compose")
+        .args(settings::compose_project_args("server"))
         .args(["-f", compose_file, "ps", "-q"])
         .output()
         .context("Failed to list existing containers")?;
 
     if !output.stdout.is_empty() {
-        println!(
+        say(format!(
             "  {} Found existing containers. Starting them...",
             "→".bold().blue()
-        );
+        ));
         let start_output = Command::new("docker-compose")
+            .args(settings::compose_project_args("server"))
             .args(["-f", compose_file, "start"])
             .output()
             .context("Failed to start existing containers")?;
@@ -1610,16 +4353,16 @@ pub fn start_existing_containers(compose_file: &str) -> Result<()> {
                 error_message.red()
             );
         } else {
-            println!(
+            say(format!(
                 "  {} Existing containers started successfully.",
                 "✓".bold().green()
-            );
+            ));
         }
     } else {
-        println!(
+        say(format!(
             "  {} No existing containers found. Creating new ones...",
             "ℹ".bold().blue()
-        );
+        ));
         // Proceed with your existing logic to create new containers
     }
 
@@ -1630,11 +4373,11 @@ pub fn remove_docker_networks() -> Result<()> {
     let networks = vec!["arch-network", "internal"];
 
     for network in networks {
-        println!(
+        say(format!(
             "  {} Removing Docker network: {}",
             "→".bold().blue(),
             network.yellow()
-        );
+        ));
 
         let output = Command::new("docker")
             .args(["network", "rm", network])
@@ -1644,11 +4387,11 @@ pub fn remove_docker_networks() -> Result<()> {
         if !output.status.success() {
             let error_message = String::from_utf8_lossy(&output.stderr);
             if error_message.contains("not found") {
-                println!(
+                say(format!(
                     "  {} Network {} not found. Skipping.",
                     "ℹ".bold().blue(),
                     network.yellow()
-                );
+                ));
             } else {
                 println!(
                     "  {} Warning: Failed to remove network {}: {}",
@@ -1658,11 +4401,11 @@ pub fn remove_docker_networks() -> Result<()> {
                 );
             }
         } else {
-            println!(
+            say(format!(
                 "  {} Network {} removed successfully.",
                 "✓".bold().green(),
                 network.yellow()
-            );
+            ));
         }
     }
 
@@ -1670,15 +4413,16 @@ pub fn remove_docker_networks() -> Result<()> {
 }
 
 pub fn stop_docker_services(compose_file: &str, service_name: &str) -> Result<()> {
-    println!(
+    say(format!(
         "  {} Stopping {} services...",
         "→".bold().blue(),
         service_name.yellow()
-    );
+    ));
     let (docker_compose_cmd, docker_compose_args) = get_docker_compose_command();
 
     let output = Command::new(docker_compose_cmd)
         .args(docker_compose_args)
+        .args(settings::compose_project_args("server"))
         .args(["-f", compose_file, "down"])
         .output()?;
 
@@ -1691,85 +4435,85 @@ pub fn stop_docker_services(compose_file: &str, service_name: &str) -> Result<()
             error_message.red()
         );
     } else {
-        println!(
+        say(format!(
             "  {} {} services stopped successfully.",
             "✓".bold().green(),
             service_name.yellow()
-        );
+        ));
     }
 
     Ok(())
 }
 
 pub async fn server_clean(config: &Config) -> Result<()> {
-    println!("{}", "Cleaning up the project...".bold().yellow());
+    say("Cleaning up the project...".bold().yellow());
     let arch_data_dir = get_arch_data_dir(&config)?;
     let config_dir = get_config_dir()?;
     let keys_file = config_dir.join("keys.json");
-    let config_file = config_dir.join("config.toml");
+    let config_file = get_config_path()?;
+    let config_name = config_file
+        .file_name()
+        .map_or_else(|| "config.toml".to_string(), |name| name.to_string_lossy().to_string());
 
     // Ask user if they want to clean the indexer
-    let clean_indexer = dialoguer::Confirm::new()
-        .with_prompt(
-            "Do you want to clean the indexer? This will remove all indexer containers and data.",
-        )
-        .default(false)
-        .interact()?;
+    let clean_indexer = confirm(
+        "Do you want to clean the indexer? This will remove all indexer containers and data.",
+        false,
+    )?;
 
     if clean_indexer {
-        println!("  {} Cleaning indexer...", "→".bold().blue());
+        say(format!("  {} Cleaning indexer...", "→".bold().blue()));
         indexer_clean(&config).await?;
     } else {
-        println!("  {} Indexer will be preserved", "ℹ".bold().blue());
+        say(format!("  {} Indexer will be preserved", "ℹ".bold().blue()));
     }
 
     // Ask user if they want to delete the keys.json file
-    let delete_keys = dialoguer::Confirm::new()
-        .with_prompt("Do you want to delete the keys.json file? This action cannot be undone.")
-        .default(false)
-        .interact()?;
+    let delete_keys = confirm(
+        "Do you want to delete the keys.json file? This action cannot be undone.",
+        false,
+    )?;
 
-    // Only ask about config.toml if indexer was cleaned
+    // Only ask about the config file if indexer was cleaned
     let delete_config = if clean_indexer {
-        dialoguer::Confirm::new()
-            .with_prompt(
-                "Do you want to delete the config.toml file? This action cannot be undone.",
-            )
-            .default(false)
-            .interact()?
+        confirm(
+            &format!("Do you want to delete the {} file? This action cannot be undone.", config_name),
+            false,
+        )?
     } else {
-        println!(
-            "  {} config.toml will be preserved as indexer was not cleaned",
-            "ℹ".bold().blue()
-        );
+        say(format!(
+            "  {} {} will be preserved as indexer was not cleaned",
+            "ℹ".bold().blue(),
+            config_name
+        ));
         false
     };
 
     if arch_data_dir.exists() {
         fs::remove_dir_all(&arch_data_dir)?;
-        println!("  {} Removed arch-data directory", "✓".bold().green());
+        say(format!("  {} Removed arch-data directory", "✓".bold().green()));
     }
 
     if keys_file.exists() {
         if delete_keys {
             fs::remove_file(&keys_file)?;
-            println!("  {} Removed keys.json file", "✓".bold().green());
+            say(format!("  {} Removed keys.json file", "✓".bold().green()));
         } else {
-            println!("  {} Preserved keys.json file", "ℹ".bold().blue());
+            say(format!("  {} Preserved keys.json file", "ℹ".bold().blue()));
         }
     } else {
-        println!("  {} No keys.json file found", "ℹ".bold().blue());
+        say(format!("  {} No keys.json file found", "ℹ".bold().blue()));
     }
 
     if config_file.exists() {
         if clean_indexer && delete_config {
             fs::remove_file(&config_file)?;
-            println!("  {} Removed config.toml file", "✓".bold().green());
+            say(format!("  {} Removed {} file", "✓".bold().green(), config_name));
         } else {
-            println!("  {} Preserved config.toml file", "ℹ".bold().blue());
+            say(format!("  {} Preserved {} file", "ℹ".bold().blue(), config_name));
         }
     } else {
-        println!("  {} No config.toml file found", "ℹ".bold().blue());
+        say(format!("  {} No {} file found", "ℹ".bold().blue(), config_name));
     }
 
     // Stop and remove Docker containers for Bitcoin
@@ -1778,6 +4522,7 @@ pub async fn server_clean(config: &Config) -> Result<()> {
         .unwrap_or_default();
     if !bitcoin_compose_file.is_empty() {
         let status = Command::new("docker-compose")
+            .args(settings::compose_project_args("server"))
             .args(["-f", &bitcoin_compose_file, "down", "--volumes"])
             .env("BITCOIN_RPC_USER", "")
             .env("ORD_PORT", "")
@@ -1788,10 +4533,10 @@ pub async fn server_clean(config: &Config) -> Result<()> {
             .context("Failed to stop Bitcoin containers")?;
 
         if status.success() {
-            println!(
+            say(format!(
                 "  {} Stopped and removed Bitcoin containers",
                 "✓".bold().green()
-            );
+            ));
         } else {
             println!("  {} Failed to stop Bitcoin containers", "✗".bold().red());
         }
@@ -1803,6 +4548,7 @@ pub async fn server_clean(config: &Config) -> Result<()> {
         .unwrap_or_default();
     if !arch_compose_file.is_empty() {
         let status = Command::new("docker-compose")
+            .args(settings::compose_project_args("server"))
             .args(["-f", &arch_compose_file, "down", "--volumes"])
             .env("BITCOIN_RPC_USER", "")
             .env("ORD_PORT", "")
@@ -1813,64 +4559,100 @@ pub async fn server_clean(config: &Config) -> Result<()> {
             .context("Failed to stop Arch containers")?;
 
         if status.success() {
-            println!(
+            say(format!(
                 "  {} Stopped and removed Arch containers",
                 "✓".bold().green()
-            );
+            ));
         } else {
             println!("  {} Failed to stop Arch containers", "✗".bold().red());
         }
     }
 
-    println!("  {} Project cleaned up successfully", "✓".bold().green());
+    say(format!("  {} Project cleaned up successfully", "✓".bold().green()));
     Ok(())
 }
 
 pub fn start_bitcoin_regtest() -> Result<()> {
-    println!(
+    say(format!(
         "  {} Starting Bitcoin regtest network...",
         "→".bold().blue()
-    );
+    ));
     let (docker_compose_cmd, docker_compose_args) = get_docker_compose_command();
 
     Command::new(docker_compose_cmd)
         .args(docker_compose_args)
+        .args(settings::compose_project_args("server"))
         .args(["-f", "path/to/bitcoin-docker-compose.yml", "up", "-d"])
         .status()?;
 
-    println!(
+    say(format!(
         "  {} Bitcoin regtest network started successfully.",
         "✓".bold().green()
-    );
+    ));
     Ok(())
 }
 
 pub fn stop_bitcoin_regtest() -> Result<()> {
-    println!(
+    say(format!(
         "  {} Stopping Bitcoin regtest network...",
         "→".bold().blue()
-    );
+    ));
     let (docker_compose_cmd, docker_compose_args) = get_docker_compose_command();
 
     Command::new(docker_compose_cmd)
         .args(docker_compose_args)
+        .args(settings::compose_project_args("server"))
         .args(["-f", "path/to/bitcoin-docker-compose.yml", "down"])
         .status()?;
 
-    println!(
+    say(format!(
         "  {} Bitcoin regtest network stopped successfully.",
         "✓".bold().green()
-    );
+    ));
     Ok(())
 }
 
-pub async fn start_dkg(config: &Config) -> Result<()> {
-    println!(
-        "{}",
-        "Starting Distributed Key Generation (DKG) process..."
-            .bold()
-            .green()
-    );
+/// Limits how long and how often `dkg start` retries, counting attempts across all its loops
+struct RetryBudget {
+    started: std::time::Instant,
+    timeout: Option<Duration>,
+    max_retries: Option<u32>,
+    attempts: u32,
+}
+
+impl RetryBudget {
+    fn new(timeout: Option<Duration>, max_retries: Option<u32>) -> Self {
+        Self {
+            started: std::time::Instant::now(),
+            timeout,
+            max_retries,
+            attempts: 0,
+        }
+    }
+
+    /// Records a failed attempt, failing with it once the budget is spent
+    fn retry(&mut self, last_error: String) -> Result<()> {
+        self.attempts += 1;
+        let limit = match (self.max_retries, self.timeout) {
+            (Some(max_retries), _) if self.attempts > max_retries => format!("--max-retries {}", max_retries),
+            (_, Some(timeout)) if self.started.elapsed() >= timeout => format!("--timeout {}s", timeout.as_secs()),
+            _ => return Ok(()),
+        };
+        Err(anyhow!(
+            "Gave up after {} attempt(s), {} reached. Last error: {}",
+            self.attempts,
+            limit,
+            last_error
+        ))
+    }
+}
+
+/// Error messages the leader answers `start_dkg` with
+const DKG_ALREADY_OCCURRED: &str = "dkg already occured";
+const DKG_NODE_NOT_READY: &str = "node not ready for dkg";
+
+pub async fn start_dkg(args: &DkgStartArgs, config: &Config) -> Result<()> {
+    say("Starting Distributed Key Generation (DKG) process...".bold().green());
 
     let leader_rpc = config
         .get_string("leader_rpc_endpoint")
@@ -1878,9 +4660,11 @@ pub async fn start_dkg(config: &Config) -> Result<()> {
 
     // Create an HTTP client with a timeout
     let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(30))
+        .timeout(Duration::from_secs(args.http_timeout))
         .build()?;
 
+    let mut budget = RetryBudget::new(args.timeout.map(Duration::from_secs), args.max_retries);
+
     // Prepare the RPC request
     let rpc_request = serde_json::json!({
         "jsonrpc": "2.0",
@@ -1893,7 +4677,7 @@ pub async fn start_dkg(config: &Config) -> Result<()> {
     loop {
         match client.get(&leader_rpc).send().await {
             Ok(_) => {
-                println!("  {} Leader node is up", "✓".bold().green());
+                say(format!("  {} Leader node is up", "✓".bold().green()));
                 break;
             }
             Err(e) => {
@@ -1902,6 +4686,7 @@ pub async fn start_dkg(config: &Config) -> Result<()> {
                     "⚠".bold().yellow(),
                     e
                 );
+                budget.retry(format!("leader node is not up: {}", e))?;
                 tokio::time::sleep(Duration::from_secs(5)).await;
             }
         }
@@ -1928,14 +4713,15 @@ pub async fn start_dkg(config: &Config) -> Result<()> {
 
             if let Some(error) = result.get("error") {
                 let error_message = error["message"].as_str().unwrap_or("Unknown error");
-                if error_message == "dkg already occured" {
-                    println!("  {} DKG process already occurred", "✓".bold().green());
+                if error_message == DKG_ALREADY_OCCURRED {
+                    say(format!("  {} DKG process already occurred", "✓".bold().green()));
                     break;
-                } else if error_message == "node not ready for dkg" {
+                } else if error_message == DKG_NODE_NOT_READY {
                     println!(
                         "  {} Node not ready for DKG, retrying...",
                         "⚠".bold().yellow()
                     );
+                    budget.retry(error_message.to_string())?;
                     tokio::time::sleep(Duration::from_secs(5)).await;
                     continue;
                 } else {
@@ -1947,12 +4733,12 @@ pub async fn start_dkg(config: &Config) -> Result<()> {
                     return Err(anyhow!(error_message.to_string()));
                 }
             } else {
-                println!("  {} DKG process started successfully", "✓".bold().green());
-                println!(
+                say(format!("  {} DKG process started successfully", "✓".bold().green()));
+                detail(format!(
                     "  {} Response: {}",
                     "ℹ".bold().blue(),
                     serde_json::to_string_pretty(&result).unwrap()
-                );
+                ));
             }
         } else {
             let error_message = response
@@ -1961,6 +4747,7 @@ pub async fn start_dkg(config: &Config) -> Result<()> {
                 .context("Failed to get error message")?;
             println!("  {} Failed to start DKG process", "✗".bold().red());
             println!("  {} Error: {}", "ℹ".bold().blue(), error_message);
+            budget.retry(error_message)?;
             tokio::time::sleep(Duration::from_secs(5)).await;
         }
     }
@@ -1982,14 +4769,15 @@ pub async fn start_dkg(config: &Config) -> Result<()> {
 
             if let Some(error) = result.get("error") {
                 let error_message = error["message"].as_str().unwrap_or("Unknown error");
-                if error_message == "dkg already occured" {
-                    println!("  {} DKG process already occurred", "✓".bold().green());
+                if error_message == DKG_ALREADY_OCCURRED {
+                    say(format!("  {} DKG process already occurred", "✓".bold().green()));
                     break;
                 } else {
                     println!(
                         "  {} Waiting for DKG process to complete...",
                         "⚠".bold().yellow()
                     );
+                    budget.retry(format!("DKG has not completed: {}", error_message))?;
                     tokio::time::sleep(Duration::from_secs(5)).await;
                 }
             }
@@ -2000,6 +4788,7 @@ pub async fn start_dkg(config: &Config) -> Result<()> {
                 .context("Failed to get error message")?;
             println!("  {} Failed to check DKG process status", "✗".bold().red());
             println!("  {} Error: {}", "ℹ".bold().blue(), error_message);
+            budget.retry(error_message)?;
             tokio::time::sleep(Duration::from_secs(5)).await;
         }
     }
@@ -2007,6 +4796,75 @@ pub async fn start_dkg(config: &Config) -> Result<()> {
     Ok(())
 }
 
+/// Sends a single `start_dkg` request to the leader and reports the DKG state from its answer.
+/// The node has no read-only status method, so a leader that has not started DKG starts it.
+pub async fn dkg_status(config: &Config) -> Result<()> {
+    let leader_rpc = config
+        .get_string("leader_rpc_endpoint")
+        .context("Failed to get leader RPC endpoint from config")?;
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()?;
+
+    let response = client
+        .post(&leader_rpc)
+        .json(&json!({
+            "jsonrpc": "2.0",
+            "method": "start_dkg",
+            "params": [],
+            "id": 1
+        }))
+        .send()
+        .await
+        .with_context(|| format!("Could not reach the leader node at {}", leader_rpc))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow!("The leader node at {} answered HTTP {}: {}", leader_rpc, status, body));
+    }
+
+    let result: Value = response.json().await.context("Failed to parse JSON response")?;
+    let error_message = result["error"]["message"].as_str();
+    let (status, detail) = match error_message {
+        Some(DKG_ALREADY_OCCURRED) => ("completed", "DKG has already occurred".to_string()),
+        Some(DKG_NODE_NOT_READY) => ("node not ready", "The node is not ready for DKG yet".to_string()),
+        Some(message) if message.to_lowercase().contains("in progress") => ("in progress", message.to_string()),
+        // Anything else is a real failure, such as an internal error, not a DKG state
+        Some(message) => ("error", format!("The leader answered with an error: {}", message)),
+        None => ("in progress", "DKG had not started, and the leader started it on this request".to_string()),
+    };
+
+    if json_output() {
+        print_json(&json!({
+            "leader": leader_rpc,
+            "status": status,
+            "message": detail,
+        }))?;
+        return ensure_dkg_status_known(status, &detail);
+    }
+
+    let glyph = match status {
+        "completed" => "✓".bold().green(),
+        "in progress" => "→".bold().blue(),
+        "error" => "✗".bold().red(),
+        _ => "⚠".bold().yellow(),
+    };
+    println!("  {} DKG status: {}", glyph, status.bold());
+    println!("  {} {}", "ℹ".bold().blue(), detail);
+
+    ensure_dkg_status_known(status, &detail)
+}
+
+/// Fails on an unrecognised leader error after it was reported, so scripts see a non-zero exit
+fn ensure_dkg_status_known(status: &str, detail: &str) -> Result<()> {
+    if status == "error" {
+        return Err(anyhow!("Could not determine the DKG status. {}", detail));
+    }
+    Ok(())
+}
+
 async fn get_connected_peer_count(client: &reqwest::Client, rpc_endpoint: &str) -> Result<usize> {
     let rpc_request = serde_json::json!({
         "jsonrpc": "2.0",
@@ -2038,41 +4896,43 @@ async fn get_connected_peer_count(client: &reqwest::Client, rpc_endpoint: &str)
 }
 
 pub fn start_arch_nodes() -> Result<()> {
-    println!("  {} Starting Arch Network nodes...", "→".bold().blue());
+    say(format!("  {} Starting Arch Network nodes...", "→".bold().blue()));
     let (docker_compose_cmd, docker_compose_args) = get_docker_compose_command();
 
     Command::new(docker_compose_cmd)
         .args(docker_compose_args)
+        .args(settings::compose_project_args("server"))
         .args(["-f", "path/to/arch-docker-compose.yml", "up", "-d"])
         .status()?;
 
-    println!(
+    say(format!(
         "  {} Arch Network nodes started successfully.",
         "✓".bold().green()
-    );
+    ));
     Ok(())
 }
 
 pub fn stop_arch_nodes() -> Result<()> {
-    println!("  {} Stopping Arch Network nodes...", "→".bold().blue());
+    say(format!("  {} Stopping Arch Network nodes...", "→".bold().blue()));
     let (docker_compose_cmd, docker_compose_args) = get_docker_compose_command();
 
     Command::new(docker_compose_cmd)
         .args(docker_compose_args)
+        .args(settings::compose_project_args("server"))
         .args(["-f", "path/to/arch-docker-compose.yml", "down"])
         .status()?;
 
-    println!(
+    say(format!(
         "  {} Arch Network nodes stopped successfully.",
         "✓".bold().green()
-    );
+    ));
     Ok(())
 }
 
 pub fn load_config(network: &str) -> Result<Config> {
     let config_path = get_config_path()?;
     let config_dir = config_path.parent().unwrap().to_str().unwrap().to_string();
-    println!("Loading config for network: {}", network);
+    detail(format!("Loading config for network: {}", network));
     
     let mut builder = Config::builder();
     
@@ -2086,17 +4946,17 @@ pub fn load_config(network: &str) -> Result<Config> {
     // Check if the user config file exists
     if config_path.exists() {
         builder = builder.add_source(File::with_name(config_path.to_str().unwrap()));
-        println!(
+        detail(format!(
             "  {} Loading configuration from {}",
             "→".bold().blue(),
             config_path.display().to_string().yellow()
-        );
+        ));
     } else {
-        println!(
+        say(format!(
             "  {} Warning: {} not found.",
             "⚠".bold().yellow(),
             config_path.display().to_string().yellow()
-        );
+        ));
     }
 
     // Add environment variables and set config_dir
@@ -2111,20 +4971,20 @@ pub fn load_config(network: &str) -> Result<Config> {
     let e2e_config: Option<Value> = initial_config.get("networks.e2e").ok();
 
     if e2e_config.is_none() {
-        println!(
+        say(format!(
             "  {} e2e network configuration not found, creating from default...",
             "ℹ".bold().blue()
-        );
+        ));
 
         // If config file doesn't exist, create the directory and file
         if !config_path.exists() {
             fs::create_dir_all(&config_dir)?;
             fs::write(&config_path, default_config)?;
-            println!(
+            say(format!(
                 "  {} Created new config file at {}",
                 "✓".bold().green(),
                 config_path.display().to_string().yellow()
-            );
+            ));
         } else {
             // Read existing config
             let mut config_content = fs::read_to_string(&config_path)?;
@@ -2142,11 +5002,11 @@ pub fn load_config(network: &str) -> Result<Config> {
                 config_content.push_str(&toml::to_string(e2e_network)?);
 
                 fs::write(&config_path, config_content)?;
-                println!(
+                say(format!(
                     "  {} Added e2e network configuration to {}",
                     "✓".bold().green(),
                     config_path.display().to_string().yellow()
-                );
+                ));
             }
         }
 
@@ -2163,6 +5023,26 @@ pub fn load_config(network: &str) -> Result<Config> {
     // Try to get the network-specific configuration
     let network_config: Option<Value> = initial_config.get(&format!("networks.{}", network)).ok();
 
+    // `--leader-rpc` wins, then the network's own endpoint, and only then the local default.
+    // A validator attached with `validator start --attach` replaces local endpoints only
+    let configured_endpoint = |key: &str| {
+        initial_config
+            .get_string(key)
+            .ok()
+            .filter(|endpoint| !endpoint.trim().is_empty())
+    };
+    // `host.docker.internal` is how containers reach the host; the CLI reaches the same node on
+    // localhost, and that name does not resolve on Linux hosts
+    let network_endpoint = configured_endpoint(&format!("networks.{}.leader_rpc_endpoint", network))
+        .map(|endpoint| endpoint.replace("host.docker.internal", "localhost"));
+    let local_endpoint = network_endpoint
+        .as_deref()
+        .map_or(true, |endpoint| endpoint.contains("localhost") || endpoint.contains("127.0.0.1"));
+    let leader_rpc_endpoint = settings::leader_rpc()
+        .or_else(|| local_endpoint.then(|| configured_endpoint("validator.attach_rpc_endpoint")).flatten())
+        .or(network_endpoint)
+        .unwrap_or_else(|| "http://localhost:9002".to_string());
+
     if let Some(network_config) = network_config {
         // Merge the network-specific configuration
         builder = Config::builder()
@@ -2172,17 +5052,17 @@ pub fn load_config(network: &str) -> Result<Config> {
             ))
             .add_source(initial_config);
 
-        println!(
+        detail(format!(
             "  {} Loaded network-specific configuration for {}",
             "✓".bold().green(),
             network.yellow()
-        );
+        ));
     } else {
-        println!(
+        detail(format!(
             "  {} No specific configuration found for network {}",
             "ℹ".bold().blue(),
             network.yellow()
-        );
+        ));
         builder = Config::builder().add_source(initial_config);
     }
 
@@ -2199,7 +5079,7 @@ pub fn load_config(network: &str) -> Result<Config> {
     };
     builder = builder.set_override("bitcoin.network", bitcoin_network)?;
 
-    builder = builder.set_override("leader_rpc_endpoint", "http://localhost:9002")?;
+    builder = builder.set_override("leader_rpc_endpoint", leader_rpc_endpoint)?;
 
     // Build the final configuration
     let final_config = builder
@@ -2209,6 +5089,48 @@ pub fn load_config(network: &str) -> Result<Config> {
     Ok(final_config)
 }
 
+/// Name of the in-memory network synthesized by `--network-from-url`
+pub const ADHOC_NETWORK: &str = "adhoc";
+
+/// Builds a configuration for an Arch node that has no network block in the config file.
+/// Bitcoin settings fall back to local regtest defaults.
+pub fn load_config_from_url(rpc_url: &str) -> Result<Config> {
+    let config_path = get_config_path()?;
+    let config_dir = config_path.parent().unwrap().to_str().unwrap().to_string();
+    detail(format!("Loading ad-hoc config for Arch node: {}", rpc_url));
+
+    let mut builder = Config::builder().add_source(config::File::from_str(
+        include_str!("../templates/config.default.toml"),
+        config::FileFormat::Toml,
+    ));
+    if config_path.exists() {
+        builder = builder.add_source(File::with_name(config_path.to_str().unwrap()));
+    }
+
+    let config = builder
+        .add_source(Environment::with_prefix("ARCH_CLI").separator("_"))
+        .set_override("config_dir", config_dir)?
+        .set_override("selected_network", ADHOC_NETWORK)?
+        .set_override("type", ADHOC_NETWORK)?
+        .set_override("leader_rpc_endpoint", rpc_url)?
+        .set_override("bitcoin.network", "regtest")?
+        .set_override("bitcoin_rpc_endpoint", "localhost")?
+        .set_override("bitcoin_rpc_port", "18443")?
+        .set_override("bitcoin_rpc_user", "bitcoin")?
+        .set_override("bitcoin_rpc_password", "password")?
+        .set_override("bitcoin_rpc_wallet", "devwallet")?
+        .build()
+        .context("Failed to build configuration")?;
+
+    say(format!(
+        "  {} Using an in-memory network for {}. Bitcoin-side operations such as funding assume a local regtest node and may not be available",
+        "⚠".bold().yellow(),
+        rpc_url.yellow()
+    ));
+
+    Ok(config)
+}
+
 pub fn get_arch_data_dir(config: &Config) -> Result<PathBuf> {
     let config_dir = config.get_string("config_dir")?;
     Ok(PathBuf::from(config_dir).join("arch-data"))
@@ -2275,6 +5197,105 @@ fn set_env_vars(config: &Config, network: &str) -> Result<()> {
     Ok(())
 }
 
+/// Host ports published by the compose templates, with the service that publishes them
+const HOST_PORT_VARS: &[(&str, &str)] = &[
+    ("BITCOIN_RPC_PORT", "bitcoin"),
+    ("ORD_PORT", "ordinals-ord"),
+    ("ELECTRS_REST_API_PORT", "blockstream-electrs"),
+    ("ELECTRS_ELECTRUM_PORT", "blockstream-electrs"),
+    ("BTC_RPC_EXPLORER_PORT", "btc-rpc-explorer"),
+    ("LEADER_P2P_PORT", "leader"),
+    ("LEADER_RPC_PORT", "leader"),
+    ("VALIDATOR1_P2P_PORT", "validator-1"),
+    ("VALIDATOR1_RPC_PORT", "validator-1"),
+    ("VALIDATOR2_P2P_PORT", "validator-2"),
+    ("VALIDATOR2_RPC_PORT", "validator-2"),
+    ("VALIDATOR_P2P_PORT", "local_validator"),
+    ("VALIDATOR_RPC_PORT", "local_validator"),
+];
+
+/// Returns the port a compose file uses for `env_var`, written as `${VAR}` or `${VAR:-default}`:
+/// the variable's value when set, otherwise the default. None when the file does not use it
+fn compose_port(compose: &str, env_var: &str, value: Option<String>) -> Option<u16> {
+    let default = if compose.contains(&format!("${{{}}}", env_var)) {
+        None
+    } else {
+        let prefix = format!("${{{}:-", env_var);
+        let start = compose.find(&prefix)? + prefix.len();
+        let end = start + compose[start..].find('}')?;
+        Some(compose[start..end].to_string())
+    };
+    value
+        .filter(|value| !value.trim().is_empty())
+        .or(default)?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Checks that the ports the compose file publishes from the variables set by `set_env_vars`
+/// are free, so a taken port fails with the service and port instead of a Docker error
+fn check_host_ports(compose_file: &str) -> Result<()> {
+    let compose = fs::read_to_string(compose_file)
+        .with_context(|| format!("Failed to read {}", compose_file))?;
+
+    let mut conflicts = Vec::new();
+    for (env_var, service) in HOST_PORT_VARS {
+        let Some(port) = compose_port(&compose, env_var, env::var(env_var).ok()) else {
+            continue;
+        };
+        if let Err(e) = std::net::TcpListener::bind(("0.0.0.0", port)) {
+            if e.kind() == io::ErrorKind::AddrInUse {
+                conflicts.push((*service, *env_var, port));
+            }
+        }
+    }
+
+    if conflicts.is_empty() {
+        return Ok(());
+    }
+
+    for (service, env_var, port) in &conflicts {
+        let holder = port_owner_pid(*port)
+            .map(|pid| format!(" (held by PID {})", pid))
+            .unwrap_or_default();
+        println!(
+            "  {} Port {} for {} ({}) is already in use{}",
+            "✗".bold().red(),
+            port.to_string().yellow(),
+            service.yellow(),
+            env_var,
+            holder
+        );
+    }
+    say(format!(
+        "  {} Stop the process holding the port or change it in config.toml, or pass --skip-port-check",
+        "→".bold().blue()
+    ));
+
+    Err(anyhow!("{} port(s) needed by the stack are already in use", conflicts.len()))
+}
+
+/// Returns the PID listening on a TCP port, when `lsof` can tell
+#[cfg(unix)]
+fn port_owner_pid(port: u16) -> Option<String> {
+    let output = Command::new("lsof")
+        .args(["-nP", "-t", &format!("-iTCP:{}", port), "-sTCP:LISTEN"])
+        .output()
+        .ok()?;
+    let pids: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect();
+    (!pids.is_empty()).then(|| pids.join(", "))
+}
+
+#[cfg(not(unix))]
+fn port_owner_pid(_port: u16) -> Option<String> {
+    None
+}
+
 pub fn start_docker_service(
     service_name: &str,
     container_name: &str,
@@ -2287,6 +5308,7 @@ pub fn start_docker_service(
     if !is_running {
         let output = Command::new(docker_compose_cmd)
             .args(docker_compose_args)
+            .args(settings::compose_project_args("server"))
             .args(["-f", compose_file, "up", "-d"])
             .output()?;
 
@@ -2298,17 +5320,17 @@ pub fn start_docker_service(
                 error_message
             ));
         }
-        println!(
+        say(format!(
             "  {} {} started.",
             "✓".bold().green(),
             service_name.yellow()
-        );
+        ));
     } else {
-        println!(
+        say(format!(
             "  {} {} already running.",
             "ℹ".bold().blue(),
             service_name.yellow()
-        );
+        ));
     }
 
     Ok(())
@@ -2338,11 +5360,11 @@ fn _create_docker_network(network_name: &str) -> Result<()> {
     if !output.status.success() {
         let error_message = String::from_utf8_lossy(&output.stderr);
         if error_message.contains("already exists") {
-            println!(
+            say(format!(
                 "  {} Network {} already exists, using existing network.",
                 "ℹ".bold().blue(),
                 network_name.yellow()
-            );
+            ));
         } else {
             return Err(anyhow::anyhow!(
                 "Failed to create network: {}",
@@ -2350,16 +5372,79 @@ fn _create_docker_network(network_name: &str) -> Result<()> {
             ));
         }
     } else {
-        println!(
+        say(format!(
             "  {} Created Docker network: {}",
             "✓".bold().green(),
             network_name.yellow()
-        );
+        ));
     }
 
     Ok(())
 }
 
+/// Returns true if `dir` holds an Arch program crate, i.e. a cdylib depending on `arch_program`
+fn is_program_crate(dir: &Path) -> bool {
+    fs::read_to_string(dir.join("Cargo.toml")).map_or(false, |manifest| {
+        (manifest.contains("arch_program") || manifest.contains("arch-program"))
+            && manifest.contains("cdylib")
+    })
+}
+
+/// Lists the projects in `projects_dir` that contain a program crate, sorted by name,
+/// together with the resolved program crate directory
+fn deployable_projects(projects_dir: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let mut projects: Vec<(String, PathBuf)> = fs::read_dir(projects_dir)
+        .with_context(|| format!("Failed to read projects directory {}", projects_dir.display()))?
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if !path.is_dir() {
+                return None;
+            }
+            let program_dir = find_program_crate(&path)?;
+            Some((path.file_name()?.to_string_lossy().into_owned(), program_dir))
+        })
+        .collect();
+    projects.sort();
+    Ok(projects)
+}
+
+/// Finds the program crate of a project, preferring the conventional `app/program` and
+/// `program` folders and otherwise searching a few levels deep for workspace layouts
+fn find_program_crate(project_dir: &Path) -> Option<PathBuf> {
+    for candidate in ["app/program", "program"] {
+        let dir = project_dir.join(candidate);
+        if is_program_crate(&dir) {
+            return Some(dir);
+        }
+    }
+
+    let mut level = vec![project_dir.to_path_buf()];
+    for _ in 0..4 {
+        let mut next_level = Vec::new();
+        for dir in level {
+            let Ok(entries) = fs::read_dir(&dir) else { continue };
+            let mut subdirs: Vec<PathBuf> = entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir())
+                .filter(|path| {
+                    let name = path.file_name().unwrap_or_default().to_string_lossy();
+                    !name.starts_with('.') && name != "target" && name != "node_modules"
+                })
+                .collect();
+            subdirs.sort();
+
+            if let Some(program_dir) = subdirs.iter().find(|path| is_program_crate(path)) {
+                return Some(program_dir.clone());
+            }
+            next_level.extend(subdirs);
+        }
+        level = next_level;
+    }
+
+    None
+}
+
 fn get_program_path(args: &DeployArgs, config: &Config) -> Result<PathBuf> {
     match &args.elf_path {
         Some(path) => Ok(PathBuf::from(path)),
@@ -2378,6 +5463,7 @@ fn get_program_path(args: &DeployArgs, config: &Config) -> Result<PathBuf> {
                 .map(|p| p.file_name().to_string_lossy().into_owned())
                 .collect();
 
+            require_interactive("Selecting a project to deploy")?;
             let selection = Select::new()
                 .with_prompt("Select a project to deploy")
                 .items(&selections)
@@ -2411,7 +5497,7 @@ async fn deploy_program_with_tx_info(
             &rpc_url,
         )
         .await?;
-        println!("  {} Program deployed successfully", "✓".bold().green());
+        say(format!("  {} Program deployed successfully", "✓".bold().green()));
         Ok(())
     } else {
         println!(
@@ -2436,15 +5522,131 @@ pub fn prepare_program_keys() -> Result<(secp256k1::Keypair, Pubkey)> {
 
     create_new_key(&keys_file)
 }
-fn load_keys(keys_file: &PathBuf) -> Result<Value> {
+/// Reads a keys file, decrypting it first when it was encrypted with `account encrypt`.
+/// Entries in the old accounts-file layout are upgraded and the file is rewritten.
+fn load_keys(keys_file: &Path) -> Result<Value> {
     let keys_content = fs::read_to_string(keys_file)?;
-    Ok(serde_json::from_str(&keys_content)?)
+    let mut keys: Value = serde_json::from_str(&keys_content)?;
+    if keystore::is_encrypted(&keys) {
+        keys = keystore::decrypt(&keys)?;
+    }
+
+    let migrated = migrate_keys(&mut keys);
+    if migrated > 0 {
+        write_keys(keys_file, &keys)?;
+        say(format!(
+            "  {} Upgraded {} account(s) in {} to the name-keyed format",
+            "ℹ".bold().blue(),
+            migrated,
+            keys_file.display()
+        ));
+    }
+    Ok(keys)
+}
+
+/// Upgrades entries written in the old accounts-file layout, keyed by public key as
+/// `{name, private_key, public_key}`, to the keys.json layout keyed by name as
+/// `{public_key, secret_key}`. The public key is derived again from the secret key, since the
+/// old layout stored it in its 33-byte compressed form. Entries without a valid secret key are
+/// left in place with a warning. Returns the number of upgraded entries.
+fn migrate_keys(keys: &mut Value) -> usize {
+    let Some(entries) = keys.as_object_mut() else {
+        return 0;
+    };
+
+    let legacy: Vec<String> = entries
+        .iter()
+        .filter(|(_, info)| info.get("name").is_some() || info.get("private_key").is_some())
+        .map(|(id, _)| id.clone())
+        .collect();
+
+    let secp = Secp256k1::new();
+    let mut migrated = 0;
+    for id in &legacy {
+        // An entry without a usable secret key cannot be upgraded, so it is left as it is
+        let info = &entries[id];
+        let Some(secret_key_hex) = info["secret_key"].as_str().or_else(|| info["private_key"].as_str()) else {
+            println!(
+                "  {} Account '{}' in keys.json has no secret key, leaving it in the old format",
+                "⚠".bold().yellow(),
+                id
+            );
+            continue;
+        };
+        let Ok(secret_key) = SecretKey::from_str(secret_key_hex) else {
+            println!(
+                "  {} Account '{}' in keys.json has an invalid secret key, leaving it in the old format",
+                "⚠".bold().yellow(),
+                id
+            );
+            continue;
+        };
+        let secret_key_hex = secret_key_hex.to_string();
+        let info = entries.remove(id).expect("entry listed above");
+        let pubkey = arch_pubkey_from_secp(&secret_key.public_key(&secp));
+
+        // Keep both entries when the name is already taken by a key stored the new way
+        let name = info["name"].as_str().unwrap_or(id);
+        let mut unique_name = name.to_string();
+        let mut counter = 1;
+        while entries.contains_key(&unique_name) {
+            unique_name = format!("{}_{}", name, counter);
+            counter += 1;
+        }
+
+        entries.insert(
+            unique_name,
+            json!({
+                "public_key": hex::encode(pubkey.serialize()),
+                "secret_key": secret_key_hex,
+            }),
+        );
+        migrated += 1;
+    }
+
+    migrated
+}
+
+fn keys_file_is_encrypted(keys_file: &Path) -> bool {
+    fs::read_to_string(keys_file)
+        .ok()
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        .map_or(false, |keys| keystore::is_encrypted(&keys))
+}
+
+/// Writes a keys file with its entries sorted by name so that diffs stay minimal
+fn write_keys(keys_file: &Path, keys: &Value) -> Result<()> {
+    let sorted = match keys.as_object() {
+        Some(obj) => {
+            let mut entries: Vec<(&String, &Value)> = obj.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(name, value)| (name.clone(), value.clone()))
+                    .collect(),
+            )
+        }
+        None => keys.clone(),
+    };
+
+    // Keep an encrypted file encrypted
+    let contents = if keys_file_is_encrypted(keys_file) {
+        keystore::encrypt(&sorted)?
+    } else {
+        sorted
+    };
+
+    fs::write(keys_file, serde_json::to_string_pretty(&contents)?)?;
+    Ok(())
 }
 
 fn select_existing_key(keys: &mut Value) -> Result<(secp256k1::Keypair, Pubkey)> {
     let mut account_names: Vec<String> = keys.as_object().unwrap().keys().cloned().collect();
+    account_names.sort();
     account_names.push("Create a new key".to_string());
 
+    require_interactive("Selecting a program key (pass --program-key instead)")?;
     let selection = Select::new()
         .with_prompt("Select a key to use as the program key")
         .items(&account_names)
@@ -2458,9 +5660,9 @@ fn select_existing_key(keys: &mut Value) -> Result<(secp256k1::Keypair, Pubkey)>
             .interact_text()?;
 
         let secp = Secp256k1::new();
-        let (secret_key, public_key) = secp.generate_keypair(&mut OsRng);
+        let (secret_key, public_key) = settings::generate_keypair(&secp);
         let keypair = secp256k1::Keypair::from_secret_key(&secp, &secret_key);
-        let pubkey = Pubkey::from_slice(&public_key.serialize()[1..33]);
+        let pubkey = arch_pubkey_from_secp(&public_key);
 
         // Save the new key to the keys Value
         let new_key_value = json!({
@@ -2471,7 +5673,7 @@ fn select_existing_key(keys: &mut Value) -> Result<(secp256k1::Keypair, Pubkey)>
 
         // Save the updated keys to the file
         let keys_file = get_config_dir()?.join("keys.json");
-        fs::write(&keys_file, serde_json::to_string_pretty(keys)?)?;
+        write_keys(&keys_file, keys)?;
 
         println!("  {} Created and saved new key '{}'", "✓".bold().green(), new_key_name);
 
@@ -2485,7 +5687,8 @@ fn select_existing_key(keys: &mut Value) -> Result<(secp256k1::Keypair, Pubkey)>
 }
 
 fn create_new_key(keys_file: &PathBuf) -> Result<(secp256k1::Keypair, Pubkey)> {
-    println!("No existing keys found or keys.json is empty.");
+    say("No existing keys found or keys.json is empty.");
+    require_interactive("Creating a new program key")?;
     if Confirm::new()
         .with_prompt("Do you want to create a new key?")
         .interact()?
@@ -2495,28 +5698,84 @@ fn create_new_key(keys_file: &PathBuf) -> Result<(secp256k1::Keypair, Pubkey)> {
             .interact_text()?;
 
         let secp = Secp256k1::new();
-        let (secret_key, public_key) = secp.generate_keypair(&mut OsRng);
+        let (secret_key, public_key) = settings::generate_keypair(&secp);
         let keypair = secp256k1::Keypair::from_secret_key(&secp, &secret_key);
-        let pubkey = Pubkey::from_slice(&public_key.serialize()[1..33]); // Use only the 32-byte compressed public key
+        let pubkey = arch_pubkey_from_secp(&public_key);
 
         save_keypair_to_json(keys_file, &keypair, &pubkey, &name)?;
 
-        println!("New key created and saved as '{}'", name);
+        say(format!("New key created and saved as '{}'", name));
         Ok((keypair, pubkey))
     } else {
         Err(anyhow!("No key selected or created"))
     }
 }
 
+/// Arch account pubkey of a secp256k1 key: its 32-byte x-only (BIP340) form. The parity byte
+/// of a compressed key and the y coordinate of an uncompressed one are not part of it.
+pub fn arch_pubkey_from_secp(public_key: &secp256k1::PublicKey) -> Pubkey {
+    Pubkey::from_slice(&public_key.x_only_public_key().0.serialize())
+}
+
 fn with_secret_key(secret_key_hex: &str) -> Result<(secp256k1::Keypair, Pubkey)> {
     let secp = Secp256k1::new();
     let secret_key = SecretKey::from_str(secret_key_hex)?;
     let keypair = secp256k1::Keypair::from_secret_key(&secp, &secret_key);
-    let public_key = keypair.public_key();
-    let pubkey = Pubkey::from_slice(&public_key.serialize()[1..33]); // Use only the 32-byte compressed public key
+    let pubkey = arch_pubkey_from_secp(&keypair.public_key());
     Ok((keypair, pubkey))
 }
 
+/// Loads keypairs from files holding hex-encoded secret keys, the format `account export` writes
+fn load_signer_files(files: &[PathBuf]) -> Result<Vec<Keypair>> {
+    files
+        .iter()
+        .map(|file| {
+            let secret_key_hex = fs::read_to_string(file)
+                .with_context(|| format!("Failed to read signer key file {}", file.display()))?;
+            with_secret_key(secret_key_hex.trim())
+                .map(|(keypair, _)| keypair)
+                .with_context(|| format!("Invalid secret key in {}", file.display()))
+        })
+        .collect()
+}
+
+/// Checks that the signers are exactly the accounts the instruction requires signatures from,
+/// since the node rejects transactions with missing or extra signatures
+fn check_signers(instruction: &Instruction, signers: &[Keypair]) -> Result<()> {
+    let mut required: Vec<String> = instruction
+        .accounts
+        .iter()
+        .filter(|account| account.is_signer)
+        .map(|account| hex::encode(account.pubkey.serialize()))
+        .collect();
+    required.sort();
+    required.dedup();
+
+    let mut provided: Vec<String> = signers
+        .iter()
+        .map(|signer| hex::encode(XOnlyPublicKey::from_keypair(signer).0.serialize()))
+        .collect();
+    provided.sort();
+    provided.dedup();
+
+    if provided.len() != signers.len() {
+        return Err(anyhow!("The same signer key was given more than once"));
+    }
+    if required != provided {
+        let missing: Vec<&str> = required.iter().filter(|key| !provided.contains(key)).map(String::as_str).collect();
+        let extra: Vec<&str> = provided.iter().filter(|key| !required.contains(key)).map(String::as_str).collect();
+        return Err(anyhow!(
+            "The instruction needs {} signature(s) but {} signer(s) were given. Missing: [{}], not required: [{}]",
+            required.len(),
+            signers.len(),
+            missing.join(", "),
+            extra.join(", ")
+        ));
+    }
+
+    Ok(())
+}
+
 fn save_keypair_to_json(
     file_path: &PathBuf,
     keypair: &Keypair,
@@ -2524,7 +5783,7 @@ fn save_keypair_to_json(
     name: &str,
 ) -> Result<()> {
     let mut keys: Value = if file_path.exists() {
-        serde_json::from_str(&fs::read_to_string(file_path)?)?
+        load_keys(file_path)?
     } else {
         json!({})
     };
@@ -2536,57 +5795,116 @@ fn save_keypair_to_json(
 
     keys[name] = account_info;
 
-    fs::write(file_path, serde_json::to_string_pretty(&keys)?)?;
+    write_keys(file_path, &keys)?;
     Ok(())
 }
 
 fn generate_new_keypair() -> Result<(secp256k1::Keypair, Pubkey)> {
     let secp = Secp256k1::new();
-    let (secret_key, _) = secp.generate_keypair(&mut OsRng);
+    let (secret_key, _) = settings::generate_keypair(&secp);
     let keypair = secp256k1::Keypair::from_secret_key(&secp, &secret_key);
-
-    // Handle the public key format consistently
-    let serialized_pubkey = keypair.public_key().serialize();
-    let pubkey = if serialized_pubkey.len() == 33 {
-        // Compressed key format
-        Pubkey::from_slice(&serialized_pubkey[1..33])
-    } else if serialized_pubkey.len() == 65 {
-        // Uncompressed key format
-        Pubkey::from_slice(&serialized_pubkey[1..33])
-    } else {
-        return Err(anyhow!("Invalid public key length: {}", serialized_pubkey.len()));
-    };
+    let pubkey = arch_pubkey_from_secp(&keypair.public_key());
 
     Ok((keypair, pubkey))
 }
 
 fn display_program_id(program_pubkey: &Pubkey) {
     let program_pubkey_hex = hex::encode(program_pubkey.serialize());
-    println!(
+    say(format!(
         "  {} Program ID: {}",
         "ℹ".bold().blue(),
         program_pubkey_hex.yellow()
-    );
+    ));
 }
 
-async fn ensure_wallet_balance(client: &Client) -> Result<()> {
-    let balance = client.get_balance(None, None)?;
-    if balance == Amount::ZERO {
-        println!(
-            "  {} Generating initial blocks for mining rewards...",
-            "→".blue()
-        );
-        let new_address = client.get_new_address(None, None)?;
-        let checked_address = new_address.require_network(arch_program::bitcoin::Network::Regtest)?;
+async fn ensure_wallet_balance(client: &Client, needed: Amount) -> Result<()> {
+    ensure_spendable_balance(client, Network::Regtest, needed).await
+}
+
+/// Makes sure the wallet can spend at least `needed` by mining blocks. Coinbase rewards only become
+/// spendable after 100 confirmations, so a wallet holding nothing but immature coinbase outputs
+/// gets just enough extra blocks to mature them instead of being treated as funded.
+async fn ensure_spendable_balance(client: &Client, network: Network, needed: Amount) -> Result<()> {
+    // Any spendable balance will do when no particular amount is needed
+    let needed = needed.max(Amount::from_sat(1));
+    let balances = client.get_balances()?;
+    if balances.mine.trusted >= needed {
+        return Ok(());
+    }
+
+    let new_address = client.get_new_address(None, None)?;
+    let checked_address = new_address.require_network(network)?;
+
+    if balances.mine.immature == Amount::ZERO {
+        say(format!(
+            "  {} Generating initial blocks to receive mining rewards...",
+            "→".bold().blue()
+        ));
         client.generate_to_address(101, &checked_address)?;
-        println!("  {} Initial blocks generated", "✓".green());
-        tokio::time::sleep(Duration::from_secs(1)).await;
+        say(format!("  {} Initial blocks generated", "✓".bold().green()));
+    } else {
+        say(format!(
+            "  {} Wallet only holds {} of immature coinbase. Mining blocks until it matures...",
+            "→".bold().blue(),
+            balances.mine.immature
+        ));
+        let mut mined = 0;
+        while client.get_balances()?.mine.trusted < needed {
+            if mined > 100 {
+                return Err(anyhow!("Wallet has no spendable balance after mining {} blocks", mined));
+            }
+            client.generate_to_address(1, &checked_address)?;
+            mined += 1;
+            if mined % 10 == 0 {
+                say(format!("    Mined {} blocks so far...", mined));
+            }
+        }
+        say(format!(
+            "  {} Coinbase matured after {} additional blocks",
+            "✓".bold().green(),
+            mined
+        ));
     }
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
     Ok(())
 }
+
+/// Satoshis sent to a new account's address when neither --fund-amount nor
+/// bitcoin.funding_amount is set
+const DEFAULT_FUNDING_SATS: u64 = 5000;
+
+/// Smallest output a taproot address accepts without it being dust
+const MIN_FUNDING_SATS: u64 = 330;
+
+/// Amount sent to back a new account, from bitcoin.funding_amount in the config
+fn funding_amount(config: &Config) -> Result<Amount> {
+    // Read as a string so that negative and fractional values are rejected instead of rounded
+    let sats = match config.get_string("bitcoin.funding_amount") {
+        Ok(value) => value.trim().parse::<u64>().map_err(|_| {
+            anyhow!(
+                "bitcoin.funding_amount must be a whole number of satoshis, got '{}'",
+                value
+            )
+        })?,
+        Err(config::ConfigError::NotFound(_)) => DEFAULT_FUNDING_SATS,
+        Err(e) => return Err(anyhow!("Invalid bitcoin.funding_amount: {}", e)),
+    };
+    if sats < MIN_FUNDING_SATS {
+        return Err(anyhow!(
+            "bitcoin.funding_amount must be at least {} satoshis, smaller outputs are dust",
+            MIN_FUNDING_SATS
+        ));
+    }
+    Ok(Amount::from_sat(sats))
+}
+
+/// Sends `amount` to `account_address` from the wallet on regtest and testnet and waits for the
+/// confirmation. On other networks it asks for a deposit and waits until the wallet holds `amount`.
 async fn fund_address(
     rpc: &Client,
     account_address: &str,
+    amount: Amount,
     config: &Config,
 ) -> Result<Option<bitcoincore_rpc::json::GetTransactionResult>> {
     let network = config
@@ -2595,7 +5913,7 @@ async fn fund_address(
     let bitcoin_network =
         Network::from_str(&network).context("Invalid Bitcoin network specified in config")?;
 
-        println!("Network: {}", bitcoin_network);
+        say(format!("Network: {}", bitcoin_network));
 
     let address = Address::from_str(account_address).context("Invalid account address")?;
     let checked_address = address
@@ -2603,28 +5921,14 @@ async fn fund_address(
         .context("Account address does not match the configured Bitcoin network")?;
 
     if bitcoin_network == Network::Regtest || bitcoin_network == Network::Testnet {
-        // Ensure the wallet has funds
-        let balance = rpc.get_balance(None, None)?;
-        if balance == Amount::ZERO {
-            println!(
-                "  {} Generating initial blocks to receive mining rewards...",
-                "→".bold().blue()
-            );
-            let new_address = rpc.get_new_address(None, None)?;
-            let checked_address = new_address.require_network(bitcoin_network)?;
-            rpc.generate_to_address(101, &checked_address)?;
-            println!(
-                "  {} Initial blocks generated. Waiting for balance to be available...",
-                "✓".bold().green()
-            );
-            tokio::time::sleep(Duration::from_secs(1)).await;
-        }
+        // Ensure the wallet has spendable funds
+        ensure_spendable_balance(rpc, bitcoin_network, amount).await?;
 
-        println!("Sending funds to address: {}", checked_address.to_string());
+        say(format!("Sending funds to address: {}", checked_address.to_string()));
 
         let tx = rpc.send_to_address(
             &checked_address,
-            Amount::from_sat(5000),
+            amount,
             None,                           // comment
             None,                           // comment_to
             Some(false),                    // subtract_fee_from_amount
@@ -2633,11 +5937,11 @@ async fn fund_address(
             Some(bitcoincore_rpc::json::EstimateMode::Economical), // estimate_mode
         )?;
 
-        println!(
+        say(format!(
             "  {} Transaction sent: {}",
             "✓".bold().green(),
             tx.to_string().yellow()
-        );
+        ));
         // Generate a block to confirm the transaction
         let new_address = rpc.get_new_address(None, None)?;
         let checked_new_address = new_address.require_network(bitcoin_network)?;
@@ -2653,12 +5957,15 @@ async fn fund_address(
         );
 
         let start_time = std::time::Instant::now();
-        let timeout = Duration::from_secs(3600); // 60 minutes timeout
+        let timeout = settings::funding_timeout();
 
         // Wait for transaction confirmation
         loop {
             if start_time.elapsed() > timeout {
-                pb.finish_with_message("❌ Transaction confirmation timed out after 60 minutes");
+                pb.finish_with_message(format!(
+                    "❌ Transaction confirmation timed out after {} minutes",
+                    timeout.as_secs() / 60
+                ));
                 return Err(anyhow!("Transaction confirmation timed out"));
             }
 
@@ -2697,15 +6004,20 @@ async fn fund_address(
         println!(
             "  {} Minimum required: {} satoshis",
             "ℹ".bold().blue(),
-            "3000".yellow()
+            amount.to_sat().to_string().yellow()
         );
-        println!("  {} Waiting for funds...", "⏳".bold().blue());
+        say(format!("  {} Waiting for funds...", "⏳".bold().blue()));
 
         // Implement balance checking for non-REGTEST networks
+        let start_time = std::time::Instant::now();
+        let timeout = settings::funding_timeout();
         loop {
+            if start_time.elapsed() > timeout {
+                return Err(anyhow!("Timed out waiting for funds after {} minutes", timeout.as_secs() / 60));
+            }
             let balance = rpc.get_balance(None, None)?;
-            if balance > Amount::from_sat(5000) {
-                println!("  {} Funds received", "✓".bold().green());
+            if balance >= amount {
+                say(format!("  {} Funds received", "✓".bold().green()));
                 return Ok(None);
             }
             tokio::time::sleep(Duration::from_secs(1)).await;
@@ -2720,15 +6032,26 @@ pub fn get_rpc_url_with_fallback(rpc_url: Option<String>, config: &Config) -> Re
     match rpc_url {
         Some(url) if !url.trim().is_empty() => Ok(url),
         _ => {
-            // If rpc_url is None or empty, try getting from config
+            // If rpc_url is None or empty, use the leader endpoint resolved by load_config
             match config.get_string("leader_rpc_endpoint") {
                 Ok(url) if !url.trim().is_empty() => Ok(url),
-                _ => Ok(common::constants::NODE1_ADDRESS.to_string())
+                _ => Err(anyhow!(
+                    "No leader RPC endpoint configured. Set leader_rpc_endpoint for the network or pass --rpc-url"
+                )),
             }
         }
     }
 }
 
+/// Fails fast with an actionable error when the Arch node at `rpc_url` cannot be reached
+pub async fn probe_rpc_url(rpc_url: &str) -> Result<()> {
+    match tokio::time::timeout(Duration::from_secs(10), get_best_block_hash_async(rpc_url)).await {
+        Ok(Ok(_)) => Ok(()),
+        Ok(Err(e)) => Err(anyhow!("cannot reach Arch node at {}: {}", rpc_url, e)),
+        Err(_) => Err(anyhow!("cannot reach Arch node at {}: request timed out", rpc_url)),
+    }
+}
+
 async fn deploy_program(
     program_keypair: &Keypair,
     program_pubkey: &Pubkey,
@@ -2742,35 +6065,31 @@ async fn deploy_program(
     create_program_account(program_keypair, program_pubkey, txid, vout, rpc_url.clone()).await?;
 
     // Deploy the program transactions
-    deploy_program_txs_with_folder(program_keypair, program_pubkey, deploy_folder, config, rpc_url.clone()).await?;
+    deploy_program_txs_with_folder(program_keypair, deploy_folder, config, rpc_url.clone()).await?;
 
     // Make program executable
-    tokio::task::block_in_place(move || {
-        make_program_executable(program_keypair, program_pubkey, rpc_url)
-    }).await?;
+    make_program_executable(program_keypair, program_pubkey, rpc_url).await?;
 
     Ok(())
 }
 
 fn build_program_from_path(program_dir: &PathBuf) -> Result<()> {
-    println!("  ℹ Building program...");
-
-    // Change to the program directory
-    std::env::set_current_dir(program_dir).context("Failed to change to program directory")?;
+    say("  ℹ Building program...");
 
     let output = Command::new("cargo")
+        .current_dir(program_dir)
         .args(["build-sbf", "--manifest-path", "Cargo.toml"])
         .output()
         .context("Failed to execute cargo build-sbf")?;
 
     if !output.status.success() {
         let error_message = String::from_utf8_lossy(&output.stderr);
-        println!("Build process encountered an error:");
-        println!("{}", error_message);
+        eprintln!("Build process encountered an error:");
+        eprintln!("{}", error_message);
         return Err(anyhow!("Build failed"));
     }
 
-    println!("  ✓ Program built successfully");
+    say("  ✓ Program built successfully");
     Ok(())
 }
 
@@ -2779,8 +6098,10 @@ pub async fn deploy_program_from_path(
     config: &Config,
     keypair: Option<(Keypair, Pubkey)>,
     rpc_url: String,
-) -> Result<()> {
-    println!("  ℹ Deploying program...");
+    verify: bool,
+    resume: bool,
+) -> Result<Vec<String>> {
+    say("  ℹ Deploying program...");
 
     // Get or prepare program keys
     let (program_keypair, program_pubkey) = keypair.ok_or_else(|| anyhow!("No keypair provided"))?;
@@ -2794,17 +6115,18 @@ pub async fn deploy_program_from_path(
     let so_file_path = find_program_so_file(program_dir)?;
 
     // Deploy the program
-    deploy_program_txs(
+    let txids = deploy_program_txs(
         &so_file_path,
         &program_keypair,
-        &program_pubkey,
         config,
         rpc_url,
+        verify,
+        resume,
     ).await?;
 
-    println!("  ✓ Program deployed successfully");
+    say("  ✓ Program deployed successfully");
     display_program_id(&program_pubkey);
-    Ok(())
+    Ok(txids)
 }
 
 async fn make_program_executable(
@@ -2812,7 +6134,7 @@ async fn make_program_executable(
     program_pubkey: &Pubkey,
     rpc_url: &String,
 ) -> Result<()> {
-    println!("    Making program executable...");
+    say("    Making program executable...");
 
     let instruction = Instruction {
         program_id: Pubkey::system_program(),
@@ -2824,31 +6146,26 @@ async fn make_program_executable(
         data: vec![2],
     };
 
-    let keypair = program_keypair.clone();
-    let rpc_url_clone = rpc_url.clone();
-    let (txid, _) = tokio::task::spawn_blocking(move || {
-        sign_and_send_instruction(instruction, vec![keypair], rpc_url_clone)
-    }).await??;
+    let (txid, _) =
+        sign_and_send_instruction_async(instruction, vec![*program_keypair], rpc_url.clone()).await?;
 
-    println!("    Transaction sent: {}", txid);
+    say(format!("    Transaction sent: {}", txid));
 
-    let rpc_url_clone = rpc_url.clone();
-    tokio::task::spawn_blocking(move || {
-        get_processed_transaction(&rpc_url_clone, txid.clone())
-    }).await??;
+    get_processed_transaction_async(rpc_url, txid).await?;
 
-    println!("    Program made executable successfully");
+    say("    Program made executable successfully");
     Ok(())
 }
 
 async fn deploy_program_txs(
     so_file_path: &PathBuf,
     program_keypair: &Keypair,
-    program_pubkey: &Pubkey,
     config: &Config,
     rpc_url: String,
-) -> Result<()> {
-    println!("  ℹ Deploying program from: {:?}", so_file_path);
+    verify: bool,
+    resume: bool,
+) -> Result<Vec<String>> {
+    say(format!("  ℹ Deploying program from: {:?}", so_file_path));
 
     // Read the .so file
     let elf = fs::read(so_file_path)
@@ -2859,89 +6176,319 @@ async fn deploy_program_txs(
     let bitcoin_network =
         Network::from_str(&network).context("Invalid Bitcoin network specified in config")?;
 
-    let txs = elf
-        .chunks(extend_bytes_max_len())
-        .enumerate()
-        .map(|(i, chunk)| {
-            let mut bytes = vec![];
-
-            let offset: u32 = (i * extend_bytes_max_len()) as u32;
-            let len: u32 = chunk.len() as u32;
-
-            bytes.extend(offset.to_le_bytes());
-            bytes.extend(len.to_le_bytes());
-            bytes.extend(chunk);
-
-            let message = Message {
-                signers: vec![*program_pubkey],
-                instructions: vec![SystemInstruction::new_extend_bytes_instruction(
-                    bytes,
-                    *program_pubkey,
-                )],
-            };
+    let program_pubkey =
+        Pubkey::from_slice(&XOnlyPublicKey::from_keypair(program_keypair).0.serialize());
+    let program_hex = hex::encode(program_pubkey.serialize());
+    let elf_sha256 = bitcoin::hashes::sha256::Hash::hash(&elf).to_string();
+    let state_path = deploy_state_path(so_file_path);
+
+    let start_offset = if resume {
+        resume_offset(&rpc_url, &program_pubkey, &elf, &state_path, &program_hex, &elf_sha256).await
+    } else {
+        0
+    };
 
-            let digest_slice = message.hash();
+    // Report per-chunk progress either as a progress bar or as JSON events
+    let pb = if progress_json() {
+        None
+    } else {
+        let pb = ProgressBar::new(0);
+        pb.set_style(ProgressStyle::default_bar()
+            .progress_chars("#>-")
+            .template("{spinner:.green}[{elapsed_precise:.blue}] {msg:.blue} [{bar:100.green/blue}] {pos}/{len} ({eta})").unwrap());
+        pb.set_message("Processing Deployment Transactions:");
+        Some(pb)
+    };
 
-            RuntimeTransaction {
-                version: 0,
-                signatures: vec![common::signature::Signature(
-                    sign_message_bip322(&program_keypair, &digest_slice, bitcoin_network).to_vec(),
-                )],
-                message,
+    timing_phase("uploading chunks");
+    let txids = deploy_program_txs_from_async(
+        &rpc_url,
+        program_keypair,
+        &elf,
+        bitcoin_network,
+        start_offset,
+        deploy_concurrency(config),
+        |processed, total| {
+            // The first report comes once every chunk is sent, before any is confirmed
+            if processed == 0 {
+                timing_phase("waiting for chunk confirmations");
             }
-        })
-        .collect::<Vec<RuntimeTransaction>>();
+            if let Some(pb) = &pb {
+                pb.set_length(total as u64);
+                pb.set_position(processed as u64);
+            }
+            progress_event("deploy", processed, total, "Processed deployment transactions");
+        },
+        |offset| {
+            // Checkpointing is best effort, a missing state file only means a resume starts earlier
+            let _ = write_deploy_checkpoint(&state_path, &program_hex, Some((&elf_sha256, offset)));
+        },
+    )
+    .await?;
+
+    if let Some(pb) = pb {
+        pb.finish();
+    }
+
+    // Every chunk is confirmed, so there is nothing left to resume
+    let _ = write_deploy_checkpoint(&state_path, &program_hex, None);
+
+    if verify {
+        timing_phase("verifying the upload");
+        verify_uploaded_program(&rpc_url, &program_pubkey, &elf).await?;
+    }
+
+    Ok(txids)
+}
+
+/// Prints the transaction count and projected fees of deploying `elf_path`, for `deploy --dry-run`
+fn print_deploy_estimate(elf_path: &Path, program_pubkey: &Pubkey, config: &Config) -> Result<()> {
+    let elf = fs::read(elf_path)
+        .with_context(|| format!("Failed to read ELF binary at {}", elf_path.display()))?;
+
+    // One extend-bytes transaction per chunk, plus the one marking the program executable
+    let chunk_len = extend_bytes_max_len();
+    let chunks = (elf.len() + chunk_len - 1) / chunk_len;
+    let transactions = chunks + 1;
+    let tx_bytes = elf.len() + transactions * (RUNTIME_TX_SIZE_LIMIT - chunk_len);
+
+    // A rough heuristic rather than a quote: the runtime transaction bytes are counted at a
+    // quarter of their size, as if carried in witness data, plus the Bitcoin transaction that
+    // funds the program account. What the node actually pays depends on how it anchors them
+    let funding_vbytes = funding_tx_vsize(1) as usize;
+    let vbytes = (tx_bytes + 3) / 4 + funding_vbytes;
+    let estimated_rate = WalletManager::new(config)
+        .ok()
+        .and_then(|wallet_manager| wallet_manager.client.estimate_smart_fee(1, None).ok())
+        .and_then(|estimate| estimate.fee_rate)
+        .map(|per_kvb| (per_kvb.to_sat() as f64 / 1000.0).max(1.0));
+    let fee_rate = estimated_rate.unwrap_or(1.0);
+    let fee_sats = (vbytes as f64 * fee_rate).ceil() as u64;
+    let program_hex = hex::encode(program_pubkey.serialize());
+
+    if json_output() {
+        return print_json(&json!({
+            "program_id": program_hex,
+            "elf_path": elf_path.display().to_string(),
+            "elf_bytes": elf.len(),
+            "chunk_bytes": chunk_len,
+            "chunks": chunks,
+            "transactions": transactions,
+            "transaction_bytes": tx_bytes,
+            "funding_tx_vbytes": funding_vbytes,
+            "fee_rate_sat_per_vb": fee_rate,
+            "fee_rate_estimated": estimated_rate.is_some(),
+            "projected_fee_sats": fee_sats,
+            "projection": "heuristic",
+        }));
+    }
+
+    println!("  {} Program ID: {}", "ℹ".bold().blue(), program_hex.yellow());
+    println!("  {} Binary: {} ({} bytes)", "ℹ".bold().blue(), elf_path.display(), elf.len());
+    println!(
+        "  {} Transactions: {} ({} chunks of up to {} bytes, plus 1 to make the program executable)",
+        "ℹ".bold().blue(),
+        transactions,
+        chunks,
+        chunk_len
+    );
+    match estimated_rate {
+        Some(rate) => println!("  {} Fee rate: {:.1} sat/vB (wallet estimate for the next block)", "ℹ".bold().blue(), rate),
+        None => println!("  {} Fee rate: no wallet estimate available, assuming 1 sat/vB", "⚠".bold().yellow()),
+    }
+    println!(
+        "  {} Projected Bitcoin fees (rough heuristic): ~{} sats ({} vB, including {} vB for the account funding transaction)",
+        "ℹ".bold().blue(),
+        fee_sats,
+        vbytes,
+        funding_vbytes
+    );
+    println!("  {} Dry run: nothing was funded or sent", "✓".bold().green());
+    Ok(())
+}
 
-    let url = rpc_url.clone();
-    let url_clone = url.clone();
+/// Number of deployment transactions polled for confirmation at once, from `arch.deploy_concurrency`
+fn deploy_concurrency(config: &Config) -> usize {
+    match config.get_int("arch.deploy_concurrency") {
+        Ok(concurrency) if (1..=64).contains(&concurrency) => concurrency as usize,
+        Ok(concurrency) => {
+            say(format!(
+                "  {} Ignoring arch.deploy_concurrency = {}, it must be between 1 and 64. Using {}",
+                "⚠".bold().yellow(),
+                concurrency,
+                DEFAULT_DEPLOY_CONCURRENCY
+            ));
+            DEFAULT_DEPLOY_CONCURRENCY
+        }
+        Err(_) => DEFAULT_DEPLOY_CONCURRENCY,
+    }
+}
 
-    let txids: Vec<String> = {
-        let txs_clone = txs.clone();
-        let response = task::spawn_blocking(move || {
-            post_data(&url_clone, "send_transactions", txs_clone)
-        }).await?;
+/// State file next to a program binary recording, per program ID, the offset below which
+/// every uploaded chunk of that binary has been confirmed
+fn deploy_state_path(so_file_path: &Path) -> PathBuf {
+    so_file_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(".deploy-state")
+}
 
-        process_result(response)
-            .map_err(|e| anyhow!("Failed to process result: {}", e))?
-            .as_array()
-            .ok_or_else(|| anyhow!("Result is not an array"))?
-            .iter()
-            .filter_map(|v| v.as_str().map(String::from))
-            .collect()
-    };
+/// Returns the checkpointed offset for `program_hex`, if it was recorded for the same binary
+fn read_deploy_checkpoint(state_path: &Path, program_hex: &str, elf_sha256: &str) -> Option<usize> {
+    let state: Value = serde_json::from_str(&fs::read_to_string(state_path).ok()?).ok()?;
+    let entry = state.get(program_hex)?;
+    if entry["sha256"].as_str()? != elf_sha256 {
+        return None;
+    }
+    entry["offset"].as_u64().map(|offset| offset as usize)
+}
 
-    let pb = ProgressBar::new(txids.len() as u64);
-    pb.set_style(ProgressStyle::default_bar()
-        .progress_chars("#>-")
-        .template("{spinner:.green}[{elapsed_precise:.blue}] {msg:.blue} [{bar:100.green/blue}] {pos}/{len} ({eta})").unwrap());
-    pb.set_message("Processing Deployment Transactions:");
+/// Records the confirmed offset for `program_hex`, or removes its entry when `checkpoint` is None
+fn write_deploy_checkpoint(
+    state_path: &Path,
+    program_hex: &str,
+    checkpoint: Option<(&str, usize)>,
+) -> Result<()> {
+    let mut state: serde_json::Map<String, Value> = fs::read_to_string(state_path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default();
 
-    for txid in txids {
-        let url_clone = url.clone();
-        let txid_clone = txid.clone();
-        task::spawn_blocking(move || {
-            get_processed_transaction(&url_clone, txid_clone)
-        }).await??;
-        pb.inc(1);
+    match checkpoint {
+        Some((elf_sha256, offset)) => {
+            state.insert(program_hex.to_string(), json!({ "sha256": elf_sha256, "offset": offset }));
+        }
+        None => {
+            if state.remove(program_hex).is_none() {
+                return Ok(());
+            }
+        }
     }
 
-    pb.finish();
+    if state.is_empty() {
+        if state_path.exists() {
+            fs::remove_file(state_path)?;
+        }
+        return Ok(());
+    }
+    fs::write(state_path, serde_json::to_string_pretty(&state)?)?;
     Ok(())
 }
 
+/// Works out where a resumed upload should start: after the leading chunks that are already
+/// on chain with the right bytes, and no later than the last checkpoint for this binary
+async fn resume_offset(
+    rpc_url: &str,
+    program_pubkey: &Pubkey,
+    elf: &[u8],
+    state_path: &Path,
+    program_hex: &str,
+    elf_sha256: &str,
+) -> usize {
+    let deployed = match read_account_info_async(rpc_url, *program_pubkey).await {
+        Ok(account) => account.data,
+        Err(_) => {
+            say(format!("  {} Program account not found, uploading from the start", "ℹ".bold().blue()));
+            return 0;
+        }
+    };
+
+    let chunk_len = extend_bytes_max_len();
+    let mut offset = 0;
+    while offset < elf.len() {
+        let end = (offset + chunk_len).min(elf.len());
+        if deployed.get(offset..end) != Some(&elf[offset..end]) {
+            break;
+        }
+        offset = end;
+    }
+
+    // Chunks past the checkpoint may have landed out of order, so they are sent again
+    if let Some(checkpoint) = read_deploy_checkpoint(state_path, program_hex, elf_sha256) {
+        offset = offset.min(checkpoint);
+    }
+
+    let total_chunks = (elf.len() + chunk_len - 1) / chunk_len;
+    let done_chunks = (offset + chunk_len - 1) / chunk_len;
+    if offset >= elf.len() {
+        say(format!(
+            "  {} All {} chunks are already on chain, nothing to upload",
+            "✓".bold().green(),
+            total_chunks
+        ));
+    } else {
+        say(format!(
+            "  {} Resuming upload at offset {} ({} of {} chunks already on chain)",
+            "ℹ".bold().blue(),
+            offset,
+            done_chunks,
+            total_chunks
+        ));
+    }
+    offset
+}
+
+/// Reads the program account back and compares it with the uploaded ELF, reporting
+/// the byte ranges of the chunks that did not land so they can be retried
+async fn verify_uploaded_program(rpc_url: &str, program_pubkey: &Pubkey, elf: &[u8]) -> Result<()> {
+    say(format!("  {} Verifying the uploaded program...", "→".bold().blue()));
+
+    let account = read_account_info_async(rpc_url, *program_pubkey)
+        .await
+        .context("Failed to read the program account back for verification")?;
+    let deployed = &account.data;
+
+    if deployed.len() == elf.len()
+        && bitcoin::hashes::sha256::Hash::hash(deployed) == bitcoin::hashes::sha256::Hash::hash(elf)
+    {
+        say(format!(
+            "  {} On-chain program matches the local binary ({} bytes, sha256 {})",
+            "✓".bold().green(),
+            elf.len(),
+            bitcoin::hashes::sha256::Hash::hash(elf)
+        ));
+        return Ok(());
+    }
+
+    // Group the mismatching upload chunks into contiguous byte ranges
+    let chunk_len = extend_bytes_max_len();
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for start in (0..elf.len()).step_by(chunk_len) {
+        let end = (start + chunk_len).min(elf.len());
+        if deployed.get(start..end) == Some(&elf[start..end]) {
+            continue;
+        }
+        match ranges.last_mut() {
+            Some(last) if last.1 == start => last.1 = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+
+    let ranges = ranges
+        .iter()
+        .map(|(start, end)| format!("{}..{}", start, end))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Err(anyhow!(
+        "On-chain program ({} bytes) does not match the local binary ({} bytes). Mismatching or missing byte ranges: {}",
+        deployed.len(),
+        elf.len(),
+        if ranges.is_empty() { "trailing data past the end of the binary".to_string() } else { ranges }
+    ))
+}
+
 async fn deploy_program_txs_with_folder(
     program_keypair: &Keypair,
-    program_pubkey: &Pubkey,
     deploy_folder: Option<String>,
     config: &Config,
     rpc_url: String,
 ) -> Result<()> {
-    println!("    Deploying program transactions...");
+    say("    Deploying program transactions...");
 
     let program_dir = deploy_folder
         .ok_or_else(|| anyhow!("No deploy folder specified"))?;
 
-    println!("  ℹ Program directory: {}", program_dir);
+    detail(format!("  ℹ Program directory: {}", program_dir));
 
     // Pass the program directory directly without modifying the path
     let program_dir = PathBuf::from(program_dir);
@@ -2949,14 +6496,15 @@ async fn deploy_program_txs_with_folder(
     if let Err(e) = deploy_program_txs(
         &program_dir,
         program_keypair,
-        program_pubkey,
         config,
         rpc_url,
+        false,
+        false,
     ).await {
         println!("Failed to deploy program transactions: {}", e);
         return Err(e);
     }
-    println!("    Program transactions deployed successfully");
+    say("    Program transactions deployed successfully");
     Ok(())
 }
 
@@ -2967,30 +6515,24 @@ async fn create_program_account(
     vout: u32,
     rpc_url: String,
 ) -> Result<()> {
-    println!("    Creating program account...");
-
-    let program_keypair_clone = program_keypair.clone();
-    let program_pubkey_clone = *program_pubkey;
-    let txid_clone = txid.to_string();
-    let url = rpc_url.clone();
-
-    let (txid, _) = tokio::task::spawn_blocking(move || {
-        sign_and_send_instruction(
-            SystemInstruction::new_create_account_instruction(
-                hex::decode(txid_clone).unwrap().try_into().unwrap(),
-                vout,
-                program_pubkey_clone,
-            ),
-            vec![program_keypair_clone],
-            url,
-        )
-    }).await??;
+    say("    Creating program account...");
+
+    let txid_bytes: [u8; 32] = hex::decode(txid)?
+        .try_into()
+        .map_err(|_| anyhow!("Funding transaction ID must be 32 bytes"))?;
+
+    sign_and_send_instruction_async(
+        SystemInstruction::new_create_account_instruction(txid_bytes, vout, *program_pubkey),
+        vec![*program_keypair],
+        rpc_url,
+    )
+    .await?;
 
     Ok(())
 }
 
 pub async fn demo_start(args: &DemoStartArgs, config: &Config) -> Result<()> {
-    println!("{}", "Starting the demo application...".bold().green());
+    say("Starting the demo application...".bold().green());
 
     match args.target.as_str() {
         "local" => start_local_demo(args, config).await,
@@ -3000,40 +6542,37 @@ pub async fn demo_start(args: &DemoStartArgs, config: &Config) -> Result<()> {
 }
 
 pub async fn start_local_demo(args: &DemoStartArgs, config: &Config) -> Result<()> {
-    println!("{}", "Starting the demo application...".bold().green());
+    say("Starting the demo application...".bold().green());
 
     // Get the selected network from the config
-    let selected_network = config.get_string("selected_network")
-        .unwrap_or_else(|_| "development".to_string());
+    let selected_network = selected_network(config);
 
     // Set environment variables for the selected network
     set_env_vars(config, &selected_network)?;
 
     // Get the project directory from the config
-    let project_dir = config
-        .get_string("project.directory")
-        .context("Failed to get project directory from config")?;
+    let project_dir = get_project_dir(config)?;
 
     // Define the demo directory in the project
-    let demo_dir = PathBuf::from(&project_dir).join("projects/demo");
+    let demo_dir = project_dir.join("projects/demo");
 
     // Check if the demo directory exists, if not, copy it from the CLI directory
     if !demo_dir.exists() {
-        println!(
+        say(format!(
             "  {} Demo directory not found. Creating it...",
             "ℹ".bold().blue()
-        );
+        ));
 
         // Create the 'demo' folder within the project directory
         fs::create_dir_all(&demo_dir)?;
-        println!(
+        say(format!(
             "  {} Created demo directory at {:?}",
             "✓".bold().green(),
             demo_dir
-        );
+        ));
 
         // Extract demo files from binary
-        extract_recursive(&PROJECT_DIR, &demo_dir)?;
+        extract_demo(None, &demo_dir)?;
 
         // Rename the .env.example file to .env
         let env_example_file = PathBuf::from(&demo_dir).join("app/frontend/.env.example");
@@ -3041,18 +6580,15 @@ pub async fn start_local_demo(args: &DemoStartArgs, config: &Config) -> Result<(
             fs::rename(&env_example_file, PathBuf::from(&demo_dir).join("app/frontend/.env"))?;
         }
 
-        println!(
+        say(format!(
             "  {} Extracted demo template to {:?}",
             "✓".bold().green(),
             demo_dir
-        );
+        ));
     }
 
-    // Change to the demo directory
-    std::env::set_current_dir(&demo_dir).context("Failed to change to demo directory")?;
-
     let env_file = PathBuf::from(&demo_dir).join("app/frontend/.env");
-    println!("  {} Attempting to read .env file from: {:?}", "ℹ".bold().blue(), env_file);
+    detail(format!("  {} Attempting to read .env file from: {:?}", "ℹ".bold().blue(), env_file));
 
     // Check if the file exists
     if !env_file.exists() {
@@ -3061,10 +6597,10 @@ pub async fn start_local_demo(args: &DemoStartArgs, config: &Config) -> Result<(
         // Check if .env.example exists
         let env_example_file = PathBuf::from(&demo_dir).join("app/frontend/.env.example");
         if env_example_file.exists() {
-            println!("  {} Found .env.example file, attempting to copy it", "→".bold().blue());
+            say(format!("  {} Found .env.example file, attempting to copy it", "→".bold().blue()));
             fs::copy(&env_example_file, &env_file)
                 .with_context(|| format!("Failed to copy .env.example to .env. Source: {:?}, Destination: {:?}", env_example_file, env_file))?;
-            println!("  {} Successfully created .env file from .env.example", "✓".bold().green());
+            say(format!("  {} Successfully created .env file from .env.example", "✓".bold().green()));
         } else {
             println!("  {} Neither .env nor .env.example files found in frontend directory", "✗".bold().red());
             return Err(anyhow!("Missing required .env files in {:?}", env_file.parent().unwrap_or_else(|| Path::new(""))));
@@ -3096,8 +6632,8 @@ pub async fn start_local_demo(args: &DemoStartArgs, config: &Config) -> Result<(
         .unwrap_or("")
         .to_string();
 
-    println!("  {} Read program pubkey from .env: '{}'", "ℹ".bold().blue(),
-        if program_pubkey.is_empty() { "not found" } else { &program_pubkey });
+    detail(format!("  {} Read program pubkey from .env: '{}'", "ℹ".bold().blue(),
+        if program_pubkey.is_empty() { "not found" } else { &program_pubkey }));
 
     // If program_pubkey is empty then create a new account for the program
     let keys_file = get_config_dir()?.join("keys.json");
@@ -3116,12 +6652,18 @@ pub async fn start_local_demo(args: &DemoStartArgs, config: &Config) -> Result<(
             name
         };
 
-        println!("Creating account with name: {}", graffiti_key_name);
+        say(format!("Creating account with name: {}", graffiti_key_name));
 
         // Call create_account with the graffiti_key_name
         create_account(&CreateAccountArgs {
             name: graffiti_key_name.clone(),
             program_id: None,
+            space: None,
+            fund_from: None,
+            deterministic: false,
+            index: None,
+            no_fund: false,
+            fund_amount: None,
             rpc_url: Some(args.rpc_url.clone().unwrap_or_default()),
         }, config).await?;
 
@@ -3136,19 +6678,21 @@ pub async fn start_local_demo(args: &DemoStartArgs, config: &Config) -> Result<(
     } else {
         // If program_pubkey is not empty, we need to find the corresponding key name
         graffiti_key_name = find_key_name_by_pubkey(&keys_file, &program_pubkey)?;
-        println!("Using existing account with name: {}", graffiti_key_name);
+        say(format!("Using existing account with name: {}", graffiti_key_name));
     }
 
     // Get the program keypair from the keys.json file
     let program_keypair = get_keypair_from_name(&graffiti_key_name, &keys_file)?;
-    let program_pubkey = Pubkey::from_slice(&program_keypair.public_key().serialize()[1..33]);
+    let program_pubkey = arch_pubkey_from_secp(&program_keypair.public_key());
 
     // Deploy the program with the existing keypair
     deploy_program_from_path(
         &PathBuf::from(&demo_dir).join("app/program"),
         config,
         Some((program_keypair.clone(), program_pubkey)),
-        get_rpc_url_with_fallback(args.rpc_url.clone(), config).unwrap(),
+        get_rpc_url_with_fallback(args.rpc_url.clone(), config)?,
+        false,
+        false,
     ).await?;
 
     // Make the program executable
@@ -3158,12 +6702,18 @@ pub async fn start_local_demo(args: &DemoStartArgs, config: &Config) -> Result<(
     let graffiti_wall_state_exists = key_name_exists(&keys_file, "graffiti_wall_state")?;
 
     if graffiti_wall_state_exists {
-        println!("  {} Using existing graffiti_wall_state account", "ℹ".bold().blue());
+        say(format!("  {} Using existing graffiti_wall_state account", "ℹ".bold().blue()));
     } else {
-        println!("  {} Creating new graffiti_wall_state account", "ℹ".bold().blue());
+        say(format!("  {} Creating new graffiti_wall_state account", "ℹ".bold().blue()));
         create_account(&CreateAccountArgs {
             name: "graffiti_wall_state".to_string(),
             program_id: Some(hex::encode(program_pubkey.serialize())),
+            space: None,
+            fund_from: None,
+            deterministic: false,
+            index: None,
+            no_fund: false,
+            fund_amount: None,
             rpc_url: Some(args.rpc_url.clone().unwrap_or_default()),
         }, config).await?;
     }
@@ -3179,15 +6729,14 @@ pub async fn start_local_demo(args: &DemoStartArgs, config: &Config) -> Result<(
 
     if !args.skip_cleanup {
         // Stop existing demo containers
-        println!(
+        say(format!(
             "  {} Stopping any existing demo containers...",
             "→".bold().blue()
-        );
-
-        // Change to the demo directory
-        std::env::set_current_dir(&demo_dir).context("Failed to change to demo directory")?;
+        ));
 
         let stop_output = ShellCommand::new("docker-compose")
+            .args(settings::compose_project_args("demo"))
+            .current_dir(&demo_dir)
             .arg("-f")
             .arg("app/demo-docker-compose.yml")
             .arg("down")
@@ -3200,14 +6749,14 @@ pub async fn start_local_demo(args: &DemoStartArgs, config: &Config) -> Result<(
                 "⚠".bold().yellow()
             );
         } else {
-            println!(
+            say(format!(
                 "  {} Existing demo containers stopped successfully",
                 "✓".bold().green()
-            );
+            ));
         }
 
         // Remove the arch-network
-        println!("  {} Removing arch-network...", "→".bold().blue());
+        say(format!("  {} Removing arch-network...", "→".bold().blue()));
         let remove_network_output = ShellCommand::new("docker")
             .args(&["network", "rm", "arch-network"])
             .output()
@@ -3220,32 +6769,16 @@ pub async fn start_local_demo(args: &DemoStartArgs, config: &Config) -> Result<(
             }
         }
 
-        println!("  {} arch-network removed", "✓".bold().green());
+        say(format!("  {} arch-network removed", "✓".bold().green()));
     } else {
-        println!(
+        say(format!(
             "  {} Skipping cleanup of existing containers and network",
             "ℹ".bold().blue()
-        );
-    }
-
-    // Remove the arch-network
-    println!("  {} Removing arch-network...", "→".bold().blue());
-    let remove_network_output = ShellCommand::new("docker")
-        .args(&["network", "rm", "arch-network"])
-        .output()
-        .context("Failed to remove arch-network")?;
-
-    if !remove_network_output.status.success() {
-        let error_message = String::from_utf8_lossy(&remove_network_output.stderr);
-        if !error_message.contains("not found") {
-            println!("  {} Warning: Failed to remove arch-network: {}", "⚠".bold().yellow(), error_message);
-        }
+        ));
     }
 
-    println!("  {} arch-network removed", "✓".bold().green());
-
     // Create the arch-network if it doesn't exist
-    println!("  {} Creating arch-network...", "→".bold().blue());
+    say(format!("  {} Creating arch-network...", "→".bold().blue()));
     let create_network_output = ShellCommand::new("docker")
         .args(&["network", "create", "arch-network"])
         .output()
@@ -3258,15 +6791,19 @@ pub async fn start_local_demo(args: &DemoStartArgs, config: &Config) -> Result<(
         }
     }
 
-    println!("  {} arch-network created or already exists", "✓".bold().green());
+    say(format!("  {} arch-network created or already exists", "✓".bold().green()));
 
     // Convert program_pubkey to a string to ensure it's not dropped before use
     let program_pubkey_str = hex::encode(program_pubkey.serialize());
 
     // Convert graffiti_wall_state_pubkey to a string to ensure it's not dropped before use
     let graffiti_wall_state_pubkey_str = graffiti_wall_state_pubkey.to_string();
-    let demo_frontend_port_str = "5173".to_string();
-    let indexer_port_str = "5175".to_string();
+    let demo_frontend_port_str = config
+        .get_string("demo.frontend_port")
+        .unwrap_or_else(|_| "5173".to_string());
+    let indexer_port_str = config
+        .get_string("indexer.port")
+        .unwrap_or_else(|_| "5175".to_string());
 
     let env_vars = vec![
         ("VITE_PROGRAM_PUBKEY", &program_pubkey_str),
@@ -3276,23 +6813,16 @@ pub async fn start_local_demo(args: &DemoStartArgs, config: &Config) -> Result<(
         ("VITE_RPC_URL", &rpc_url),
     ];
 
-    // Print the current directory
-    println!("Current directory: {:?}", std::env::current_dir()?);
-
     // Start the demo application
-    println!("  {} Starting demo containers...", "→".bold().blue());
-
-    // Store original directory before any directory changes
-    let original_dir = std::env::current_dir()?;
-
-    // Change to the demo directory
-    std::env::set_current_dir(&demo_dir)?;
+    say(format!("  {} Starting demo containers...", "→".bold().blue()));
 
     // Create the docker-compose command with environment variables
     let mut command = ShellCommand::new("docker-compose");
     command
+        .args(settings::compose_project_args("demo"))
+        .current_dir(&demo_dir)
         .arg("-f")
-        .arg("app/demo-docker-compose.yml")  // Update path to be relative to original directory
+        .arg("app/demo-docker-compose.yml")
         .arg("up")
         .arg("--build")
         .arg("-d");
@@ -3313,44 +6843,58 @@ pub async fn start_local_demo(args: &DemoStartArgs, config: &Config) -> Result<(
         ));
     }
 
-    println!(
-        "{}",
-        "Demo application started successfully!".bold().green()
-    );
-
-    // Restore original directory before docker-compose
-    std::env::set_current_dir(&original_dir)?;
+    say("Demo application started successfully!".bold().green());
 
     // Open the browser with the demo application
-    if let Err(e) = open_browser(webbrowser::Browser::Default, &format!("http://localhost:5173")) {
+    if let Err(e) = open_browser(webbrowser::Browser::Default, &format!("http://localhost:{}", demo_frontend_port_str)) {
         return Err(anyhow!("Failed to open the browser: {}", e));
     }
 
     Ok(())
 }
 
+/// Registry used for GCP images when neither `--registry` nor `gcp.registry` is set. Kept for
+/// existing deployments, although Google has deprecated Container Registry in favor of Artifact Registry
+const DEFAULT_GCP_REGISTRY: &str = "gcr.io/{project}";
+
+/// Resolves the registry GCP images are pushed to from `--registry`, then `gcp.registry`,
+/// substituting the `{project}` and `{region}` placeholders
+fn gcp_registry(registry: Option<&str>, config: &Config, project_id: &str, region: &str) -> String {
+    let template = registry
+        .map(str::to_string)
+        .or_else(|| config.get_string("gcp.registry").ok())
+        .filter(|registry| !registry.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_GCP_REGISTRY.to_string());
+
+    template
+        .trim()
+        .trim_end_matches('/')
+        .replace("{project}", project_id)
+        .replace("{region}", region)
+}
+
 async fn start_gcp_demo(args: &DemoStartArgs, config: &Config) -> Result<()> {
-    println!("Starting GCP deployment...");
+    say("Starting GCP deployment...");
 
     // Setup demo environment first
     let (demo_dir, _, _, rpc_url) = setup_demo_environment(args, config).await?;
 
     let project_id = args.gcp_project.clone()
         .ok_or_else(|| anyhow!("GCP project ID is required for GCP deployment"))?;
+    let region = args.gcp_region.as_deref().unwrap_or("us-central1");
+    let registry = gcp_registry(args.registry.as_deref(), config, &project_id, region);
 
     // Build and deploy the demo container
-    println!("Building and deploying demo container...");
+    say("Building and deploying demo container...");
 
     let demo_app_dir = demo_dir.join("app");
 
-    println!("  {} Changing to demo app directory: {:?}", "→".bold().blue(), demo_app_dir);
-
-    std::env::set_current_dir(&demo_app_dir)
-        .context("Failed to change to demo app directory")?;
+    say(format!("  {} Building from demo app directory: {:?}", "→".bold().blue(), demo_app_dir));
 
     // Build the container
-    let image_name = format!("gcr.io/{}/arch-demo", project_id);
+    let image_name = format!("{}/arch-demo", registry);
     let build_status = Command::new("docker")
+        .current_dir(&demo_app_dir)
         .args([
             "build",
             "-t", &image_name,
@@ -3364,7 +6908,7 @@ async fn start_gcp_demo(args: &DemoStartArgs, config: &Config) -> Result<()> {
         return Err(anyhow!("Failed to build demo container"));
     }
 
-    // Push to GCR
+    // Push to the registry
     let push_status = Command::new("docker")
         .args(["push", &image_name])
         .status()
@@ -3393,15 +6937,15 @@ async fn start_gcp_demo(args: &DemoStartArgs, config: &Config) -> Result<()> {
         return Err(anyhow!("Failed to deploy to Cloud Run"));
     }
 
-    println!("✓ Demo application deployed successfully to Cloud Run");
+    say("✓ Demo application deployed successfully to Cloud Run");
     Ok(())
 }
 
-async fn deploy_to_cloud_run(project_id: &str, region: &str, demo_dir: &Path) -> Result<()> {
+async fn deploy_to_cloud_run(project_id: &str, region: &str, registry: &str, demo_dir: &Path) -> Result<()> {
     // Build and push Docker image
-    let image_name = format!("gcr.io/{}/arch-demo", project_id);
+    let image_name = format!("{}/arch-demo", registry);
 
-    println!("  {} Building Docker image...", "→".bold().blue());
+    say(format!("  {} Building Docker image...", "→".bold().blue()));
     let build_output = ShellCommand::new("docker")
         .args(["build", "--platform", "linux/amd64", "-t", &image_name, "."])
         .current_dir(demo_dir.join("app/frontend"))
@@ -3411,7 +6955,7 @@ async fn deploy_to_cloud_run(project_id: &str, region: &str, demo_dir: &Path) ->
         return Err(anyhow!("Failed to build Docker image"));
     }
 
-    println!("  {} Pushing image to Container Registry...", "→".bold().blue());
+    say(format!("  {} Pushing image to Container Registry...", "→".bold().blue()));
     let push_output = ShellCommand::new("docker")
         .args(["push", &image_name])
         .output()?;
@@ -3420,7 +6964,7 @@ async fn deploy_to_cloud_run(project_id: &str, region: &str, demo_dir: &Path) ->
         return Err(anyhow!("Failed to push Docker image"));
     }
 
-    println!("  {} Deploying to Cloud Run...", "→".bold().blue());
+    say(format!("  {} Deploying to Cloud Run...", "→".bold().blue()));
     let deploy_output = ShellCommand::new("gcloud")
         .args([
             "run", "deploy", "arch-demo",
@@ -3450,12 +6994,22 @@ fn find_key_name_by_pubkey(keys_file: &PathBuf, pubkey: &str) -> Result<String>
 }
 
 fn get_pubkey_from_name(name: &str, keys_file: &Path) -> Result<String> {
-    let keys = serde_json::from_str::<serde_json::Value>(&fs::read_to_string(keys_file)?)?;
+    let keys = load_keys(keys_file)?;
     let pubkey = keys.get(name).context(format!("Key with name '{}' not found", name))?;
     let pubkey = pubkey.get("public_key").context(format!("Public key for '{}' not found", name))?;
     Ok(pubkey.as_str().context(format!("Public key for '{}' is not a string", name))?.to_string())
 }
 
+/// Resolves a hex-encoded public key or the name of a key in keys.json to a hex public key
+fn resolve_pubkey_hex(identifier: &str) -> Result<String> {
+    if identifier.len() == 64 && identifier.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Ok(identifier.to_lowercase());
+    }
+
+    get_pubkey_from_name(identifier, &get_config_dir()?.join("keys.json"))
+        .with_context(|| format!("'{}' is neither a public key nor a known key name", identifier))
+}
+
 fn get_keypair_from_name(name: &str, keys_file: &PathBuf) -> Result<Keypair> {
     let keys = load_keys(keys_file)?;
 
@@ -3475,25 +7029,22 @@ fn get_keypair_from_name(name: &str, keys_file: &PathBuf) -> Result<Keypair> {
 }
 
 pub async fn demo_stop(config: &Config) -> Result<()> {
-    println!("{}", "Stopping the demo application...".bold().green());
+    say("Stopping the demo application...".bold().green());
 
     // Get the selected network from the config
-    let selected_network = config.get_string("selected_network")
-        .unwrap_or_else(|_| "development".to_string());
+    let selected_network = selected_network(config);
 
     // Set environment variables for the selected network
     set_env_vars(config, &selected_network)?;
 
     // Get the project directory from the config
-    let project_dir = config
-        .get_string("project.directory")
-        .context("Failed to get project directory from config")?;
+    let project_dir = get_project_dir(config)?;
 
-    // Change to the demo directory
-    let demo_dir = PathBuf::from(project_dir).join("projects/demo");
-    std::env::set_current_dir(&demo_dir).context("Failed to change to demo directory")?;
+    let demo_dir = project_dir.join("projects/demo");
 
     let output = ShellCommand::new("docker-compose")
+        .args(settings::compose_project_args("demo"))
+        .current_dir(&demo_dir)
         .arg("-f")
         .arg("app/demo-docker-compose.yml")
         .arg("down")
@@ -3506,11 +7057,376 @@ pub async fn demo_stop(config: &Config) -> Result<()> {
             String::from_utf8_lossy(&output.stderr)
         ));
     }
-
-    println!(
-        "{}",
-        "Demo application stopped successfully!".bold().green()
-    );
+
+    say("Demo application stopped successfully!".bold().green());
+    Ok(())
+}
+
+pub async fn demo_load_test(args: &LoadTestArgs, config: &Config) -> Result<()> {
+    say("Running graffiti wall load test...".bold().green());
+
+    if args.concurrency == 0 {
+        return Err(anyhow!("--concurrency must be at least 1"));
+    }
+
+    let rpc_url = get_rpc_url_with_fallback(args.rpc_url.clone(), config)?;
+    let keys_file = get_config_dir()?.join("keys.json");
+
+    // Resolve the wall account, defaulting to the one created by `demo start`
+    let wall_hex = match &args.wall_account {
+        Some(wall) => wall.clone(),
+        None => get_pubkey_from_name("graffiti_wall_state", &keys_file).context(
+            "No graffiti_wall_state key found. Run 'arch-cli demo start' first or pass --wall-account",
+        )?,
+    };
+    let wall_bytes = hex::decode(&wall_hex).context("Invalid wall account public key")?;
+    if wall_bytes.len() != 32 {
+        return Err(anyhow!("Wall account public key must be 32 bytes, got {}", wall_bytes.len()));
+    }
+    let wall_pubkey = Pubkey::from_slice(&wall_bytes);
+
+    // The graffiti program is whoever owns the wall account
+    let rpc_url_clone = rpc_url.clone();
+    let wall_info = tokio::task::spawn_blocking(move || read_account_info(&rpc_url_clone, wall_pubkey))
+        .await?
+        .context("Failed to read the wall account")?;
+    let program_id = wall_info.owner;
+    say(format!("  {} Program ID: {}", "ℹ".bold().blue(), hex::encode(program_id.serialize()).yellow()));
+    say(format!("  {} Wall account: {}", "ℹ".bold().blue(), wall_hex.yellow()));
+
+    // Create and fund the signer accounts
+    let signer_funding = funding_amount(config)?;
+    let wallet_manager = WalletManager::new(config)?;
+    ensure_wallet_balance(&wallet_manager.client, signer_funding * args.count as u64).await?;
+
+    let secp = Secp256k1::new();
+    let mut signers = Vec::with_capacity(args.count);
+    for index in 0..args.count {
+        say(format!(
+            "  {} Creating signer account {}/{}...",
+            "→".bold().blue(),
+            index + 1,
+            args.count
+        ));
+        let (secret_key, _) = settings::generate_keypair(&secp);
+        let keypair = Keypair::from_secret_key(&secp, &secret_key);
+        let pubkey = Pubkey::from_slice(&XOnlyPublicKey::from_keypair(&keypair).0.serialize());
+
+        let account_address = generate_account_address(&rpc_url, pubkey).await?;
+        create_arch_account(
+            &keypair,
+            &pubkey,
+            &account_address,
+            signer_funding,
+            &wallet_manager,
+            config,
+            Some(rpc_url.clone()),
+        )
+        .await?;
+        signers.push((keypair, pubkey));
+    }
+    wallet_manager.close_wallet()?;
+
+    // Submit the messages, keeping at most `concurrency` transactions in flight
+    let total = args.count * args.messages;
+    say(format!(
+        "  {} Submitting {} messages with concurrency {}...",
+        "→".bold().blue(),
+        total.to_string().yellow(),
+        args.concurrency.to_string().yellow()
+    ));
+
+    let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(args.concurrency));
+    let mut tasks = tokio::task::JoinSet::new();
+    let started = std::time::Instant::now();
+
+    for (index, (keypair, pubkey)) in signers.iter().enumerate() {
+        for sequence in 0..args.messages {
+            let permit = semaphore.clone().acquire_owned().await?;
+            let instruction = graffiti_instruction(
+                program_id,
+                *pubkey,
+                wall_pubkey,
+                &format!("load-{}", index),
+                &format!("load test message {}", sequence),
+            );
+            let keypair = *keypair;
+            let rpc_url = rpc_url.clone();
+            tasks.spawn(async move {
+                let result = sign_and_send_instruction_async(instruction, vec![keypair], rpc_url).await;
+                drop(permit);
+                result
+            });
+        }
+    }
+
+    let mut succeeded = 0usize;
+    let mut failures: Vec<String> = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok(Ok(_)) => succeeded += 1,
+            Ok(Err(e)) => failures.push(e.to_string()),
+            Err(e) => failures.push(e.to_string()),
+        }
+    }
+    let elapsed = started.elapsed().as_secs_f64();
+
+    println!(
+        "  {} Submitted {}/{} transactions in {:.2}s ({:.2} tx/s)",
+        "✓".bold().green(),
+        succeeded.to_string().yellow(),
+        total,
+        elapsed,
+        succeeded as f64 / elapsed.max(f64::EPSILON)
+    );
+
+    if !failures.is_empty() {
+        println!(
+            "  {} {} transactions failed",
+            "⚠".bold().yellow(),
+            failures.len().to_string().yellow()
+        );
+        failures.sort();
+        failures.dedup();
+        for failure in failures.iter().take(5) {
+            println!("    {}", failure);
+        }
+    }
+
+    Ok(())
+}
+
+/// Builds a graffiti wall instruction; name and message are truncated to the program's fixed sizes
+fn graffiti_instruction(
+    program_id: Pubkey,
+    signer: Pubkey,
+    wall: Pubkey,
+    name: &str,
+    message: &str,
+) -> Instruction {
+    let mut name_bytes = [0u8; 16];
+    let name_len = name.len().min(16);
+    name_bytes[..name_len].copy_from_slice(&name.as_bytes()[..name_len]);
+
+    let mut message_bytes = [0u8; 64];
+    let message_len = message.len().min(64);
+    message_bytes[..message_len].copy_from_slice(&message.as_bytes()[..message_len]);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta {
+                pubkey: signer,
+                is_signer: true,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: wall,
+                is_signer: false,
+                is_writable: true,
+            },
+        ],
+        data: [name_bytes.as_slice(), message_bytes.as_slice()].concat(),
+    }
+}
+
+/// Returns the network saved as `networks.default` in config.toml, falling back to development
+pub fn default_network() -> String {
+    get_config_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| content.parse::<Document>().ok())
+        .and_then(|doc| doc.get("networks")?.get("default")?.as_str().map(str::to_string))
+        .filter(|network| !network.trim().is_empty())
+        .unwrap_or_else(|| "development".to_string())
+}
+
+/// Returns the network this run uses: the global `--network` flag, or the saved default.
+/// load_config records it as `selected_network`, so every command sees the same one
+fn selected_network(config: &Config) -> String {
+    config
+        .get_string("selected_network")
+        .unwrap_or_else(|_| default_network())
+}
+
+pub async fn config_set_network(name: &str) -> Result<()> {
+    let config_path = get_config_path()?;
+    let mut doc = fs::read_to_string(&config_path)
+        .with_context(|| format!("Failed to read {:?}", config_path))?
+        .parse::<Document>()?;
+
+    // Only accept networks defined in the config file or in the bundled defaults
+    let defaults = include_str!("../templates/config.default.toml").parse::<Document>()?;
+    let is_defined = |doc: &Document| {
+        doc.get("networks")
+            .and_then(|networks| networks.get(name))
+            .map_or(false, |network| network.is_table())
+    };
+    if !is_defined(&doc) && !is_defined(&defaults) {
+        return Err(anyhow!(
+            "Network '{}' is not defined. Add a [networks.{}] section to {:?} first",
+            name,
+            name,
+            config_path
+        ));
+    }
+
+    if doc.get("networks").is_none() {
+        doc["networks"] = toml_edit::table();
+    }
+    doc["networks"]["default"] = value(name);
+    fs::write(&config_path, doc.to_string())?;
+
+    say(format!(
+        "  {} Default network set to {}",
+        "✓".bold().green(),
+        name.yellow()
+    ));
+    Ok(())
+}
+
+pub async fn config_get_network() -> Result<()> {
+    let network = default_network();
+    if json_output() {
+        return print_json(&json!({ "network": network }));
+    }
+
+    println!("  {} Default network: {}", "ℹ".bold().blue(), network.yellow());
+    Ok(())
+}
+
+/// Splits a dotted configuration key into its segments
+fn config_key_path(key: &str) -> Result<Vec<&str>> {
+    let segments: Vec<&str> = key.split('.').collect();
+    if segments.iter().any(|segment| segment.trim().is_empty()) {
+        return Err(anyhow!("Invalid key '{}': use a dotted path such as networks.testnet.bitcoin_rpc_port", key));
+    }
+    Ok(segments)
+}
+
+pub async fn config_get(key: &str) -> Result<()> {
+    let config_path = get_config_path()?;
+    let contents = fs::read_to_string(&config_path)
+        .with_context(|| format!("Failed to read {:?}", config_path))?;
+    let doc: toml::Value = toml::from_str(&contents)
+        .with_context(|| format!("Failed to parse {:?}", config_path))?;
+
+    let mut current = &doc;
+    for segment in config_key_path(key)? {
+        current = current
+            .get(segment)
+            .ok_or_else(|| anyhow!("Key '{}' is not set in {:?}", key, config_path))?;
+    }
+
+    if json_output() {
+        return print_json(&json!({ "key": key, "value": serde_json::to_value(current)? }));
+    }
+
+    // Print strings bare so scripts can use the output directly
+    match current {
+        toml::Value::String(s) => println!("{}", s),
+        toml::Value::Table(table) => print!("{}", toml::to_string(table)?),
+        other => println!("{}", other),
+    }
+    Ok(())
+}
+
+pub async fn config_set(key: &str, new_value: &str) -> Result<()> {
+    let config_path = get_config_path()?;
+    let mut doc = fs::read_to_string(&config_path)
+        .with_context(|| format!("Failed to read {:?}", config_path))?
+        .parse::<Document>()
+        .with_context(|| format!("Failed to parse {:?}", config_path))?;
+
+    let segments = config_key_path(key)?;
+    let (last, parents) = segments.split_last().expect("split always yields a segment");
+
+    // Create intermediate tables as needed, like update_config_with_project_dir does for [project]
+    let mut table = doc.as_table_mut();
+    for (depth, segment) in parents.iter().enumerate() {
+        let item = table.entry(segment).or_insert_with(toml_edit::table);
+        table = item.as_table_mut().ok_or_else(|| {
+            anyhow!("'{}' is not a table, so '{}' cannot be set", segments[..=depth].join("."), key)
+        })?;
+    }
+
+    // Keep string keys such as bitcoin_rpc_port strings; otherwise accept any TOML value
+    let keeps_string = table.get(last).map_or(false, |item| item.is_str());
+    let parsed = if keeps_string {
+        toml_edit::Value::from(new_value)
+    } else {
+        new_value
+            .parse::<toml_edit::Value>()
+            .unwrap_or_else(|_| toml_edit::Value::from(new_value))
+    };
+    if table.get(last).map_or(false, |item| item.is_table_like()) {
+        return Err(anyhow!("'{}' is a table; set one of its keys instead", key));
+    }
+    table[*last] = Item::Value(parsed);
+
+    let updated = doc.to_string();
+    updated
+        .parse::<Document>()
+        .with_context(|| format!("Setting '{}' would leave {:?} unparseable; nothing was written", key, config_path))?;
+    fs::write(&config_path, updated)
+        .with_context(|| format!("Failed to write {:?}", config_path))?;
+
+    if json_output() {
+        return print_json(&json!({ "key": key, "value": new_value, "path": config_path }));
+    }
+    say(format!("  {} Set {} to {}", "✓".bold().green(), key.yellow(), new_value.yellow()));
+    Ok(())
+}
+
+pub async fn config_list() -> Result<()> {
+    let config_dir = get_config_dir()?;
+    let active = settings::config_profile().unwrap_or_else(|| "default".to_string());
+
+    let mut profiles: Vec<(String, PathBuf)> = fs::read_dir(&config_dir)
+        .with_context(|| format!("Failed to read {:?}", config_dir))?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| {
+            let profile = config_profile_of(&entry.file_name().to_string_lossy())?;
+            Some((profile, entry.path()))
+        })
+        .collect();
+    profiles.sort_by(|a, b| (a.0 != "default", &a.0).cmp(&(b.0 != "default", &b.0)));
+
+    // ARCH_CLI_CONFIG points at a single file and takes precedence over profiles
+    let override_path = env::var("ARCH_CLI_CONFIG").ok();
+
+    if json_output() {
+        return print_json(&json!({
+            "active": active,
+            "override": override_path,
+            "profiles": profiles
+                .iter()
+                .map(|(name, path)| json!({
+                    "name": name,
+                    "path": path,
+                    "active": override_path.is_none() && *name == active,
+                }))
+                .collect::<Vec<_>>(),
+        }));
+    }
+
+    println!("{}", "Configuration profiles:".bold().green());
+    for (name, path) in &profiles {
+        let marker = if override_path.is_none() && *name == active {
+            "*".bold().green()
+        } else {
+            " ".normal()
+        };
+        println!("  {} {} ({})", marker, name.yellow(), path.display());
+    }
+    if let Some(path) = override_path {
+        println!(
+            "  {} ARCH_CLI_CONFIG is set; {} is used instead of any profile",
+            "ℹ".bold().blue(),
+            path.yellow()
+        );
+    }
     Ok(())
 }
 
@@ -3570,17 +7486,17 @@ pub async fn config_view(config: &Config) -> Result<()> {
 }
 
 pub async fn config_edit() -> Result<()> {
-    println!("{}", "Editing configuration...".bold().yellow());
+    say("Editing configuration...".bold().yellow());
 
     // Get the path to the configuration file
     let config_path = get_config_path()?;
 
     // Check if the config file exists
     if !config_path.exists() {
-        println!(
+        say(format!(
             "  {} Configuration file not found. Creating a default one...",
             "ℹ".bold().blue()
-        );
+        ));
         config_reset().await?;
     }
 
@@ -3595,69 +7511,231 @@ pub async fn config_edit() -> Result<()> {
             }
         });
 
-    println!(
-        "  {} Opening configuration file with {}...",
-        "→".bold().blue(),
-        editor
-    );
+    // Keep the current contents so a broken edit can be rolled back
+    let original = fs::read_to_string(&config_path)?;
 
-    // Open the editor
-    let status = Command::new(&editor)
-        .arg(&config_path)
-        .status()
-        .context(format!("Failed to open editor: {}", editor))?;
+    loop {
+        say(format!(
+            "  {} Opening configuration file with {}...",
+            "→".bold().blue(),
+            editor
+        ));
 
-    if status.success() {
-        println!(
+        // Open the editor
+        let status = Command::new(&editor)
+            .arg(&config_path)
+            .status()
+            .context(format!("Failed to open editor: {}", editor))?;
+
+        if !status.success() {
+            say(format!(
+                "  {} Editor closed without saving changes or encountered an error",
+                "ℹ".bold().blue()
+            ));
+            return Ok(());
+        }
+
+        say(format!(
             "  {} Configuration file closed. Verifying changes...",
             "✓".bold().green()
-        );
+        ));
 
-        // Attempt to reload the configuration to verify it's still valid
-        match Config::builder()
-            .add_source(config::File::with_name(config_path.to_str().unwrap()))
-            .build()
-        {
-            Ok(_) => println!(
+        let problems = config_problems(&config_path);
+        if problems.is_empty() {
+            say(format!(
                 "  {} Configuration updated successfully!",
                 "✓".bold().green()
-            ),
-            Err(e) => {
-                println!(
-                    "  {} Warning: The configuration file may contain errors.",
-                    "⚠".bold().yellow()
-                );
-                println!("    Error details: {}", e);
-                println!("    Please review and correct the configuration file.");
+            ));
+            return Ok(());
+        }
+
+        println!("  {} The configuration has problems:", "⚠".bold().yellow());
+        for problem in &problems {
+            println!("    {} {}", "✗".bold().red(), problem);
+        }
+
+        // Without a terminal there is nobody to fix the file, so only offer the rollback
+        if !settings::non_interactive() && confirm("Reopen the editor to fix them?", true)? {
+            continue;
+        }
+
+        if confirm("Restore the configuration from before this edit?", true)? {
+            fs::write(&config_path, &original)?;
+            say(format!("  {} Previous configuration restored", "✓".bold().green()));
+        } else {
+            println!(
+                "  {} Configuration left as edited. Run 'arch-cli config validate' after fixing it",
+                "⚠".bold().yellow()
+            );
+        }
+        return Ok(());
+    }
+}
+
+pub async fn config_validate() -> Result<()> {
+    let config_path = get_config_path()?;
+    println!(
+        "{} {}",
+        "Validating".bold().green(),
+        config_path.display().to_string().yellow()
+    );
+
+    let problems = config_problems(&config_path);
+    if problems.is_empty() {
+        println!("  {} Configuration is valid", "✓".bold().green());
+        return Ok(());
+    }
+
+    for problem in &problems {
+        println!("  {} {}", "✗".bold().red(), problem);
+    }
+    Err(anyhow!("Configuration has {} problem(s)", problems.len()))
+}
+
+/// Port settings outside [networks] that must hold a port number
+const CONFIG_PORT_KEYS: &[&str] = &[
+    "electrs.rest_api_port",
+    "electrs.electrum_port",
+    "btc_rpc_explorer.port",
+    "demo.frontend_port",
+    "indexer.port",
+    "ord.port",
+    "arch.bootnode_p2p_port",
+    "arch.leader_p2p_port",
+    "arch.leader_rpc_port",
+    "arch.validator1_p2p_port",
+    "arch.validator1_rpc_port",
+    "arch.validator2_p2p_port",
+    "arch.validator2_rpc_port",
+];
+
+/// Keys every `[networks.<name>]` section needs. Remote networks may leave the Bitcoin ones empty
+const NETWORK_REQUIRED_KEYS: &[&str] = &[
+    "type",
+    "leader_rpc_endpoint",
+    "bitcoin_rpc_endpoint",
+    "bitcoin_rpc_port",
+    "bitcoin_rpc_user",
+    "bitcoin_rpc_password",
+];
+
+/// Checks the user configuration file, on top of the bundled defaults, and returns what is wrong
+/// with it, each problem naming its TOML key: syntax errors, networks missing required keys,
+/// invalid endpoints and ports, and files or directories that do not exist
+fn config_problems(config_path: &Path) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    let contents = match fs::read_to_string(config_path) {
+        Ok(contents) => contents,
+        Err(e) => return vec![format!("cannot read {}: {}", config_path.display(), e)],
+    };
+    if let Err(e) = contents.parse::<Document>() {
+        return vec![format!("invalid TOML: {}", e.to_string().trim())];
+    }
+
+    let config = match Config::builder()
+        .add_source(config::File::from_str(
+            include_str!("../templates/config.default.toml"),
+            config::FileFormat::Toml,
+        ))
+        .add_source(config::File::from_str(&contents, config::FileFormat::Toml))
+        .build()
+    {
+        Ok(config) => config,
+        Err(e) => return vec![e.to_string()],
+    };
+
+    let is_port = |value: &str| value.trim().parse::<u16>().map_or(false, |port| port > 0);
+
+    let networks = config.get_table("networks").unwrap_or_default();
+    for (name, network) in &networks {
+        if name == "default" {
+            continue;
+        }
+        let Ok(network) = network.clone().into_table() else {
+            problems.push(format!("networks.{} must be a table", name));
+            continue;
+        };
+        for key in NETWORK_REQUIRED_KEYS {
+            if !network.contains_key(*key) {
+                problems.push(format!("networks.{}.{} is missing", name, key));
+            }
+        }
+        let string_value = |key: &str| {
+            network
+                .get(key)
+                .and_then(|value| value.clone().into_string().ok())
+                .filter(|value| !value.is_empty())
+        };
+        // Remote networks leave the Bitcoin RPC settings empty until they are filled in
+        if let Some(port) = string_value("bitcoin_rpc_port") {
+            if !is_port(&port) {
+                problems.push(format!("networks.{}.bitcoin_rpc_port '{}' is not a port number", name, port));
+            }
+        }
+        if let Some(endpoint) = string_value("leader_rpc_endpoint") {
+            if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+                problems.push(format!("networks.{}.leader_rpc_endpoint '{}' is not an http(s) URL", name, endpoint));
+            }
+        }
+        // Compose files are resolved against the config directory, like `server start` does
+        if let Some(compose_file) = string_value("docker_compose_file") {
+            let path = config_path.parent().unwrap_or(Path::new(".")).join(&compose_file);
+            if !path.is_file() {
+                problems.push(format!(
+                    "networks.{}.docker_compose_file '{}' does not exist ({})",
+                    name,
+                    compose_file,
+                    path.display()
+                ));
             }
         }
-    } else {
-        println!(
-            "  {} Editor closed without saving changes or encountered an error",
-            "ℹ".bold().blue()
-        );
     }
 
-    Ok(())
+    if let Ok(default) = config.get_string("networks.default") {
+        if !networks.contains_key(&default) {
+            problems.push(format!("networks.default '{}' has no [networks.{}] section", default, default));
+        }
+    }
+
+    for key in CONFIG_PORT_KEYS {
+        match config.get_string(key) {
+            Ok(port) if is_port(&port) => {}
+            Ok(port) => problems.push(format!("{} '{}' is not a port number", key, port)),
+            Err(_) => problems.push(format!("{} is missing", key)),
+        }
+    }
+
+    if let Ok(directory) = config.get_string("project.directory") {
+        if !directory.is_empty() && !Path::new(&directory).is_dir() {
+            problems.push(format!("project.directory '{}' is not an existing directory", directory));
+        }
+    }
+
+    if let Ok(concurrency) = config.get_string("arch.deploy_concurrency") {
+        if concurrency.parse::<usize>().map_or(true, |concurrency| !(1..=64).contains(&concurrency)) {
+            problems.push(format!("arch.deploy_concurrency '{}' must be a number from 1 to 64", concurrency));
+        }
+    }
+
+    problems
 }
+
 pub async fn config_reset() -> Result<()> {
-    println!(
-        "{}",
-        "Resetting configuration to default...".bold().yellow()
-    );
+    say("Resetting configuration to default...".bold().yellow());
 
     let config_path = get_config_path()?;
     let config_dir = config_path.parent().unwrap();
 
     // Check if the config file already exists
     if config_path.exists() {
-        let confirm = Confirm::new()
-            .with_prompt("Existing configuration found. Are you sure you want to reset it? This will remove all custom settings.")
-            .default(false)
-            .interact()?;
+        let confirmed = confirm(
+            "Existing configuration found. Are you sure you want to reset it? This will remove all custom settings.",
+            false,
+        )?;
 
-        if !confirm {
-            println!("  {} Configuration reset cancelled", "ℹ".bold().blue());
+        if !confirmed {
+            say(format!("  {} Configuration reset cancelled", "ℹ".bold().blue()));
             return Ok(());
         }
 
@@ -3670,11 +7748,11 @@ pub async fn config_reset() -> Result<()> {
                 e
             );
         } else {
-            println!(
+            say(format!(
                 "  {} Created backup at {}",
                 "✓".bold().green(),
                 backup_path.display()
-            );
+            ));
         }
     }
 
@@ -3688,57 +7766,252 @@ pub async fn config_reset() -> Result<()> {
     fs::write(&config_path, default_config_content)
         .context("Failed to write default configuration")?;
 
-    println!(
+    say(format!(
         "  {} Configuration reset to default at {}",
         "✓".bold().green(),
         config_path.display()
-    );
+    ));
 
-    // Ensure all template files are reset as well
-    println!("  {} Resetting template files...", "→".bold().blue());
+    // Customized templates are kept; only missing ones are recreated
+    say(format!("  {} Creating missing template files...", "→".bold().blue()));
     copy_template_files()?;
+    say(format!(
+        "  {} Use {} to replace templates that differ from this release",
+        "ℹ".bold().blue(),
+        "arch-cli templates sync --force".cyan()
+    ));
 
-    println!("\n{}", "Configuration reset complete!".bold().green());
-    println!(
+    say(format!("\n{}", "Configuration reset complete!".bold().green()));
+    say(format!(
         "  {} Use {} to view or {} to edit the new configuration",
         "ℹ".bold().blue(),
         "arch-cli config view".cyan(),
         "arch-cli config edit".cyan()
-    );
+    ));
 
     Ok(())
 }
 
-// Update the create_account function
+/// Checks whether the account derived for `--deterministic` was already created.
+/// An account that exists on chain but is missing from keys.json is saved under `args.name`.
+async fn deterministic_account_exists(
+    args: &CreateAccountArgs,
+    secret_key: &SecretKey,
+    keys_file: &PathBuf,
+    config: &Config,
+) -> Result<bool> {
+    let (keypair, pubkey) = with_secret_key(&hex::encode(secret_key.secret_bytes()))?;
+    let pubkey_hex = hex::encode(pubkey.serialize());
+    say(format!(
+        "  {} Derived account {} at index {}",
+        "ℹ".bold().blue(),
+        pubkey_hex.yellow(),
+        args.index.unwrap_or(0)
+    ));
+
+    let stored = if key_name_exists(keys_file, &args.name)? {
+        let stored = get_pubkey_from_name(&args.name, keys_file)?;
+        if stored != pubkey_hex {
+            return Err(anyhow!(
+                "An account named '{}' already exists with a different key ({}). Choose another name or index.",
+                args.name,
+                stored
+            ));
+        }
+        true
+    } else {
+        false
+    };
+
+    let rpc_url = get_rpc_url_with_fallback(args.rpc_url.clone(), config)?;
+    probe_rpc_url(&rpc_url).await?;
+    if read_account_if_exists(&rpc_url, pubkey).await?.is_none() {
+        return Ok(false);
+    }
+
+    if !stored {
+        save_keypair_to_json(keys_file, &keypair, &pubkey, &args.name)?;
+    }
+    say(format!(
+        "  {} Account '{}' already exists on chain, nothing to do",
+        "✓".bold().green(),
+        args.name
+    ));
+    Ok(true)
+}
+
+/// Finishes an account whose key was saved by `--no-fund`: once a deposit to its address has
+/// confirmed, the largest deposit output backs the account. Returns false when the account
+/// already exists on chain, so the name is simply taken.
+async fn complete_unfunded_account(args: &CreateAccountArgs, keys_file: &PathBuf, config: &Config) -> Result<bool> {
+    let keypair = get_keypair_from_name(&args.name, keys_file)?;
+    let pubkey = arch_pubkey_from_secp(&keypair.public_key());
+    let pubkey_hex = hex::encode(pubkey.serialize());
+
+    let rpc_url = get_rpc_url_with_fallback(args.rpc_url.clone(), config)?;
+    probe_rpc_url(&rpc_url).await?;
+    if read_account_if_exists(&rpc_url, pubkey).await?.is_some() {
+        return Ok(false);
+    }
+
+    let account_address = generate_account_address(&rpc_url, pubkey).await?;
+    say(format!(
+        "  {} Key '{}' is saved but has no account yet, looking for a deposit to {}",
+        "ℹ".bold().blue(),
+        args.name,
+        account_address.yellow()
+    ));
+
+    // Deposits come from other wallets, so scan the UTXO set rather than the bitcoind wallet
+    let wallet_manager = WalletManager::new(config)?;
+    let scan = wallet_manager
+        .client
+        .scan_tx_out_set_blocking(&[bitcoincore_rpc::json::ScanTxOutRequest::Single(format!(
+            "addr({})",
+            account_address
+        ))])?;
+    wallet_manager.close_wallet()?;
+    let deposit = scan.unspents.iter().max_by_key(|utxo| utxo.amount).ok_or_else(|| {
+        anyhow!(
+            "No confirmed deposit to {} yet. Deposit at least {} satoshis and run the command again",
+            account_address,
+            funding_amount(config).map_or(MIN_FUNDING_SATS, |amount| amount.to_sat())
+        )
+    })?;
+
+    timing_phase("creating the account");
+    send_create_account_instruction(&keypair, &pubkey, &deposit.txid.to_string(), deposit.vout, &rpc_url).await?;
+
+    if let Some(space) = args.space.filter(|space| *space > 0) {
+        timing_phase("allocating account data");
+        allocate_account_space(&keypair, &pubkey, space, &rpc_url).await?;
+    }
+    let owner = args.program_id.as_deref().filter(|id| !id.is_empty());
+    if let Some(hex_program_id) = owner {
+        let program_id = Pubkey::from_slice(
+            &hex::decode(hex_program_id).context("Failed to decode program ID from hex")?,
+        );
+        timing_phase("transferring ownership");
+        transfer_account_ownership(&keypair, &pubkey, &program_id, rpc_url).await?;
+    }
+
+    if json_output() {
+        print_json(&json!({
+            "name": args.name,
+            "pubkey": pubkey_hex,
+            "bitcoin_deposit_address": account_address,
+            "funded": true,
+            "owner": owner,
+        }))?;
+    } else {
+        say("Account created successfully!".bold().green());
+        println!("  {} Public Key: {}", "ℹ".bold().blue(), pubkey_hex.bright_green());
+    }
+    Ok(true)
+}
+
 pub async fn create_account(args: &CreateAccountArgs, config: &Config) -> Result<()> {
-    println!("{}", "Creating account for dApp...".bold().green());
+    say("Creating account for dApp...".bold().green());
 
     // Get the keys directory
     let keys_dir = get_config_dir()?;
     let keys_file = keys_dir.join("keys.json");
 
-    // Check if an account with the same name already exists
+    // A derived key that already exists turns the whole command into a no-op
+    let derived_key = if args.deterministic {
+        let secret_key = settings::derive_secret_key(args.index.unwrap_or(0))?;
+        if deterministic_account_exists(args, &secret_key, &keys_file, config).await? {
+            return Ok(());
+        }
+        Some(secret_key)
+    } else {
+        None
+    };
+
+    // Check if an account with the same name already exists. A key saved by --no-fund is
+    // completed instead, once its deposit has arrived
     if key_name_exists(&keys_file, &args.name)? {
+        if !args.no_fund && complete_unfunded_account(args, &keys_file, config).await? {
+            return Ok(());
+        }
         return Err(anyhow!(
             "An account with the name '{}' already exists. Please choose a different name.",
             args.name
         ));
     }
 
+    let fund_amount = match args.fund_amount {
+        Some(sats) if sats < MIN_FUNDING_SATS => {
+            return Err(anyhow!(
+                "--fund-amount must be at least {} satoshis, smaller outputs are dust",
+                MIN_FUNDING_SATS
+            ));
+        }
+        Some(sats) => Amount::from_sat(sats),
+        None => funding_amount(config)?,
+    };
+
+    // Validate the requested data length before any funds are spent
+    if let Some(space) = args.space {
+        if space > MAX_PERMITTED_DATA_LENGTH {
+            return Err(anyhow!(
+                "--space {} exceeds the maximum account data length of {} bytes",
+                space,
+                MAX_PERMITTED_DATA_LENGTH
+            ));
+        }
+        detail(format!("  {} Account data length: {} bytes", "ℹ".bold().blue(), space.to_string().yellow()));
+    }
+
     // Create a new keypair
     let secp = Secp256k1::new();
-    let (secret_key, public_key) = secp.generate_keypair(&mut OsRng);
+    let (secret_key, public_key) = match derived_key {
+        Some(secret_key) => (secret_key, secret_key.public_key(&secp)),
+        None => settings::generate_keypair(&secp),
+    };
     let caller_keypair = Keypair::from_secret_key(&secp, &secret_key);
 
-    // Convert secp256k1::PublicKey to Pubkey
-    let public_key_bytes = public_key.serialize_uncompressed();
-    let caller_pubkey = Pubkey::from_slice(&public_key_bytes[1..33]); // Skip the first byte and take the next 32
+    let caller_pubkey = arch_pubkey_from_secp(&public_key);
 
-    let rpc_url = get_rpc_url_with_fallback(args.rpc_url.clone(), config).unwrap();
-    println!("  {} RPC URL: {}", "ℹ".bold().blue(), rpc_url.yellow());
+    let rpc_url = get_rpc_url_with_fallback(args.rpc_url.clone(), config)?;
+    detail(format!("  {} RPC URL: {}", "ℹ".bold().blue(), rpc_url.yellow()));
+    probe_rpc_url(&rpc_url).await?;
 
     // Get account address
     let account_address = generate_account_address(&rpc_url, caller_pubkey).await?;
+    let pubkey_hex = hex::encode(caller_pubkey.serialize());
+
+    if args.no_fund {
+        save_keypair_to_json(&keys_file, &caller_keypair, &caller_pubkey, &args.name)?;
+
+        if json_output() {
+            return print_json(&json!({
+                "name": args.name,
+                "pubkey": pubkey_hex,
+                "bitcoin_deposit_address": account_address,
+                "fund_amount_sat": fund_amount.to_sat(),
+                "funded": false,
+            }));
+        }
+
+        println!(
+            "  {} Bitcoin deposit address: {}",
+            "→".bold().blue(),
+            account_address.yellow()
+        );
+        println!(
+            "  {} Deposit at least {} satoshis to back the account",
+            "ℹ".bold().blue(),
+            fund_amount.to_sat().to_string().yellow()
+        );
+        println!("  {} Public Key: {}", "ℹ".bold().blue(), pubkey_hex.bright_green());
+        println!(
+            "  {} Key saved as '{}'. The account was not funded or created on chain",
+            "✓".bold().green(),
+            args.name
+        );
+        return Ok(());
+    }
 
     // Set up Bitcoin RPC client
     let wallet_manager = WalletManager::new(config)?;
@@ -3753,19 +8026,40 @@ pub async fn create_account(args: &CreateAccountArgs, config: &Config) -> Result
     println!(
         "  {} Minimum required: {} satoshis",
         "ℹ".bold().blue(),
-        "5000".yellow()
+        fund_amount.to_sat().to_string().yellow()
     );
-    println!("  {} Waiting for funds...", "⏳".bold().blue());
+    say(format!("  {} Waiting for funds...", "⏳".bold().blue()));
+
+    timing_phase("funding and creating the account");
+    match &args.fund_from {
+        Some(funder) => {
+            let funder_keypair = get_keypair_from_name(funder, &keys_file)
+                .with_context(|| format!("Funding account '{}' not found in keys.json", funder))?;
+            let (txid, vout) =
+                fund_address_from_key(&wallet_manager.client, &funder_keypair, &account_address, fund_amount, config)
+                    .await?;
+            send_create_account_instruction(&caller_keypair, &caller_pubkey, &txid, vout, &rpc_url)
+                .await?;
+        }
+        None => {
+            create_arch_account(
+                &caller_keypair,
+                &caller_pubkey,
+                &account_address,
+                fund_amount,
+                &wallet_manager,
+                config,
+                Some(args.rpc_url.clone().unwrap_or_default()),
+            )
+            .await?;
+        }
+    }
 
-    create_arch_account(
-        &caller_keypair,
-        &caller_pubkey,
-        &account_address,
-        &wallet_manager,
-        config,
-        Some(args.rpc_url.clone().unwrap_or_default()),
-    )
-    .await?;
+    // Allocate the data while the system program still owns the account
+    if let Some(space) = args.space.filter(|space| *space > 0) {
+        timing_phase("allocating account data");
+        allocate_account_space(&caller_keypair, &caller_pubkey, space, &rpc_url).await?;
+    }
 
     // Only transfer ownership if program_id is provided
     if let Some(hex_program_id) = &args.program_id {
@@ -3774,9 +8068,10 @@ pub async fn create_account(args: &CreateAccountArgs, config: &Config) -> Result
                 .context("Failed to decode program ID from hex")?;
             let program_id = Pubkey::from_slice(&program_id_bytes);
             
-            let rpc_url = get_rpc_url_with_fallback(args.rpc_url.clone(), config).unwrap();
+            let rpc_url = get_rpc_url_with_fallback(args.rpc_url.clone(), config)?;
             
             // Transfer ownership to the program
+            timing_phase("transferring ownership");
             transfer_account_ownership(
                 &caller_keypair,
                 &caller_pubkey,
@@ -3784,146 +8079,369 @@ pub async fn create_account(args: &CreateAccountArgs, config: &Config) -> Result
                 rpc_url,
             ).await?;
             
-            println!(
-                "{}",
-                "Account created and ownership transferred successfully!"
-                    .bold()
-                    .green()
-            );
+            say("Account created and ownership transferred successfully!".bold().green());
         } else {
-            println!(
-                "{}",
-                "Account created successfully!"
-                    .bold()
-                    .green()
-            );
+            say("Account created successfully!".bold().green());
+        }
+    } else {
+        say("Account created successfully!".bold().green());
+    }
+
+    // Save the account information to keys.json
+    save_keypair_to_json(&keys_file, &caller_keypair, &caller_pubkey, &args.name)?;
+
+    // The private key stays in keys.json in JSON mode, use `account export` to get it
+    if json_output() {
+        wallet_manager.close_wallet()?;
+        return print_json(&json!({
+            "name": args.name,
+            "pubkey": pubkey_hex,
+            "bitcoin_deposit_address": account_address,
+            "funded": true,
+            "owner": args.program_id.as_deref().filter(|id| !id.is_empty()),
+        }));
+    }
+
+    // Output the private key to the user
+    let private_key_hex = hex::encode(secret_key.secret_bytes());
+    println!(
+        "{}",
+        "IMPORTANT: Please save your private key securely. It will not be displayed again."
+            .bold()
+            .red()
+    );
+    println!(
+        "  {} Private Key: {}",
+        "🔑".bold().yellow(),
+        private_key_hex.bright_red()
+    );
+    println!(
+        "  {} Public Key: {}",
+        "🔑".bold().yellow(),
+        pubkey_hex.bright_green()
+    );
+
+    // Close the Bitcoin wallet
+    wallet_manager.close_wallet()?;
+
+    Ok(())
+}
+
+/// Reads an account, treating only the node's not-found error as a missing account so that
+/// RPC and network failures are reported instead
+async fn read_account_if_exists(rpc_url: &str, pubkey: Pubkey) -> Result<Option<AccountInfoResult>> {
+    match read_account_info_async(rpc_url, pubkey).await {
+        Ok(account) => Ok(Some(account)),
+        Err(err) if is_not_found_error(&err) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// The node answers a read of an unknown account with a 404 RPC error
+fn is_not_found_error(err: &anyhow::Error) -> bool {
+    let message = err.to_string().to_lowercase();
+    message.contains("404") || message.contains("not found") || message.contains("not in database")
+}
+
+// Add a new function to list accounts
+pub async fn list_accounts(args: &ListAccountsArgs, config: &Config) -> Result<()> {
+    let keys_dir = get_config_dir()?;
+    let keys_file = keys_dir.join("keys.json");
+
+    let keys = if keys_file.exists() {
+        load_keys(&keys_file)?
+    } else {
+        json!({})
+    };
+    let mut accounts: Vec<(String, String)> = keys
+        .as_object()
+        .ok_or_else(|| anyhow!("keys.json is not a JSON object"))?
+        .iter()
+        .map(|(name, account_info)| {
+            let public_key = account_info["public_key"].as_str().unwrap_or_default().to_string();
+            (name.clone(), public_key)
+        })
+        .collect();
+    // Sort by name so the output is stable regardless of how keys.json was edited
+    accounts.sort_by(|a, b| a.0.cmp(&b.0));
+
+    // Resolve the owner filter before querying the node
+    let owner_filter = args.owner.as_deref().map(resolve_pubkey_hex).transpose()?;
+
+    // Look up every account on the node concurrently
+    let on_chain = if args.on_chain || owner_filter.is_some() {
+        let rpc_url = get_rpc_url_with_fallback(args.rpc_url.clone(), config)?;
+        probe_rpc_url(&rpc_url).await?;
+
+        let lookups: Vec<_> = accounts
+            .iter()
+            .map(|(_, public_key)| {
+                let rpc_url = rpc_url.clone();
+                let pubkey = hex::decode(public_key)
+                    .ok()
+                    .filter(|bytes| bytes.len() == 32)
+                    .map(|bytes| Pubkey::from_slice(&bytes));
+                tokio::spawn(async move {
+                    match pubkey {
+                        Some(pubkey) => read_account_if_exists(&rpc_url, pubkey).await,
+                        None => Ok(None),
+                    }
+                })
+            })
+            .collect();
+
+        // A failed lookup aborts the listing rather than showing the account as missing
+        let mut results = Vec::with_capacity(lookups.len());
+        for ((name, _), lookup) in accounts.iter().zip(lookups) {
+            results.push(lookup.await?.with_context(|| format!("Failed to look up account '{}'", name))?);
+        }
+
+        // Keep only the accounts owned by the requested program
+        if let Some(owner_hex) = &owner_filter {
+            let (kept_accounts, kept_results): (Vec<_>, Vec<_>) = accounts
+                .into_iter()
+                .zip(results)
+                .filter(|(_, info)| {
+                    info.as_ref()
+                        .map_or(false, |info| hex::encode(info.owner.serialize()) == *owner_hex)
+                })
+                .unzip();
+            accounts = kept_accounts;
+            results = kept_results;
         }
+        Some(results)
     } else {
-        println!(
-            "{}",
-            "Account created successfully!"
-                .bold()
-                .green()
-        );
-    }
+        None
+    };
 
-    // Save the account information to keys.json
-    save_keypair_to_json(&keys_file, &caller_keypair, &caller_pubkey, &args.name)?;
+    if json_output() {
+        let entries: Vec<Value> = accounts
+            .iter()
+            .enumerate()
+            .map(|(index, (name, public_key))| {
+                let mut entry = json!({ "name": name, "public_key": public_key });
+                if let Some(results) = &on_chain {
+                    entry["on_chain"] = match &results[index] {
+                        Some(info) => json!({
+                            "exists": true,
+                            "owner": hex::encode(info.owner.serialize()),
+                            "data_len": info.data.len(),
+                            "is_executable": info.is_executable,
+                        }),
+                        None => json!({ "exists": false }),
+                    };
+                }
+                entry
+            })
+            .collect();
+        return print_json(&Value::Array(entries));
+    }
 
-    // Output the private key to the user
-    let private_key_hex = hex::encode(secret_key.secret_bytes());
-    println!(
-        "{}",
-        "IMPORTANT: Please save your private key securely. It will not be displayed again."
-            .bold()
-            .red()
-    );
-    println!(
-        "  {} Private Key: {}",
-        "🔑".bold().yellow(),
-        private_key_hex.bright_red()
-    );
-    println!(
-        "  {} Public Key: {}",
-        "🔑".bold().yellow(),
-        hex::encode(caller_pubkey.serialize()).bright_green()
-    );
+    if accounts.is_empty() {
+        println!("  {} No accounts found", "ℹ".bold().blue());
+        return Ok(());
+    }
 
-    // Close the Bitcoin wallet
-    wallet_manager.close_wallet()?;
+    println!("{}", "Stored accounts:".bold().green());
+    for (index, (name, public_key)) in accounts.iter().enumerate() {
+        println!("  {} Account: {}", "→".bold().blue(), name.yellow());
+        println!("    Public Key: {}", public_key);
+
+        if let Some(results) = &on_chain {
+            match &results[index] {
+                Some(info) => {
+                    println!("    Owner: {}", hex::encode(info.owner.serialize()));
+                    println!("    Data Length: {} bytes", info.data.len());
+                    println!("    Executable: {}", info.is_executable);
+                }
+                None => println!("    {}", "Not found on-chain".yellow()),
+            }
+        }
+    }
 
     Ok(())
 }
 
-fn account_name_exists(accounts_file: &Path, name: &str) -> Result<bool> {
-    if !accounts_file.exists() {
+fn key_name_exists(keys_file: &PathBuf, name: &str) -> Result<bool> {
+    if !keys_file.exists() {
         return Ok(false);
     }
 
-    let file = OpenOptions::new().read(true).open(accounts_file)?;
-    let reader = BufReader::new(file);
-    let accounts: Value = serde_json::from_reader(reader)?;
+    let keys = load_keys(keys_file)?;
 
-    for account_info in accounts.as_object().unwrap().values() {
-        if account_info["name"].as_str().unwrap() == name {
-            return Ok(true);
-        }
+    Ok(keys.as_object().unwrap().contains_key(name))
+}
+
+/// Finds an entry in keys.json by its name or its public key, returning the entry name twice:
+/// as the key to look it up with and as the name to display
+fn find_key_entry(accounts: &serde_json::Map<String, Value>, identifier: &str) -> Option<(String, String)> {
+    if accounts.contains_key(identifier) {
+        return Some((identifier.to_string(), identifier.to_string()));
     }
 
-    Ok(false)
+    accounts.iter().find_map(|(name, account_info)| {
+        account_info["public_key"]
+            .as_str()
+            .filter(|public_key| public_key.eq_ignore_ascii_case(identifier))
+            .map(|_| (name.clone(), name.clone()))
+    })
 }
 
-fn save_account_to_file(
-    file_path: &Path,
-    secret_key: &SecretKey,
-    public_key: &secp256k1::PublicKey,
-    name: &str,
-) -> Result<()> {
-    let mut accounts = if file_path.exists() {
-        let file = OpenOptions::new().read(true).open(file_path)?;
-        let reader = BufReader::new(file);
-        serde_json::from_reader(reader)?
-    } else {
-        json!({})
-    };
+pub async fn rename_account(args: &RenameAccountArgs) -> Result<()> {
+    let keys_file = get_config_dir()?.join("keys.json");
+    if !keys_file.exists() {
+        return Err(anyhow!("No accounts found in {}", keys_file.display()));
+    }
 
-    let account_id = hex::encode(public_key.serialize());
-    let private_key = hex::encode(secret_key.secret_bytes());
+    let mut accounts = load_keys(&keys_file)?;
+    let accounts_obj = accounts
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("Invalid keys file format: {}", keys_file.display()))?;
 
-    accounts[&account_id] = json!({
-        "name": name,
-        "private_key": private_key,
-        "public_key": hex::encode(public_key.serialize()),
-    });
+    let (account_id, old_name) = find_key_entry(accounts_obj, &args.old)
+        .ok_or_else(|| anyhow!("Account '{}' not found", args.old))?;
+    if key_name_exists(&keys_file, &args.new)?
+        || find_key_entry(accounts_obj, &args.new).is_some()
+    {
+        return Err(anyhow!("An account named '{}' already exists", args.new));
+    }
 
-    let file = OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(file_path)?;
-    serde_json::to_writer_pretty(file, &accounts)?;
+    // Only the name changes, never the key material
+    let entry = accounts_obj
+        .remove(&account_id)
+        .expect("entry found above");
+    accounts_obj.insert(args.new.clone(), entry);
 
-    println!(
-        "  {} Account '{}' saved to {}",
+    write_keys(&keys_file, &accounts)?;
+
+    say(format!(
+        "  {} Renamed '{}' to '{}'",
         "✓".bold().green(),
-        name,
-        file_path.display()
-    );
+        old_name,
+        args.new.yellow()
+    ));
     Ok(())
 }
 
-// Add a new function to list accounts
-pub async fn list_accounts() -> Result<()> {
-    let keys_dir = get_config_dir()?;
-    let keys_file = keys_dir.join("keys.json");
-
+pub async fn export_account(args: &ExportAccountArgs) -> Result<()> {
+    let keys_file = get_config_dir()?.join("keys.json");
     if !keys_file.exists() {
-        println!("  {} No accounts found", "ℹ".bold().blue());
-        return Ok(());
+        return Err(anyhow!("No accounts found in {}", keys_file.display()));
     }
 
-    let keys = load_keys(&keys_file)?;
+    let accounts = load_keys(&keys_file)?;
+    let accounts_obj = accounts
+        .as_object()
+        .ok_or_else(|| anyhow!("Invalid keys file format: {}", keys_file.display()))?;
+    let (account_id, account_name) = find_key_entry(accounts_obj, &args.identifier)
+        .ok_or_else(|| anyhow!("Account '{}' not found", args.identifier))?;
 
-    println!("{}", "Stored accounts:".bold().green());
-    for (name, account_info) in keys.as_object().unwrap() {
-        println!("  {} Account: {}", "→".bold().blue(), name.yellow());
-        println!(
-            "    Public Key: {}",
-            account_info["public_key"].as_str().unwrap()
-        );
+    let secret_key = accounts_obj[&account_id]["secret_key"]
+        .as_str()
+        .ok_or_else(|| anyhow!("Account '{}' has no secret key stored", account_name))?;
+
+    // Warnings go to stderr so that stdout holds nothing but the key
+    eprintln!(
+        "  {} The exported key is a PRIVATE KEY. Anyone holding it controls account '{}'. Store it securely.",
+        "⚠".bold().yellow(),
+        account_name
+    );
+
+    let out = match &args.out {
+        Some(out) => out,
+        None => {
+            println!("{}", secret_key);
+            return Ok(());
+        }
+    };
+
+    if out.exists() && !args.force {
+        return Err(anyhow!(
+            "{} already exists. Pass --force to overwrite it",
+            out.display()
+        ));
+    }
+
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
     }
+    let mut file = options
+        .open(out)
+        .with_context(|| format!("Failed to create {}", out.display()))?;
+    writeln!(file, "{}", secret_key)?;
+
+    eprintln!(
+        "  {} Secret key of '{}' written to {}",
+        "✓".bold().green(),
+        account_name,
+        out.display()
+    );
 
     Ok(())
 }
 
-fn key_name_exists(keys_file: &PathBuf, name: &str) -> Result<bool> {
+pub async fn encrypt_keys() -> Result<()> {
+    let keys_file = get_config_dir()?.join("keys.json");
     if !keys_file.exists() {
-        return Ok(false);
+        return Err(anyhow!("No keys file found at {}", keys_file.display()));
+    }
+    if keys_file_is_encrypted(&keys_file) {
+        say(format!("  {} {} is already encrypted", "ℹ".bold().blue(), keys_file.display()));
+        return Ok(());
     }
 
-    let keys = load_keys(keys_file)?;
+    let keys = load_keys(&keys_file)?;
+    let encrypted = keystore::encrypt(&keys)?;
+    fs::write(&keys_file, serde_json::to_string_pretty(&encrypted)?)?;
 
-    Ok(keys.as_object().unwrap().contains_key(name))
+    say(format!("  {} Encrypted {}", "✓".bold().green(), keys_file.display()));
+    say(format!(
+        "  {} Commands that use keys now ask for the passphrase. Set {} to provide it non-interactively",
+        "ℹ".bold().blue(),
+        keystore::PASSPHRASE_ENV
+    ));
+    Ok(())
+}
+
+pub async fn import_account(args: &ImportAccountArgs) -> Result<()> {
+    let keys_file = get_config_dir()?.join("keys.json");
+    if key_name_exists(&keys_file, &args.name)? {
+        return Err(anyhow!("A key named '{}' already exists in {}", args.name, keys_file.display()));
+    }
+
+    // Accept the key inline or from a file, such as one written by account export
+    let secret_key_hex = if Path::new(&args.secret_key).is_file() {
+        fs::read_to_string(&args.secret_key)
+            .with_context(|| format!("Failed to read secret key file {}", args.secret_key))?
+    } else {
+        args.secret_key.clone()
+    };
+    let secret_key_hex = secret_key_hex.trim();
+
+    let key_bytes = hex::decode(secret_key_hex)
+        .map_err(|e| anyhow!("Invalid hex-encoded secret key: {}", e))?;
+    if key_bytes.len() != 32 {
+        return Err(anyhow!(
+            "Invalid secret key length: expected 32 bytes (64 hex characters), got {} bytes",
+            key_bytes.len()
+        ));
+    }
+
+    let (keypair, pubkey) = with_secret_key(secret_key_hex)?;
+    save_keypair_to_json(&keys_file, &keypair, &pubkey, &args.name)?;
+
+    println!(
+        "  {} Key '{}' imported with public key {}",
+        "✓".bold().green(),
+        args.name,
+        hex::encode(pubkey.serialize()).yellow()
+    );
+
+    Ok(())
 }
 
 pub async fn delete_account(args: &DeleteAccountArgs) -> Result<()> {
@@ -3931,60 +8449,32 @@ pub async fn delete_account(args: &DeleteAccountArgs) -> Result<()> {
     let keys_file = keys_dir.join("keys.json");
 
     if !keys_file.exists() {
-        println!("  {} No accounts found", "ℹ".bold().blue());
+        say(format!("  {} No accounts found", "ℹ".bold().blue()));
         return Ok(());
     }
 
-    let file = OpenOptions::new().read(true).open(&keys_file)?;
-    let reader = BufReader::new(file);
-    let mut accounts: Value = serde_json::from_reader(reader)?;
+    let mut accounts = load_keys(&keys_file)?;
 
     let accounts_obj = accounts.as_object_mut().unwrap();
-    let mut account_to_remove = None;
-    let mut account_name = String::new();
-
-    // First try to find by public key (account_id)
-    if accounts_obj.contains_key(&args.identifier) {
-        account_to_remove = Some(args.identifier.clone());
-        account_name = accounts_obj[&args.identifier]["name"]
-            .as_str()
-            .unwrap_or(&args.identifier)
-            .to_string();
-    } else {
-        // If not found, try to find by name
-        for (account_id, account_info) in accounts_obj.iter() {
-            if let Some(name) = account_info["name"].as_str() {
-                if name == args.identifier {
-                    account_to_remove = Some(account_id.clone());
-                    account_name = name.to_string();
-                    break;
-                }
-            }
-        }
-    }
 
-    if let Some(account_id) = account_to_remove {
+    if let Some((account_id, account_name)) = find_key_entry(accounts_obj, &args.identifier) {
         // Use dialoguer for better user interaction
-        let confirm = dialoguer::Confirm::new()
-            .with_prompt(format!(
+        let confirmed = confirm(
+            &format!(
                 "Are you sure you want to delete account '{}' (public key: {})?",
                 account_name, account_id
-            ))
-            .default(false)
-            .interact()?;
+            ),
+            false,
+        )?;
 
-        if confirm {
+        if confirmed {
             accounts_obj.remove(&account_id);
-            let file = OpenOptions::new()
-                .write(true)
-                .truncate(true)
-                .open(&keys_file)?;
-            serde_json::to_writer_pretty(file, &accounts)?;
-            println!(
+            write_keys(&keys_file, &accounts)?;
+            say(format!(
                 "  {} Account '{}' deleted successfully",
                 "✓".bold().green(),
                 account_name
-            );
+            ));
         } else {
             println!(
                 "  {} Deletion of account '{}' cancelled",
@@ -4035,16 +8525,16 @@ async fn generate_account_address(rpc_url: &str, caller_pubkey: Pubkey) -> Resul
 async fn _wait_for_funds(client: &Client, address: &str, config: &Config) -> Result<()> {
     // Check if wallet_manager.client is connected
     let connected = client.get_blockchain_info()?;
-    println!("  {} Connected: {:?}", "ℹ".bold().blue(), connected);
+    detail(format!("  {} Connected: {:?}", "ℹ".bold().blue(), connected));
 
-    let tx_info = fund_address(client, address, config).await?;
+    let tx_info = fund_address(client, address, funding_amount(config)?, config).await?;
 
     if let Some(info) = tx_info {
-        println!(
+        say(format!(
             "  {} Transaction confirmed with {} confirmations",
             "✓".bold().green(),
             info.info.confirmations.to_string().yellow()
-        );
+        ));
     }
 
     Ok(())
@@ -4054,41 +8544,17 @@ async fn create_arch_account(
     caller_keypair: &Keypair,
     caller_pubkey: &Pubkey,
     account_address: &str,
+    amount: Amount,
     wallet_manager: &WalletManager,
     config: &Config,
     rpc_url: Option<String>,
 ) -> Result<()> {
-    let tx_info = fund_address(&wallet_manager.client, account_address, config).await?;
+    let tx_info = fund_address(&wallet_manager.client, account_address, amount, config).await?;
 
     if let Some(info) = tx_info {
-        let caller_keypair = caller_keypair.clone();
-        let caller_pubkey = *caller_pubkey;
-        let rpc_url = get_rpc_url_with_fallback(rpc_url, config).unwrap();
-
-        let (txid, _) = tokio::task::spawn_blocking(move || {
-            sign_and_send_instruction(
-                SystemInstruction::new_create_account_instruction(
-                    hex::decode(&info.info.txid.to_string())
-                        .unwrap()
-                        .try_into()
-                        .unwrap(),
-                    0,
-                    caller_pubkey,
-                ),
-                vec![caller_keypair],
-                rpc_url,
-            )
-            .expect("signing and sending a transaction should not fail")
-        })
-        .await
-        .unwrap();
-
-        println!(
-            "  {} Account created with Arch Network transaction ID: {}",
-            "✓".bold().green(),
-            txid.yellow()
-        );
-        Ok(())
+        let rpc_url = get_rpc_url_with_fallback(rpc_url, config)?;
+        send_create_account_instruction(caller_keypair, caller_pubkey, &info.info.txid.to_string(), 0, &rpc_url)
+            .await
     } else {
         println!(
             "  {} Warning: No transaction info available for deployment",
@@ -4099,6 +8565,235 @@ async fn create_arch_account(
     }
 }
 
+/// Grows a freshly created account to `space` zeroed bytes, one extend-bytes chunk at a time
+async fn allocate_account_space(
+    caller_keypair: &Keypair,
+    caller_pubkey: &Pubkey,
+    space: usize,
+    rpc_url: &str,
+) -> Result<()> {
+    say(format!("  {} Allocating {} bytes of account data...", "→".bold().blue(), space));
+
+    let chunk_len = extend_bytes_max_len();
+    let mut offset = 0;
+    while offset < space {
+        let len = chunk_len.min(space - offset);
+
+        let mut bytes = Vec::with_capacity(8 + len);
+        bytes.extend((offset as u32).to_le_bytes());
+        bytes.extend((len as u32).to_le_bytes());
+        bytes.extend(std::iter::repeat(0u8).take(len));
+
+        let (txid, _) = sign_and_send_instruction_async(
+            SystemInstruction::new_extend_bytes_instruction(bytes, *caller_pubkey),
+            vec![*caller_keypair],
+            rpc_url.to_string(),
+        )
+        .await?;
+        get_processed_transaction_async(rpc_url, txid).await?;
+
+        offset += len;
+    }
+
+    say(format!("  {} Allocated {} bytes of account data", "✓".bold().green(), space));
+    Ok(())
+}
+
+/// Creates the Arch account for `caller_pubkey` backed by the funding output `txid:vout`
+async fn send_create_account_instruction(
+    caller_keypair: &Keypair,
+    caller_pubkey: &Pubkey,
+    funding_txid: &str,
+    vout: u32,
+    rpc_url: &str,
+) -> Result<()> {
+    let txid_bytes: [u8; 32] = hex::decode(funding_txid)?
+        .try_into()
+        .map_err(|_| anyhow!("Funding transaction ID must be 32 bytes"))?;
+
+    let (txid, _) = sign_and_send_instruction_async(
+        SystemInstruction::new_create_account_instruction(txid_bytes, vout, *caller_pubkey),
+        vec![*caller_keypair],
+        rpc_url.to_string(),
+    )
+    .await?;
+
+    say(format!(
+        "  {} Account created with Arch Network transaction ID: {}",
+        "✓".bold().green(),
+        txid.yellow()
+    ));
+    Ok(())
+}
+
+/// Confirmations a coinbase output needs before it can be spent
+const COINBASE_MATURITY: u32 = 100;
+
+/// Virtual size of a key-path taproot funding transaction with `inputs` inputs and two outputs
+fn funding_tx_vsize(inputs: usize) -> u64 {
+    11 + 58 * inputs as u64 + 43 * 2
+}
+
+/// Picks outputs in order until they cover `amount` and the fee at `fee_rate` sat/vB.
+/// Returns the number of outputs used, their total value and the fee, or None when they fall short
+fn select_funding_inputs(values: &[Amount], amount: Amount, fee_rate: u64) -> Option<(usize, Amount, Amount)> {
+    let mut total = Amount::ZERO;
+    for (index, value) in values.iter().enumerate() {
+        total += *value;
+        let fee = Amount::from_sat(fee_rate * funding_tx_vsize(index + 1));
+        if total >= amount + fee {
+            return Some((index + 1, total, fee));
+        }
+    }
+    None
+}
+
+/// Funds `account_address` by spending outputs held by the funder key's own taproot address,
+/// so account creation does not depend on a funded bitcoind wallet. Returns the funding outpoint.
+async fn fund_address_from_key(
+    rpc: &Client,
+    funder: &Keypair,
+    account_address: &str,
+    amount: Amount,
+    config: &Config,
+) -> Result<(String, u32)> {
+    use bitcoin::absolute::LockTime;
+    use bitcoin::key::TapTweak;
+    use bitcoin::sighash::{Prevouts, SighashCache, TapSighashType};
+    use bitcoin::transaction::Version;
+    use bitcoin::{OutPoint, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Witness};
+
+    let network = config
+        .get_string("bitcoin.network")
+        .unwrap_or_else(|_| "regtest".to_string());
+    let bitcoin_network =
+        Network::from_str(&network).context("Invalid Bitcoin network specified in config")?;
+
+    let secp = Secp256k1::new();
+    let funder_address = Address::p2tr(&secp, funder.x_only_public_key().0, None, bitcoin_network);
+    let destination = Address::from_str(account_address)
+        .context("Invalid account address")?
+        .require_network(bitcoin_network)
+        .context("Account address does not match the configured Bitcoin network")?;
+
+    say(format!(
+        "  {} Funding from {}",
+        "→".bold().blue(),
+        funder_address.to_string().yellow()
+    ));
+
+    // Find the funder's confirmed outputs without relying on a wallet
+    let scan = rpc.scan_tx_out_set_blocking(&[bitcoincore_rpc::json::ScanTxOutRequest::Single(
+        format!("addr({})", funder_address),
+    )])?;
+
+    let fee_rate = rpc
+        .estimate_smart_fee(1, None)
+        .ok()
+        .and_then(|estimate| estimate.fee_rate)
+        .map_or(2, |per_kvb| (per_kvb.to_sat() / 1000).max(1));
+
+    // Immature coinbase outputs are rejected by the node, so leave them out
+    let mut spendable = Vec::new();
+    for utxo in &scan.unspents {
+        match rpc.get_tx_out(&utxo.txid, utxo.vout, Some(false))? {
+            Some(out) if out.coinbase && out.confirmations < COINBASE_MATURITY => continue,
+            Some(_) => spendable.push(utxo),
+            None => continue,
+        }
+    }
+
+    let values: Vec<Amount> = spendable.iter().map(|utxo| utxo.amount).collect();
+    let (input_count, total, fee) = select_funding_inputs(&values, amount, fee_rate).ok_or_else(|| {
+        anyhow!(
+            "Funding account holds {} spendable at {}, which does not cover {} plus fees. Send funds to that address first",
+            values.iter().copied().sum::<Amount>(),
+            funder_address,
+            amount
+        )
+    })?;
+    let inputs = &spendable[..input_count];
+
+    let mut outputs = vec![TxOut {
+        value: amount,
+        script_pubkey: destination.script_pubkey(),
+    }];
+    let change = total - amount - fee;
+    if change > Amount::from_sat(330) {
+        outputs.push(TxOut {
+            value: change,
+            script_pubkey: funder_address.script_pubkey(),
+        });
+    }
+
+    let mut tx = Transaction {
+        version: Version::TWO,
+        lock_time: LockTime::ZERO,
+        input: inputs
+            .iter()
+            .map(|utxo| TxIn {
+                previous_output: OutPoint { txid: utxo.txid, vout: utxo.vout },
+                script_sig: ScriptBuf::new(),
+                sequence: Sequence::MAX,
+                witness: Witness::new(),
+            })
+            .collect(),
+        output: outputs,
+    };
+
+    // Key-path spend of every input with the funder's tweaked key
+    let prevouts: Vec<TxOut> = inputs
+        .iter()
+        .map(|utxo| TxOut {
+            value: utxo.amount,
+            script_pubkey: utxo.script_pub_key.clone(),
+        })
+        .collect();
+    let tweaked = funder.tap_tweak(&secp, None).to_inner();
+    let mut sighasher = SighashCache::new(&mut tx);
+    for index in 0..inputs.len() {
+        let sighash = sighasher.taproot_key_spend_signature_hash(
+            index,
+            &Prevouts::All(&prevouts),
+            TapSighashType::Default,
+        )?;
+        let signature = secp.sign_schnorr(&secp256k1::Message::from(sighash), &tweaked);
+        let signature = bitcoin::taproot::Signature {
+            signature,
+            sighash_type: TapSighashType::Default,
+        };
+        *sighasher.witness_mut(index).ok_or_else(|| anyhow!("Missing input {}", index))? =
+            Witness::p2tr_key_spend(&signature);
+    }
+
+    let txid = rpc.send_raw_transaction(&tx)?;
+    say(format!("  {} Transaction sent: {}", "✓".bold().green(), txid.to_string().yellow()));
+
+    // Mine to a wallet address: a coinbase paid to the funder could be selected before it matures
+    if bitcoin_network == Network::Regtest {
+        let mining_address = rpc.get_new_address(None, None)?.require_network(bitcoin_network)?;
+        rpc.generate_to_address(1, &mining_address)?;
+    }
+
+    // Wait until the funding output is confirmed
+    let start_time = std::time::Instant::now();
+    let timeout = settings::funding_timeout();
+    loop {
+        if let Some(out) = rpc.get_tx_out(&txid, 0, Some(false))? {
+            if out.confirmations > 0 {
+                break;
+            }
+        }
+        if start_time.elapsed() > timeout {
+            return Err(anyhow!("Funding transaction {} was not confirmed in time", txid));
+        }
+        tokio::time::sleep(Duration::from_secs(5)).await;
+    }
+    say(format!("  {} Funding transaction confirmed", "✓".bold().green()));
+
+    Ok((txid.to_string(), 0))
+}
+
 async fn transfer_account_ownership(
     caller_keypair: &Keypair,
     account_pubkey: &Pubkey,
@@ -4108,54 +8803,72 @@ async fn transfer_account_ownership(
     let mut instruction_data = vec![3]; // Transfer instruction
     instruction_data.extend(program_pubkey.serialize());
 
-    println!(
+    detail(format!(
         "  {} Account public key: {:?}",
         "ℹ".bold().blue(),
         hex::encode(account_pubkey.serialize())
-    );
+    ));
 
-    let instruction_data_clone = instruction_data.clone();
-    let account_pubkey_clone = *account_pubkey;
-    let caller_keypair_clone = caller_keypair.clone();
-
-    let (_txid, _) = tokio::task::spawn_blocking(move || {
-        sign_and_send_instruction(
-            Instruction {
-                program_id: Pubkey::system_program(),
-                accounts: vec![AccountMeta {
-                    pubkey: account_pubkey_clone,
-                    is_signer: true,
-                    is_writable: true,
-                }],
-                data: instruction_data_clone,
-            },
-            vec![caller_keypair_clone],
-            rpc_url,
-        )
-        .expect("signing and sending a transaction should not fail")
-    })
-    .await
-    .unwrap();
+    sign_and_send_instruction_async(
+        Instruction {
+            program_id: Pubkey::system_program(),
+            accounts: vec![AccountMeta {
+                pubkey: *account_pubkey,
+                is_signer: true,
+                is_writable: true,
+            }],
+            data: instruction_data,
+        },
+        vec![*caller_keypair],
+        rpc_url,
+    )
+    .await?;
 
     Ok(())
 }
 
 pub async fn indexer_start(args: &IndexerStartArgs, config: &Config) -> Result<()> {
     match args.target.as_str() {
-        "local" => start_local_indexer(config).await,
+        "local" => start_local_indexer(args, config).await,
         "gcp" => start_gcp_indexer(args, config).await,
         _ => Err(anyhow!("Invalid deployment target. Use 'local' or 'gcp'"))
     }
 }
 
-pub async fn start_local_indexer(config: &Config) -> Result<()> {
-    println!("{}", "Starting the arch-indexer...".bold().green());
+/// Arch node URL the local indexer uses when neither --rpc-url nor indexer.arch_node_url is set
+const DEFAULT_INDEXER_NODE_URL: &str = "http://host.docker.internal:9002";
+
+/// Resolves the Arch node URL passed to the local indexer as ARCH_NODE_URL. `host.docker.internal`
+/// does not resolve in containers on Linux by default, so there the default URL points at the
+/// Docker bridge gateway instead, which is the host as seen from the containers.
+fn local_indexer_node_url(args: &IndexerStartArgs, config: &Config) -> String {
+    if let Some(url) = args.rpc_url.clone().or_else(|| config.get_string("indexer.arch_node_url").ok()) {
+        return url;
+    }
+
+    if cfg!(target_os = "linux") {
+        let gateway = Command::new("docker")
+            .args(["network", "inspect", "bridge", "--format", "{{(index .IPAM.Config 0).Gateway}}"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .filter(|gateway| !gateway.is_empty())
+            .unwrap_or_else(|| "172.17.0.1".to_string());
+        return DEFAULT_INDEXER_NODE_URL.replace("host.docker.internal", &gateway);
+    }
+
+    DEFAULT_INDEXER_NODE_URL.to_string()
+}
+
+pub async fn start_local_indexer(args: &IndexerStartArgs, config: &Config) -> Result<()> {
+    say("Starting the arch-indexer...".bold().green());
 
-    let arch_node_url = "http://host.docker.internal:9002";
+    let arch_node_url = local_indexer_node_url(args, config);
+    detail(format!("  {} Arch node URL: {}", "ℹ".bold().blue(), arch_node_url.yellow()));
 
     // Get the selected network from the config
-    let selected_network = config.get_string("selected_network")
-        .unwrap_or_else(|_| "development".to_string());
+    let selected_network = selected_network(config);
 
     // Set environment variables for the selected network
     set_env_vars(config, &selected_network)?;
@@ -4164,30 +8877,22 @@ pub async fn start_local_indexer(config: &Config) -> Result<()> {
     let indexer_dir = get_indexer_dir()?;
 
     // Clone or update the arch-indexer repository
-    clone_or_update_repo(&indexer_dir)?;
-
-    // Remember the current directory
-    let original_dir = env::current_dir()?;
-
-    // Change to the indexer directory
-    env::set_current_dir(&indexer_dir)
-        .context("Failed to change to indexer directory")?;
+    let indexer_ref = indexer_ref(args, config);
+    clone_or_update_repo(&indexer_dir, indexer_ref.as_deref())?;
 
     // Start the indexer using docker-compose
     let output = ShellCommand::new("docker-compose")
+        .args(settings::compose_project_args("indexer"))
+        .current_dir(&indexer_dir)
         .arg("-f")
         .arg("docker-compose.yml")
         .arg("up")
         .arg("--build")
         .arg("-d")
-        .env("ARCH_NODE_URL", arch_node_url)
+        .env("ARCH_NODE_URL", &arch_node_url)
         .output()
         .context("Failed to start the arch-indexer using Docker Compose")?;
 
-    // Change back to the original directory
-    env::set_current_dir(original_dir)
-        .context("Failed to change back to original directory")?;
-
     if !output.status.success() {
         return Err(anyhow!(
             "Failed to start the arch-indexer: {}",
@@ -4195,12 +8900,12 @@ pub async fn start_local_indexer(config: &Config) -> Result<()> {
         ));
     }
 
-    println!("{}", "arch-indexer started successfully!".bold().green());
+    say("arch-indexer started successfully!".bold().green());
     Ok(())
 }
 
-async fn prepare_indexer_files(temp_dir: &Path) -> Result<()> {
-    println!("  {} Preparing indexer files...", "→".bold().blue());
+async fn prepare_indexer_files(temp_dir: &Path, indexer_ref: Option<&str>) -> Result<()> {
+    say(format!("  {} Preparing indexer files...", "→".bold().blue()));
 
     // Clone the repository
     let clone_status = Command::new("git")
@@ -4215,6 +8920,9 @@ async fn prepare_indexer_files(temp_dir: &Path) -> Result<()> {
     if !clone_status.success() {
         return Err(anyhow!("Failed to clone indexer repository"));
     }
+    if let Some(indexer_ref) = indexer_ref {
+        checkout_indexer_ref(temp_dir, indexer_ref)?;
+    }
 
     // Create docker-compose.yml for GCP
     let docker_compose = r#"version: '3'
@@ -4285,7 +8993,7 @@ async fn stop_gcp_indexer(args: &IndexerStartArgs) -> Result<()> {
     let zone = &"us-central1".to_string();
     let region = args.gcp_region.as_ref().unwrap_or(zone);
 
-    println!("{}", "Stopping GCP indexer...".bold().green());
+    say("Stopping GCP indexer...".bold().green());
 
     // Stop and delete the indexer instance
     let _ = ShellCommand::new("gcloud")
@@ -4316,7 +9024,7 @@ async fn stop_gcp_indexer(args: &IndexerStartArgs) -> Result<()> {
         ])
         .output()?;
 
-    println!("{}", "GCP indexer stopped successfully!".bold().green());
+    say("GCP indexer stopped successfully!".bold().green());
     Ok(())
 }
 
@@ -4327,8 +9035,9 @@ pub async fn start_gcp_indexer(args: &IndexerStartArgs, config: &Config) -> Resu
     let machine = &"e2-medium".to_string();
     let region = args.gcp_region.as_ref().unwrap_or(zone);
     let machine_type = args.gcp_machine_type.as_ref().unwrap_or(machine);
+    let registry = gcp_registry(args.registry.as_deref(), config, project_id, region);
 
-    println!("Starting indexer deployment to GCP...");
+    say("Starting indexer deployment to GCP...");
 
     // Setup Cloud SQL
     let (sql_connection_name, db_password) = setup_cloud_sql(project_id, region).await?;
@@ -4336,19 +9045,19 @@ pub async fn start_gcp_indexer(args: &IndexerStartArgs, config: &Config) -> Resu
     // Initialize schema
     initialize_cloud_sql_schema(project_id, "arch-indexer-db").await?;
 
-    let temp_dir = tempfile::tempdir()?;
-    prepare_indexer_files(temp_dir.path()).await?;
+    let temp_dir = settings::scratch_dir("the indexer build")?;
+    prepare_indexer_files(temp_dir.path(), indexer_ref(args, config).as_deref()).await?;
 
     // Build and push using Cloud Build
     let cloudbuild_content = format!(r#"steps:
 - name: 'gcr.io/cloud-builders/docker'
-  args: ['build', '-t', 'gcr.io/{}/arch-indexer:latest', '.']
-images: ['gcr.io/{}/arch-indexer:latest']
-"#, project_id, project_id);
+  args: ['build', '-t', '{registry}/arch-indexer:latest', '.']
+images: ['{registry}/arch-indexer:latest']
+"#, registry = registry);
 
     fs::write(temp_dir.path().join("cloudbuild.yaml"), cloudbuild_content)?;
 
-    println!("  {} Building and pushing indexer image...", "→".bold().blue());
+    say(format!("  {} Building and pushing indexer image...", "→".bold().blue()));
     let build_output = ShellCommand::new("gcloud")
         .args([
             "builds", "submit",
@@ -4367,7 +9076,7 @@ images: ['gcr.io/{}/arch-indexer:latest']
     }
 
     // Deploy the indexer container
-    println!("  {} Deploying indexer to GCP...", "→".bold().blue());
+    say(format!("  {} Deploying indexer to GCP...", "→".bold().blue()));
     let rpc_url = args.rpc_url.as_deref().unwrap_or("http://localhost:9001");
     let create_instance_output = ShellCommand::new("gcloud")
         .args([
@@ -4375,7 +9084,7 @@ images: ['gcr.io/{}/arch-indexer:latest']
             "--project", project_id,
             "--zone", &format!("{}-a", region),
             "--machine-type", machine_type,
-            "--container-image", &format!("gcr.io/{}/arch-indexer:latest", project_id),
+            "--container-image", &format!("{}/arch-indexer:latest", registry),
             "--tags", "indexer",
             "--container-env", &format!("ARCH_NODE_URL={}", rpc_url),
             "--container-env", &format!("DB_HOST=/cloudsql/{}", sql_connection_name),
@@ -4399,19 +9108,19 @@ images: ['gcr.io/{}/arch-indexer:latest']
         .output()?
         .stdout).trim().to_string();
 
-    setup_indexer_ssl_proxy(project_id, region, &indexer_ip).await?;
+    setup_indexer_ssl_proxy(project_id, region, &registry, &indexer_ip).await?;
 
     Ok(())
 }
 
 async fn setup_cloud_sql(project_id: &str, region: &str) -> Result<(String, String)> {
-    println!("  {} Setting up Cloud SQL instance...", "→".bold().blue());
+    say(format!("  {} Setting up Cloud SQL instance...", "→".bold().blue()));
 
     let instance_name = "arch-indexer-db";
     let db_password = generate_random_password();
 
     // Check if instance exists
-    println!("  {} Checking if Cloud SQL instance exists...", "→".bold().blue());
+    say(format!("  {} Checking if Cloud SQL instance exists...", "→".bold().blue()));
     let instance_exists = ShellCommand::new("gcloud")
         .args([
             "sql", "instances", "describe", instance_name,
@@ -4422,7 +9131,7 @@ async fn setup_cloud_sql(project_id: &str, region: &str) -> Result<(String, Stri
         .unwrap_or(false);
 
     if !instance_exists {
-        println!("  {} Creating new Cloud SQL instance...", "→".bold().blue());
+        say(format!("  {} Creating new Cloud SQL instance...", "→".bold().blue()));
         let create_output = ShellCommand::new("gcloud")
             .args([
                 "sql", "instances", "create", instance_name,
@@ -4444,7 +9153,7 @@ async fn setup_cloud_sql(project_id: &str, region: &str) -> Result<(String, Stri
         }
 
         // Create database
-        println!("  {} Creating database...", "→".bold().blue());
+        say(format!("  {} Creating database...", "→".bold().blue()));
         let db_output = ShellCommand::new("gcloud")
             .args([
                 "sql", "databases", "create", "archindexer",
@@ -4462,7 +9171,7 @@ async fn setup_cloud_sql(project_id: &str, region: &str) -> Result<(String, Stri
         }
 
         // Create user
-        println!("  {} Creating database user...", "→".bold().blue());
+        say(format!("  {} Creating database user...", "→".bold().blue()));
         let user_output = ShellCommand::new("gcloud")
             .args([
                 "sql", "users", "create", "postgres",
@@ -4480,11 +9189,11 @@ async fn setup_cloud_sql(project_id: &str, region: &str) -> Result<(String, Stri
             ));
         }
     } else {
-        println!("  {} Using existing Cloud SQL instance", "✓".bold().green());
+        say(format!("  {} Using existing Cloud SQL instance", "✓".bold().green()));
     }
 
     // Get connection name
-    println!("  {} Getting connection details...", "→".bold().blue());
+    say(format!("  {} Getting connection details...", "→".bold().blue()));
     let conn_output = ShellCommand::new("gcloud")
         .args([
             "sql", "instances", "describe", instance_name,
@@ -4503,12 +9212,12 @@ async fn setup_cloud_sql(project_id: &str, region: &str) -> Result<(String, Stri
 
     let connection_name = String::from_utf8_lossy(&conn_output.stdout).trim().to_string();
 
-    println!("  {} Cloud SQL setup complete", "✓".bold().green());
+    say(format!("  {} Cloud SQL setup complete", "✓".bold().green()));
     Ok((connection_name, db_password))
 }
 
 async fn initialize_cloud_sql_schema(projectid: &str, instance_name: &str) -> Result<()> {
-    println!("  {} Initializing database schema...", "→".bold().blue());
+    say(format!("  {} Initializing database schema...", "→".bold().blue()));
 
     let temp_file = tempfile::NamedTempFile::new()?;
 
@@ -4552,10 +9261,10 @@ CREATE INDEX IF NOT EXISTS idx_blocks_bitcoin_block_height ON blocks(bitcoin_blo
     Ok(())
 }
 
-async fn setup_indexer_ssl_proxy(project_id: &str, region: &str, indexer_ip: &str) -> Result<()> {
-    println!("  {} Setting up HTTPS proxy for indexer...", "→".bold().blue());
+async fn setup_indexer_ssl_proxy(project_id: &str, region: &str, registry: &str, indexer_ip: &str) -> Result<()> {
+    say(format!("  {} Setting up HTTPS proxy for indexer...", "→".bold().blue()));
 
-    let temp_dir = tempfile::tempdir()?;
+    let temp_dir = settings::scratch_dir("the indexer SSL proxy")?;
 
     // Create nginx.conf for indexer
     let nginx_conf = format!(r#"
@@ -4640,9 +9349,9 @@ EXPOSE 443
     fs::write(temp_dir.path().join("Dockerfile"), dockerfile_content)?;
 
     // Build and push the proxy image
-    let proxy_image = format!("gcr.io/{}/arch-indexer-proxy:latest", project_id);
+    let proxy_image = format!("{}/arch-indexer-proxy:latest", registry);
 
-    println!("  {} Building and pushing proxy image...", "→".bold().blue());
+    say(format!("  {} Building and pushing proxy image...", "→".bold().blue()));
     let build_status = Command::new("docker")
         .args([
             "build",
@@ -4666,7 +9375,7 @@ EXPOSE 443
     }
 
     // Create firewall rules
-    println!("  {} Creating firewall rules...", "→".bold().blue());
+    say(format!("  {} Creating firewall rules...", "→".bold().blue()));
     let _ = ShellCommand::new("gcloud")
         .args([
             "compute", "firewall-rules", "create", "allow-indexer-internal",
@@ -4689,7 +9398,7 @@ EXPOSE 443
         .output();
 
     // Deploy the proxy container
-    println!("  {} Deploying HTTPS proxy...", "→".bold().blue());
+    say(format!("  {} Deploying HTTPS proxy...", "→".bold().blue()));
     let create_proxy_output = ShellCommand::new("gcloud")
         .args([
             "compute", "instances", "create-with-container", "arch-indexer-proxy",
@@ -4720,7 +9429,7 @@ EXPOSE 443
         .output()?
         .stdout).trim().to_string();
 
-    println!("\n{}", "HTTPS proxy setup complete!".bold().green());
+    say(format!("\n{}", "HTTPS proxy setup complete!".bold().green()));
     println!("Proxy IP: {}", proxy_ip);
     println!("HTTPS endpoint: {}", format!("https://{}", proxy_ip).yellow());
 
@@ -4745,22 +9454,57 @@ fn get_indexer_dir() -> Result<PathBuf> {
     Ok(indexer_dir)
 }
 
-fn clone_or_update_repo(indexer_dir: &Path) -> Result<()> {
-    if indexer_dir.join(".git").exists() {
-        // Repository already exists, update it
-        println!("  {} Updating arch-indexer repository...", "→".bold().blue());
-        let status = ShellCommand::new("git")
-            .current_dir(indexer_dir)
-            .args(&["pull", "origin", "main"])
-            .status()
-            .context("Failed to update arch-indexer repository")?;
+/// arch-indexer ref to check out, from --indexer-ref or indexer.ref in the config
+fn indexer_ref(args: &IndexerStartArgs, config: &Config) -> Option<String> {
+    args.indexer_ref
+        .clone()
+        .or_else(|| config.get_string("indexer.ref").ok())
+        .filter(|indexer_ref| !indexer_ref.is_empty())
+}
 
-        if !status.success() {
-            return Err(anyhow!("Failed to update arch-indexer repository"));
-        }
-    } else {
+fn run_git(dir: &Path, git_args: &[&str], action: &str) -> Result<()> {
+    let status = ShellCommand::new("git")
+        .current_dir(dir)
+        .args(git_args)
+        .status()
+        .with_context(|| format!("Failed to {}", action))?;
+
+    if !status.success() {
+        return Err(anyhow!("Failed to {}", action));
+    }
+    Ok(())
+}
+
+/// Fetches the arch-indexer repository in `dir` and checks out `indexer_ref` (a tag, branch or
+/// commit) as a detached HEAD. Branches resolve to their latest fetched commit.
+fn checkout_indexer_ref(dir: &Path, indexer_ref: &str) -> Result<()> {
+    say(format!("  {} Checking out arch-indexer {}...", "→".bold().blue(), indexer_ref.yellow()));
+    run_git(dir, &["fetch", "--tags", "origin"], "fetch the arch-indexer repository")?;
+
+    let resolve = |candidate: &str| {
+        ShellCommand::new("git")
+            .current_dir(dir)
+            .args(["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", candidate)])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    let commit = resolve(&format!("origin/{}", indexer_ref))
+        .or_else(|| resolve(indexer_ref))
+        .ok_or_else(|| anyhow!("arch-indexer has no tag, branch or commit named '{}'", indexer_ref))?;
+
+    run_git(
+        dir,
+        &["checkout", "--quiet", "--detach", &commit],
+        &format!("check out arch-indexer {}", indexer_ref),
+    )
+}
+
+fn clone_or_update_repo(indexer_dir: &Path, indexer_ref: Option<&str>) -> Result<()> {
+    if !indexer_dir.join(".git").exists() {
         // Clone the repository
-        println!("  {} Cloning arch-indexer repository...", "→".bold().blue());
+        say(format!("  {} Cloning arch-indexer repository...", "→".bold().blue()));
         let status = ShellCommand::new("git")
             .args(&[
                 "clone",
@@ -4773,9 +9517,18 @@ fn clone_or_update_repo(indexer_dir: &Path) -> Result<()> {
         if !status.success() {
             return Err(anyhow!("Failed to clone arch-indexer repository"));
         }
+    } else if indexer_ref.is_none() {
+        // Repository already exists, update it. A previous pinned run may have left HEAD detached
+        say(format!("  {} Updating arch-indexer repository...", "→".bold().blue()));
+        run_git(indexer_dir, &["checkout", "--quiet", "main"], "check out arch-indexer main")?;
+        run_git(indexer_dir, &["pull", "origin", "main"], "update arch-indexer repository")?;
+    }
+
+    if let Some(indexer_ref) = indexer_ref {
+        checkout_indexer_ref(indexer_dir, indexer_ref)?;
     }
 
-    println!("  {} arch-indexer repository ready", "✓".bold().green());
+    say(format!("  {} arch-indexer repository ready", "✓".bold().green()));
     Ok(())
 }
 
@@ -4788,25 +9541,19 @@ pub async fn indexer_stop(args: &IndexerStartArgs, config: &Config) -> Result<()
 }
 
 pub async fn stop_local_indexer(config: &Config) -> Result<()> {
-    println!("{}", "Stopping the arch-indexer...".bold().green());
+    say("Stopping the arch-indexer...".bold().green());
 
     // Get the selected network from the config
-    let selected_network = config.get_string("selected_network")
-        .unwrap_or_else(|_| "development".to_string());
+    let selected_network = selected_network(config);
 
     // Set environment variables for the selected network
     set_env_vars(config, &selected_network)?;
 
     let indexer_dir = get_indexer_dir()?;
 
-    // Remember the current directory
-    let original_dir = env::current_dir()?;
-
-    // Change to the indexer directory
-    env::set_current_dir(&indexer_dir)
-        .context("Failed to change to indexer directory")?;
-
     let output = ShellCommand::new("docker-compose")
+        .args(settings::compose_project_args("indexer"))
+        .current_dir(&indexer_dir)
         .arg("-f")
         .arg("docker-compose.yml")
         .arg("down")
@@ -4820,18 +9567,173 @@ pub async fn stop_local_indexer(config: &Config) -> Result<()> {
         ));
     }
 
-    println!("{}", "arch-indexer stopped successfully!".bold().green());
+    say("arch-indexer stopped successfully!".bold().green());
+
+    Ok(())
+}
+
+/// Names of the containers of the local indexer stack, keyed by compose service (`indexer`, `db`)
+fn local_indexer_containers() -> Result<HashMap<String, String>> {
+    let indexer_dir = get_indexer_dir()?;
+    let output = Command::new("docker")
+        .args([
+            "ps",
+            "-a",
+            "--filter",
+            &format!("label=com.docker.compose.project.working_dir={}", indexer_dir.display()),
+            "--format",
+            "{{.Label \"com.docker.compose.service\"}}\t{{.Names}}",
+        ])
+        .output()
+        .context("Failed to list the indexer containers")?;
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(service, name)| (service.to_string(), name.to_string()))
+        .collect())
+}
+
+pub async fn indexer_status(args: &IndexerStatusArgs) -> Result<()> {
+    match args.target.as_str() {
+        "local" => local_indexer_status(),
+        "gcp" => {
+            let project_id = args.gcp_project.as_ref()
+                .ok_or_else(|| anyhow!("GCP project ID is required for the gcp target"))?;
+            let region = args.gcp_region.as_deref().unwrap_or("us-central1");
+            gcp_indexer_status(project_id, region)
+        }
+        _ => Err(anyhow!("Invalid target. Use 'local' or 'gcp'")),
+    }
+}
+
+fn local_indexer_status() -> Result<()> {
+    let containers = local_indexer_containers()?;
+
+    let mut services = Vec::new();
+    for service in ["indexer", "db"] {
+        let container = containers.get(service);
+        let state = match container {
+            None => "not created",
+            Some(name) if check_docker_status(name)? => "running",
+            Some(_) => "stopped",
+        };
+        services.push((service, container, state));
+    }
+
+    if json_output() {
+        let services: Vec<Value> = services
+            .iter()
+            .map(|(service, container, state)| {
+                json!({ "service": service, "container": container, "state": state })
+            })
+            .collect();
+        return print_json(&json!({
+            "target": "local",
+            "running": services.iter().all(|service| service["state"] == "running"),
+            "services": services,
+        }));
+    }
+
+    println!("{}", "Checking indexer status...".bold().blue());
+    for (service, container, state) in &services {
+        let label = if *service == "db" { "Postgres" } else { "Indexer" };
+        match (container, *state) {
+            (Some(name), "running") => println!("  {} {} container '{}' is running", "✓".bold().green(), label, name),
+            (Some(name), _) => println!("  {} {} container '{}' exists but is stopped", "⚠".bold().yellow(), label, name),
+            (None, _) => println!("  {} {} container not created", "✗".bold().red(), label),
+        }
+    }
+
+    if services.iter().any(|(_, _, state)| *state != "running") {
+        println!("  {} Start it with 'arch-cli indexer start'", "→".bold().blue());
+    }
+    Ok(())
+}
+
+fn gcp_indexer_status(project_id: &str, region: &str) -> Result<()> {
+    let zone = format!("{}-a", region);
+    let describe = |args: &[&str]| -> Result<Option<String>> {
+        let output = ShellCommand::new("gcloud")
+            .args(args)
+            .args(["--project", project_id])
+            .output()
+            .context("Failed to describe the GCP indexer")?;
+        Ok(output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string()))
+    };
+
+    let status = describe(&["compute", "instances", "describe", "arch-indexer", "--zone", &zone, "--format", "get(status)"])?;
+    let db_state = describe(&["sql", "instances", "describe", "arch-indexer-db", "--format", "get(state)"])?;
+    let proxy_ip = describe(&[
+        "compute", "instances", "describe", "arch-indexer-proxy",
+        "--zone", &zone,
+        "--format", "get(networkInterfaces[0].accessConfigs[0].natIP)",
+    ])?
+    .filter(|ip| !ip.is_empty());
+    let https_endpoint = proxy_ip.as_ref().map(|ip| format!("https://{}", ip));
+
+    if json_output() {
+        return print_json(&json!({
+            "target": "gcp",
+            "instance": "arch-indexer",
+            "status": status.as_deref().unwrap_or("not deployed"),
+            "database": db_state.as_deref().unwrap_or("not deployed"),
+            "https_endpoint": https_endpoint,
+        }));
+    }
+
+    println!("{}", "Checking GCP indexer status...".bold().blue());
+    let Some(status) = status else {
+        println!("  {} Indexer not deployed: no 'arch-indexer' instance in {}", "✗".bold().red(), zone);
+        return Ok(());
+    };
+
+    let glyph = if status == "RUNNING" { "✓".bold().green() } else { "⚠".bold().yellow() };
+    println!("  {} Instance 'arch-indexer': {}", glyph, status.yellow());
+    match db_state {
+        Some(state) => {
+            let glyph = if state == "RUNNABLE" { "✓".bold().green() } else { "⚠".bold().yellow() };
+            println!("  {} Cloud SQL 'arch-indexer-db': {}", glyph, state.yellow());
+        }
+        None => println!("  {} No 'arch-indexer-db' Cloud SQL instance found", "✗".bold().red()),
+    }
+    match https_endpoint {
+        Some(https_endpoint) => println!("  {} HTTPS endpoint: {}", "ℹ".bold().blue(), https_endpoint.yellow()),
+        None => println!("  {} No SSL proxy instance found", "ℹ".bold().blue()),
+    }
+    Ok(())
+}
+
+pub async fn indexer_logs(args: &IndexerLogsArgs) -> Result<()> {
+    let container_name = local_indexer_containers()?
+        .remove("indexer")
+        .ok_or_else(|| anyhow!("No indexer container found. Start it with 'arch-cli indexer start'"))?;
 
-    // Change back to the original directory
-    env::set_current_dir(original_dir)
-        .context("Failed to change back to original directory")?;
+    let mut command = ShellCommand::new("docker");
+    command.arg("logs");
+    if args.follow {
+        command.arg("--follow");
+    }
+    if let Some(tail) = args.tail {
+        command.args(["--tail", &tail.to_string()]);
+    }
+    let status = command
+        .arg(&container_name)
+        .status()
+        .context("Failed to run docker logs")?;
 
+    if !status.success() {
+        return Err(anyhow!("docker logs exited with {}", status));
+    }
     Ok(())
 }
 
 // Remove the docker containers and associated volumes
 pub async fn indexer_clean(config: &Config) -> Result<()> {
-    println!("{}", "Cleaning up the arch-indexer...".bold().yellow());
+    say("Cleaning up the arch-indexer...".bold().yellow());
 
     // Confirmation prompt
     let proceed = Confirm::with_theme(&ColorfulTheme::default())
@@ -4840,19 +9742,19 @@ pub async fn indexer_clean(config: &Config) -> Result<()> {
         .interact()?;
 
     if !proceed {
-        println!("  {} Operation cancelled.", "ℹ".bold().blue());
+        say(format!("  {} Operation cancelled.", "ℹ".bold().blue()));
         return Ok(());
     }
 
     // Get the selected network from the config
-    let selected_network = config.get_string("selected_network")
-        .unwrap_or_else(|_| "development".to_string());
+    let selected_network = selected_network(config);
 
     // Set environment variables for the selected network
     set_env_vars(config, &selected_network)?;
 
     // Stop and remove containers
     let output = Command::new("docker-compose")
+        .args(settings::compose_project_args("indexer"))
         .arg("-f")
         .arg("arch-indexer/docker-compose.yml")
         .arg("down")
@@ -4880,22 +9782,76 @@ pub async fn indexer_clean(config: &Config) -> Result<()> {
         );
     }
 
-    println!("{}", "Arch-indexer cleaned up successfully!".bold().green());
+    say("Arch-indexer cleaned up successfully!".bold().green());
     Ok(())
 }
 
 pub async fn validator_start(args: &ValidatorStartArgs, config: &Config) -> Result<()> {
+    if let Some(rpc_url) = &args.attach {
+        return attach_validator(rpc_url).await;
+    }
+
     match args.target.as_str() {
-        "local" => start_local_validator(&args, config).await,
+        "local" => start_local_validator(config).await,
         "gcp" => start_gcp_validator(&args, config).await,
         _ => Err(anyhow!("Invalid deployment target. Use 'local' or 'gcp'"))
     }
 }
 
-async fn start_local_validator(args: &ValidatorStartArgs, config: &Config) -> Result<()> {
-    println!("{}", "Starting the local validator...".bold().green());
+/// Records the RPC endpoint of a validator managed outside arch-cli so that
+/// every later command targets it instead of the local container
+async fn attach_validator(rpc_url: &str) -> Result<()> {
+    say("Attaching to a running validator...".bold().green());
+
+    probe_rpc_url(rpc_url).await?;
+    say(format!("  {} Validator at {} is reachable", "✓".bold().green(), rpc_url.yellow()));
+
+    set_attached_validator(Some(rpc_url))?;
+    say(format!(
+        "  {} Commands now use {}. Run 'arch-cli validator stop' to detach",
+        "ℹ".bold().blue(),
+        rpc_url.yellow()
+    ));
+    Ok(())
+}
+
+/// Returns the RPC endpoint recorded by `validator start --attach`, if any
+fn attached_validator() -> Option<String> {
+    get_config_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| content.parse::<Document>().ok())
+        .and_then(|doc| doc.get("validator")?.get("attach_rpc_endpoint")?.as_str().map(str::to_string))
+        .filter(|endpoint| !endpoint.trim().is_empty())
+}
+
+fn set_attached_validator(rpc_url: Option<&str>) -> Result<()> {
+    let config_path = get_config_path()?;
+    let mut doc = fs::read_to_string(&config_path)
+        .with_context(|| format!("Failed to read {:?}", config_path))?
+        .parse::<Document>()?;
+
+    match rpc_url {
+        Some(rpc_url) => {
+            if doc.get("validator").is_none() {
+                doc["validator"] = toml_edit::table();
+            }
+            doc["validator"]["attach_rpc_endpoint"] = value(rpc_url);
+        }
+        None => {
+            if let Some(validator) = doc.get_mut("validator").and_then(|item| item.as_table_mut()) {
+                validator.remove("attach_rpc_endpoint");
+            }
+        }
+    }
+
+    fs::write(&config_path, doc.to_string())?;
+    Ok(())
+}
+
+async fn start_local_validator(config: &Config) -> Result<()> {
+    say("Starting the local validator...".bold().green());
 
-    let _network = &args.network;
     let rust_log = config.get_string("arch.rust_log")?;
     let rpc_bind_ip = "0.0.0.0";
     let rpc_bind_port = config.get_string("arch.leader_rpc_port")?;
@@ -4931,20 +9887,8 @@ async fn start_local_validator(args: &ValidatorStartArgs, config: &Config) -> Re
     };
 
     let container_name = "local_validator";
-    let container_exists = String::from_utf8(
-        ShellCommand::new("docker")
-            .arg("ps")
-            .arg("-a")
-            .arg("--format")
-            .arg("{{.Names}}")
-            .output()
-            .context("Failed to check existing containers")?
-            .stdout,
-    )?
-    .lines()
-    .any(|name| name == container_name);
 
-    let output = if container_exists {
+    let output = if container_exists(container_name)? {
         ShellCommand::new("docker")
             .arg("start")
             .arg(container_name)
@@ -4987,7 +9931,7 @@ async fn start_local_validator(args: &ValidatorStartArgs, config: &Config) -> Re
         ));
     }
 
-    println!("{}", "Local validator started successfully!".bold().green());
+    say("Local validator started successfully!".bold().green());
     Ok(())
 }
 
@@ -4998,18 +9942,19 @@ async fn start_gcp_validator(args: &ValidatorStartArgs, config: &Config) -> Resu
         .map_or("us-central1".to_string(), |r| r.to_string());
     let machine_type = args.gcp_machine_type.as_ref()
         .map_or("e2-medium".to_string(), |m| m.to_string());
+    let registry = gcp_registry(args.registry.as_deref(), config, project_id, &region);
     let instance_name = "arch-validator";
 
-    // Get network from ValidatorStartArgs, but if development then network is "devnet", if testnet then network is "testnet", if mainnet then network is "mainnet"
-    let network = match args.network.as_str() {
+    // The image names development networks "devnet"
+    let network = match selected_network(config).as_str() {
         "development" => "devnet",
         "testnet" => "testnet",
         "mainnet" => "mainnet",
         _ => "devnet",
     }.to_string();
-    println!("Network: {}", network.bold().green());
+    say(format!("Network: {}", network.bold().green()));
 
-    println!("{}", "Starting validator deployment to GCP...".bold().green());
+    say("Starting validator deployment to GCP...".bold().green());
 
     // Check if instance already exists
     let instance_exists = ShellCommand::new("gcloud")
@@ -5061,7 +10006,7 @@ async fn start_gcp_validator(args: &ValidatorStartArgs, config: &Config) -> Resu
         }
 
         // Delete the existing instance
-        println!("  {} Removing existing validator instance...", "→".bold().blue());
+        say(format!("  {} Removing existing validator instance...", "→".bold().blue()));
         let delete_output = ShellCommand::new("gcloud")
             .args([
                 "compute", "instances", "delete", instance_name,
@@ -5078,12 +10023,12 @@ async fn start_gcp_validator(args: &ValidatorStartArgs, config: &Config) -> Resu
                 String::from_utf8_lossy(&delete_output.stderr)
             ));
         }
-        println!("  {} Existing instance removed", "✓".bold().green());
+        say(format!("  {} Existing instance removed", "✓".bold().green()));
     }
 
     // Create a temporary directory for the build
-    let temp_dir = tempfile::tempdir()?;
-    println!("  {} Creating build directory", "→".bold().blue());
+    let temp_dir = settings::scratch_dir("the validator build")?;
+    say(format!("  {} Creating build directory", "→".bold().blue()));
 
     // Create Dockerfile
     let dockerfile_content = r#"FROM ghcr.io/arch-network/local_validator:latest
@@ -5098,21 +10043,21 @@ ENTRYPOINT ["/usr/bin/local_validator"]
 
     let dockerfile_path = temp_dir.path().join("Dockerfile");
     fs::write(&dockerfile_path, dockerfile_content)?;
-    println!("  {} Created Dockerfile", "✓".bold().green());
+    say(format!("  {} Created Dockerfile", "✓".bold().green()));
 
     // Create cloudbuild.yaml
     let cloudbuild_content = format!(r#"steps:
 - name: 'gcr.io/cloud-builders/docker'
-  args: ['build', '-t', 'gcr.io/{}/arch-validator:latest', '.']
-images: ['gcr.io/{}/arch-validator:latest']
-"#, project_id, project_id);
+  args: ['build', '-t', '{registry}/arch-validator:latest', '.']
+images: ['{registry}/arch-validator:latest']
+"#, registry = registry);
 
     let cloudbuild_path = temp_dir.path().join("cloudbuild.yaml");
     fs::write(&cloudbuild_path, cloudbuild_content)?;
-    println!("  {} Created Cloud Build configuration", "✓".bold().green());
+    say(format!("  {} Created Cloud Build configuration", "✓".bold().green()));
 
     // Build and push the validator image to Google Container Registry
-    println!("Building and pushing validator image to GCR...");
+    say("Building and pushing validator image to GCR...");
     let build_push_output = ShellCommand::new("gcloud")
         .args([
             "builds", "submit",
@@ -5123,12 +10068,12 @@ images: ['gcr.io/{}/arch-validator:latest']
         .output()
         .context("Failed to build and push image to GCR")?;
 
-    let image_name = format!("gcr.io/{}/arch-validator:latest", project_id);
+    let image_name = format!("{}/arch-validator:latest", registry);
 
-    println!("  {} Image built and pushed successfully", "✓".bold().green());
+    say(format!("  {} Image built and pushed successfully", "✓".bold().green()));
 
     // Create firewall rule if it doesn't exist
-    println!("Ensuring firewall rule exists for validator...");
+    say("Ensuring firewall rule exists for validator...");
     let firewall_rule_name = "allow-validator";
     let create_firewall_output = ShellCommand::new("gcloud")
         .args([
@@ -5142,11 +10087,11 @@ images: ['gcr.io/{}/arch-validator:latest']
 
     // Ignore if firewall rule already exists
     if let Err(e) = create_firewall_output {
-        println!("  {} Firewall rule may already exist: {}", "ℹ".bold().blue(), e);
+        say(format!("  {} Firewall rule may already exist: {}", "ℹ".bold().blue(), e));
     }
 
     // Create and start the GCE instance
-    println!("Creating GCE instance for validator...");
+    say("Creating GCE instance for validator...");
     let instance_name = "arch-validator";
     let create_instance_output = ShellCommand::new("gcloud")
         .args([
@@ -5199,8 +10144,8 @@ images: ['gcr.io/{}/arch-validator:latest']
     println!("External IP: {}", instance_ip);
     println!("Validator RPC endpoint: {}", format!("http://{}:9001", instance_ip).yellow());
 
-    println!("\n{}", "Setting up HTTPS access...".bold().blue());
-    setup_ssl_proxy(project_id, &region, &instance_ip).await?;
+    say(format!("\n{}", "Setting up HTTPS access...".bold().blue()));
+    setup_ssl_proxy(project_id, &region, &registry, &instance_ip).await?;
     
     println!("\nTo view logs, run:");
     println!("  {}", format!("gcloud compute instances get-serial-port-output {} --zone {} --project {}", 
@@ -5220,7 +10165,18 @@ images: ['gcr.io/{}/arch-validator:latest']
 }
 // Update the validator_stop function signature and implementation
 pub async fn validator_stop(args: &ValidatorStartArgs) -> Result<()> {
-    println!("{}", "Stopping the validator...".bold().green());
+    // An attached validator is not ours to stop, so only forget about it
+    if let Some(rpc_url) = attached_validator() {
+        set_attached_validator(None)?;
+        say(format!(
+            "  {} Detached from the validator at {}. It is still running",
+            "✓".bold().green(),
+            rpc_url.yellow()
+        ));
+        return Ok(());
+    }
+
+    say("Stopping the validator...".bold().green());
 
     match args.target.as_str() {
         "local" => stop_local_validator(),
@@ -5236,9 +10192,270 @@ pub async fn validator_stop(args: &ValidatorStartArgs) -> Result<()> {
     }
 }
 
+/// Returns true if a container with this name exists, running or not
+fn container_exists(container_name: &str) -> Result<bool> {
+    Ok(String::from_utf8(
+        ShellCommand::new("docker")
+            .arg("ps")
+            .arg("-a")
+            .arg("--format")
+            .arg("{{.Names}}")
+            .output()
+            .context("Failed to check existing containers")?
+            .stdout,
+    )?
+    .lines()
+    .any(|name| name == container_name))
+}
+
+pub async fn validator_logs(args: &ValidatorLogsArgs) -> Result<()> {
+    if let Some(rpc_url) = attached_validator() {
+        println!(
+            "  {} The validator at {} is attached, not managed by arch-cli. Check its logs where it runs",
+            "ℹ".bold().blue(),
+            rpc_url.yellow()
+        );
+        return Ok(());
+    }
+
+    match args.target.as_str() {
+        "local" => {
+            let container_name = "local_validator";
+            if !container_exists(container_name)? {
+                return Err(anyhow!(
+                    "No '{}' container found. Start it with 'arch-cli validator start'",
+                    container_name
+                ));
+            }
+
+            let mut command = ShellCommand::new("docker");
+            command.arg("logs");
+            if args.follow {
+                command.arg("--follow");
+            }
+            if let Some(tail) = args.tail {
+                command.args(["--tail", &tail.to_string()]);
+            }
+            let status = command
+                .arg(container_name)
+                .status()
+                .context("Failed to run docker logs")?;
+
+            if !status.success() {
+                return Err(anyhow!("docker logs exited with {}", status));
+            }
+            Ok(())
+        }
+        "gcp" => {
+            let project_id = args.gcp_project.as_ref()
+                .ok_or_else(|| anyhow!("GCP project ID is required for the gcp target"))?;
+            let region = args.gcp_region.as_deref().unwrap_or("us-central1");
+
+            gcp_validator_serial_output(project_id, region, args.tail, args.follow).await
+        }
+        _ => Err(anyhow!("Invalid target. Use 'local' or 'gcp'")),
+    }
+}
+
+pub async fn validator_status(args: &ValidatorStatusArgs, config: &Config) -> Result<()> {
+    match args.target.as_str() {
+        "local" => local_validator_status(config).await,
+        "gcp" => {
+            let project_id = args.gcp_project.as_ref()
+                .ok_or_else(|| anyhow!("GCP project ID is required for the gcp target"))?;
+            let region = args.gcp_region.as_deref().unwrap_or("us-central1");
+
+            gcp_validator_status(project_id, region)
+        }
+        _ => Err(anyhow!("Invalid target. Use 'local' or 'gcp'")),
+    }
+}
+
+async fn local_validator_status(config: &Config) -> Result<()> {
+    let container_name = "local_validator";
+
+    // An attached validator has no container, so only its RPC endpoint can be checked
+    let attached = attached_validator();
+    let state = if attached.is_some() {
+        "attached"
+    } else if !container_exists(container_name)? {
+        "not started"
+    } else if check_docker_status(container_name)? {
+        "running"
+    } else {
+        "stopped"
+    };
+
+    let rpc_url = match &attached {
+        Some(rpc_url) => rpc_url.clone(),
+        None => format!("http://localhost:{}", config.get_string("arch.leader_rpc_port")?),
+    };
+
+    let (rpc_error, peers) = if state == "running" || state == "attached" {
+        match probe_rpc_url(&rpc_url).await {
+            Ok(()) => (None, get_connected_peer_count(&reqwest::Client::new(), &rpc_url).await.ok()),
+            Err(e) => (Some(e.to_string()), None),
+        }
+    } else {
+        (None, None)
+    };
+    let rpc_reachable = (state == "running" || state == "attached") && rpc_error.is_none();
+
+    if json_output() {
+        return print_json(&json!({
+            "target": "local",
+            "container": container_name,
+            "state": state,
+            "rpc_url": rpc_url,
+            "rpc_reachable": rpc_reachable,
+            "rpc_error": rpc_error,
+            "peers": peers,
+        }));
+    }
+
+    println!("{}", "Checking validator status...".bold().blue());
+    match state {
+        "not started" => {
+            println!("  {} Validator not started: no '{}' container exists", "✗".bold().red(), container_name);
+            println!("  {} Start it with 'arch-cli validator start'", "→".bold().blue());
+            return Ok(());
+        }
+        "stopped" => {
+            println!("  {} Container '{}' exists but is stopped", "⚠".bold().yellow(), container_name);
+            println!("  {} Start it with 'arch-cli validator start'", "→".bold().blue());
+            return Ok(());
+        }
+        "attached" => println!("  {} Attached to an external validator", "ℹ".bold().blue()),
+        _ => println!("  {} Container '{}' is running", "✓".bold().green(), container_name),
+    }
+
+    match rpc_error {
+        None => println!("  {} RPC responding at {}", "✓".bold().green(), rpc_url.yellow()),
+        Some(e) => {
+            println!("  {} Validator is running but its RPC is not responding: {}", "✗".bold().red(), e);
+            println!("  {} Check 'arch-cli validator logs' for errors", "→".bold().blue());
+            return Ok(());
+        }
+    }
+
+    match peers {
+        Some(peers) => println!("  {} Connected peers: {}", "ℹ".bold().blue(), peers.to_string().yellow()),
+        None => println!("  {} The validator did not report its peer count", "⚠".bold().yellow()),
+    }
+    Ok(())
+}
+
+fn gcp_validator_status(project_id: &str, region: &str) -> Result<()> {
+    let zone = format!("{}-a", region);
+    let describe = |instance: &str, format: &str| -> Result<Option<String>> {
+        let output = ShellCommand::new("gcloud")
+            .args([
+                "compute", "instances", "describe", instance,
+                "--project", project_id,
+                "--zone", &zone,
+                "--format", format,
+            ])
+            .output()
+            .context("Failed to describe the GCP instance")?;
+        Ok(output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string()))
+    };
+
+    let status = describe("arch-validator", "get(status)")?;
+    let validator_ip = describe("arch-validator", "get(networkInterfaces[0].accessConfigs[0].natIP)")?
+        .filter(|ip| !ip.is_empty());
+    let proxy_ip = describe("arch-validator-proxy", "get(networkInterfaces[0].accessConfigs[0].natIP)")?
+        .filter(|ip| !ip.is_empty());
+
+    let rpc_endpoint = validator_ip.as_ref().map(|ip| format!("http://{}:9001", ip));
+    let https_endpoint = proxy_ip.as_ref().map(|ip| format!("https://{}", ip));
+
+    if json_output() {
+        return print_json(&json!({
+            "target": "gcp",
+            "instance": "arch-validator",
+            "status": status.as_deref().unwrap_or("not deployed"),
+            "rpc_endpoint": rpc_endpoint,
+            "https_endpoint": https_endpoint,
+        }));
+    }
+
+    println!("{}", "Checking GCP validator status...".bold().blue());
+    let Some(status) = status else {
+        println!("  {} Validator not deployed: no 'arch-validator' instance in {}", "✗".bold().red(), zone);
+        return Ok(());
+    };
+
+    let glyph = if status == "RUNNING" { "✓".bold().green() } else { "⚠".bold().yellow() };
+    println!("  {} Instance 'arch-validator': {}", glyph, status.yellow());
+    if let Some(rpc_endpoint) = rpc_endpoint {
+        println!("  {} RPC endpoint: {}", "ℹ".bold().blue(), rpc_endpoint.yellow());
+    }
+    match https_endpoint {
+        Some(https_endpoint) => println!("  {} HTTPS endpoint: {}", "ℹ".bold().blue(), https_endpoint.yellow()),
+        None => println!("  {} No SSL proxy instance found", "ℹ".bold().blue()),
+    }
+    Ok(())
+}
+
+/// Prints the serial port output of the GCP validator instance. With `follow`, polls for new
+/// output from the offset gcloud reports after each read.
+async fn gcp_validator_serial_output(
+    project_id: &str,
+    region: &str,
+    tail: Option<usize>,
+    follow: bool,
+) -> Result<()> {
+    let zone = format!("{}-a", region);
+    let next_start = Regex::new(r"--start=(\d+)")?;
+    let mut start: Option<String> = None;
+
+    loop {
+        let mut command = ShellCommand::new("gcloud");
+        command.args([
+            "compute", "instances", "get-serial-port-output", "arch-validator",
+            "--project", project_id,
+            "--zone", &zone,
+        ]);
+        if let Some(start) = &start {
+            command.arg(format!("--start={}", start));
+        }
+        let output = command
+            .output()
+            .context("Failed to get the serial port output of the GCP validator")?;
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !output.status.success() {
+            return Err(anyhow!("Failed to get the serial port output: {}", stderr.trim()));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let lines: Vec<&str> = stdout.lines().collect();
+        // Only the first read is cut to the requested tail, later reads are all new output
+        let skip = match (tail, &start) {
+            (Some(tail), None) => lines.len().saturating_sub(tail),
+            _ => 0,
+        };
+        for line in &lines[skip..] {
+            println!("{}", line);
+        }
+
+        if !follow {
+            return Ok(());
+        }
+        let captures = next_start
+            .captures(&stderr)
+            .ok_or_else(|| anyhow!("gcloud did not report where the serial port output ends, so it cannot be followed"))?;
+        start = Some(captures[1].to_string());
+        tokio::time::sleep(Duration::from_secs(5)).await;
+    }
+}
+
 // Update the stop_gcp_validator function signature
 async fn stop_gcp_validator(project_id: &str, region: &str) -> Result<()> {
-    println!("  {} Managing GCP validator...", "→".bold().blue());
+    say(format!("  {} Managing GCP validator...", "→".bold().blue()));
 
     // Get instance details with separate fields
     let describe_output = ShellCommand::new("gcloud")
@@ -5265,11 +10482,11 @@ async fn stop_gcp_validator(project_id: &str, region: &str) -> Result<()> {
         match selection {
             0 => {
                 if status == "SUSPENDED" {
-                    println!("  {} Instance is already suspended", "ℹ".bold().blue());
+                    say(format!("  {} Instance is already suspended", "ℹ".bold().blue()));
                     return Ok(());
                 }
 
-                println!("  {} Suspending GCP validator...", "→".bold().blue());
+                say(format!("  {} Suspending GCP validator...", "→".bold().blue()));
                 let suspend_output = ShellCommand::new("gcloud")
                     .args([
                         "compute", "instances", "suspend",
@@ -5288,7 +10505,7 @@ async fn stop_gcp_validator(project_id: &str, region: &str) -> Result<()> {
                     ));
                 }
 
-                println!("{}", "GCP validator suspended successfully!".bold().green());
+                say("GCP validator suspended successfully!".bold().green());
             }
             1 => {
                 let proceed = Confirm::with_theme(&ColorfulTheme::default())
@@ -5297,12 +10514,12 @@ async fn stop_gcp_validator(project_id: &str, region: &str) -> Result<()> {
                     .interact()?;
 
                 if !proceed {
-                    println!("  {} Operation cancelled", "ℹ".bold().blue());
+                    say(format!("  {} Operation cancelled", "ℹ".bold().blue()));
                     return Ok(());
                 }
 
                 // Delete proxy instance first
-                println!("  {} Deleting HTTPS proxy...", "→".bold().blue());
+                say(format!("  {} Deleting HTTPS proxy...", "→".bold().blue()));
                 let _ = ShellCommand::new("gcloud")
                     .args([
                         "compute", "instances", "delete", "arch-validator-proxy",
@@ -5312,7 +10529,7 @@ async fn stop_gcp_validator(project_id: &str, region: &str) -> Result<()> {
                     ])
                     .output();
 
-                println!("  {} Deleting GCP validator...", "→".bold().blue());
+                say(format!("  {} Deleting GCP validator...", "→".bold().blue()));
                 let delete_output = ShellCommand::new("gcloud")
                     .args([
                         "compute", "instances", "delete",
@@ -5331,7 +10548,7 @@ async fn stop_gcp_validator(project_id: &str, region: &str) -> Result<()> {
                     ));
                 }
 
-                println!("{}", "GCP validator deleted successfully!".bold().green());
+                say("GCP validator deleted successfully!".bold().green());
             }
             _ => unreachable!()
         }
@@ -5343,7 +10560,7 @@ async fn stop_gcp_validator(project_id: &str, region: &str) -> Result<()> {
 }
 
 fn stop_local_validator() -> Result<()> {
-    println!("  {} Stopping local validator...", "→".bold().blue());
+    say(format!("  {} Stopping local validator...", "→".bold().blue()));
 
     // Stop the container
     let stop_output = ShellCommand::new("docker")
@@ -5359,7 +10576,7 @@ fn stop_local_validator() -> Result<()> {
             String::from_utf8_lossy(&stop_output.stderr)
         );
     } else {
-        println!("  {} Local validator stopped", "✓".bold().green());
+        say(format!("  {} Local validator stopped", "✓".bold().green()));
     }
 
     // Remove the container and its volumes
@@ -5377,22 +10594,16 @@ fn stop_local_validator() -> Result<()> {
         ));
     }
 
-    println!("{}", "Local validator stopped and removed successfully!".bold().green());
+    say("Local validator stopped and removed successfully!".bold().green());
     Ok(())
 }
 
 pub async fn project_create(args: &CreateProjectArgs, config: &Config) -> Result<()> {
     ensure_global_config()?;
-    println!("{}", "Creating a new project...".bold().green());
+    say("Creating a new project...".bold().green());
     
     // Get the project directory from the config or prompt the user
-    let project_dir = match config.get_string("project.directory") {
-        Ok(dir) => PathBuf::from(dir),
-        Err(_) => {
-            let default_dir = get_default_project_dir();
-            prompt_for_project_dir(&default_dir)?
-        }
-    };
+    let project_dir = get_project_dir(config)?;
 
     // Ensure the project directory and its projects subfolder exist
     let projects_subfolder = project_dir.join("projects");
@@ -5401,22 +10612,22 @@ pub async fn project_create(args: &CreateProjectArgs, config: &Config) -> Result
             "Failed to create project directory: {:?}",
             project_dir
         ))?;
-        println!(
+        say(format!(
             "  {} Created project directory at {:?}",
             "✓".bold().green(),
             project_dir
-        );
+        ));
     }
     if !projects_subfolder.exists() {
         fs::create_dir_all(&projects_subfolder).context(format!(
             "Failed to create projects subfolder within project directory: {:?}",
             projects_subfolder
         ))?;
-        println!(
+        say(format!(
             "  {} Created projects subfolder within project directory at {:?}",
             "✓".bold().green(),
             projects_subfolder
-        );
+        ));
     }
 
     // Update the config with the new project directory
@@ -5425,6 +10636,7 @@ pub async fn project_create(args: &CreateProjectArgs, config: &Config) -> Result
     // Get project name, either from args or by asking the user
     let mut project_name = args.name.clone().unwrap_or_default();
     if project_name.is_empty() {
+        require_interactive("Choosing a project name (pass --name instead)")?;
         project_name = Input::<String>::new()
             .with_prompt("Enter a name for your project")
             .interact()?;
@@ -5444,128 +10656,78 @@ pub async fn project_create(args: &CreateProjectArgs, config: &Config) -> Result
     fs::create_dir_all(&new_project_dir).context(format!(
         "Failed to create project directory: {:?}",
         new_project_dir
-    ))?;
-    println!(
-        "  {} Created project directory at {:?}",
-        "✓".bold().green(),
-        new_project_dir
-    );
-
-    // Create the app folder
-    let app_dir = new_project_dir.join("app");
-    fs::create_dir_all(&app_dir)
-        .context(format!("Failed to create app directory: {:?}", app_dir))?;
-
-    // Copy the sample project files to the new project directory
-    extract_project_files(&SAMPLE_DIR, &new_project_dir)?;
-
-    // Create Vite app using npm
-    println!("Creating Vite application...");
-    let create_vite_output = std::process::Command::new("npm")
-        .args(["create", "vite@latest", "frontend", "--", "--template", "react"])
-        .current_dir(&app_dir)
-        .output()
-        .context("Failed to create Vite application")?;
-
-    if !create_vite_output.status.success() {
-        return Err(anyhow!("Failed to create Vite application: {}",
-            String::from_utf8_lossy(&create_vite_output.stderr)));
-    }
-    println!("  {} Created Vite application", "✓".bold().green());
-
-    // Change to frontend directory and install base dependencies
-    let frontend_dir = app_dir.join("frontend");
-    let install_output = std::process::Command::new("npm")
-        .arg("install")
-        .current_dir(&frontend_dir)
-        .output()
-        .context("Failed to install base dependencies")?;
+    ))?;
+    say(format!(
+        "  {} Created project directory at {:?}",
+        "✓".bold().green(),
+        new_project_dir
+    ));
 
-    if !install_output.status.success() {
-        return Err(anyhow!("Failed to install base dependencies: {}",
-            String::from_utf8_lossy(&install_output.stderr)));
-    }
-    println!("  {} Installed base dependencies", "✓".bold().green());
+    // Create the app folder
+    let app_dir = new_project_dir.join("app");
+    fs::create_dir_all(&app_dir)
+        .context(format!("Failed to create app directory: {:?}", app_dir))?;
 
-    // Install additional packages
-    let additional_packages = ["sats-connect", "@saturnbtcio/arch-sdk"];
-    let install_additional_output = std::process::Command::new("npm")
-        .arg("install")
-        .args(&additional_packages)
-        .current_dir(&frontend_dir)
-        .output()
-        .context("Failed to install additional packages")?;
+    // Copy the sample project files to the new project directory
+    extract_project_files(&SAMPLE_DIR, &new_project_dir)?;
 
-    if !install_additional_output.status.success() {
-        return Err(anyhow!("Failed to install additional packages: {}",
-            String::from_utf8_lossy(&install_additional_output.stderr)));
-    }
-    println!("  {} Installed additional packages", "✓".bold().green());
+    scaffold_frontend(&app_dir, args.no_install, config)?;
 
-    println!("{}", "New project created successfully! 🎉".bold().green());
-    println!(
+    say("New project created successfully! 🎉".bold().green());
+    say(format!(
         "  {} Project location: {:?}",
         "ℹ".bold().blue(),
         new_project_dir
-    );
+    ));
 
-    println!("\n{}", "Next steps:".bold().yellow());
-    println!(
+    say(format!("\n{}", "Next steps:".bold().yellow()));
+    say(format!(
         "  1. Navigate to {} to find the Rust program template",
         new_project_dir.join("app").join("program").display().to_string().yellow()
-    );
-    println!("  2. Edit the source code to implement your program logic");
-    println!("  3. When ready, run {} to compile and deploy your program to the network", "arch-cli deploy".cyan());
-    println!("\n{}", "Need help? Check out our documentation at https://docs.arch.network".italic());
+    ));
+    say("  2. Edit the source code to implement your program logic");
+    say(format!("  3. When ready, run {} to compile and deploy your program to the network", "arch-cli deploy".cyan()));
+    say(format!("\n{}", "Need help? Check out our documentation at https://docs.arch.network".italic()));
 
     Ok(())
 }
 
 pub async fn project_deploy(config: &Config) -> Result<()> {
-    println!("{}", "Deploying a project...".bold().green());
+    say("Deploying a project...".bold().green());
 
     // Get the project directory from the config
-    let project_dir = PathBuf::from(config.get_string("project.directory")?);
+    let project_dir = get_project_dir(config)?;
 
-    // Get list of projects
-    let projects: Vec<_> = fs::read_dir(&project_dir)?
-        .filter_map(|entry| {
-            entry.ok().and_then(|e| {
-                let path = e.path();
-                if path.is_dir() && path.join("program").exists() {
-                    Some(path.file_name().unwrap().to_string_lossy().into_owned())
-                } else {
-                    None
-                }
-            })
-        })
-        .collect();
+    // Get list of projects along with the program crate each one will deploy, so that
+    // only projects that can actually be deployed are offered
+    let projects = deployable_projects(&project_dir.join("projects"))?;
 
     if projects.is_empty() {
-        println!("No deployable projects found. Make sure your projects have a 'program' folder.");
+        println!("No deployable projects found. Make sure your projects have an 'app/program' or 'program' folder.");
         return Ok(());
     }
 
     // Ask user to select a project
+    require_interactive("Selecting a project to deploy")?;
+    let names: Vec<&String> = projects.iter().map(|(name, _)| name).collect();
     let selection = Select::new()
         .with_prompt("Select a project to deploy")
-        .items(&projects)
+        .items(&names)
         .interact()?;
 
-    let selected_project = &projects[selection];
-    let program_dir = project_dir.join(selected_project).join("app/program");
+    let (selected_project, program_dir) = &projects[selection];
 
-    println!("Deploying project: {}", selected_project.yellow());
+    say(format!("Deploying project: {}", selected_project.yellow()));
 
     // Here, call your existing deploy function with the program_dir
     // You may need to modify your existing deploy function to accept a PathBuf instead of DeployArgs
     let rpc_url = "";
-    if let Err(e) = deploy_program_from_path(&program_dir, config, None, rpc_url.to_string()).await {
+    if let Err(e) = deploy_program_from_path(program_dir, config, None, rpc_url.to_string(), false, false).await {
         println!("Failed to deploy program: {}", e);
         return Err(e);
     }
 
-    println!("{}", "Project deployed successfully!".bold().green());
+    say("Project deployed successfully!".bold().green());
     Ok(())
 }
 
@@ -5574,11 +10736,11 @@ fn ensure_default_config() -> Result<()> {
     if !config_path.exists() {
         let default_config_content = include_str!("../templates/config.default.toml");
         fs::write(&config_path, default_config_content)?;
-        println!(
+        say(format!(
             "  {} Created default configuration at {:?}",
             "✓".bold().green(),
             config_path
-        );
+        ));
     }
     Ok(())
 }
@@ -5587,7 +10749,7 @@ pub fn ensure_global_config() -> Result<()> {
     let config_dir = get_config_dir()?;
     if !config_dir.exists() {
         fs::create_dir_all(&config_dir)?;
-        println!("Created global configuration directory at {:?}", config_dir);
+        say(format!("Created global configuration directory at {:?}", config_dir));
     }
 
     ensure_default_config()?;
@@ -5596,7 +10758,7 @@ pub fn ensure_global_config() -> Result<()> {
     let arch_data_dir = config_dir.join("arch-data");
     if !arch_data_dir.exists() {
         fs::create_dir_all(&arch_data_dir)?;
-        println!("Created arch-data directory at {:?}", arch_data_dir);
+        say(format!("Created arch-data directory at {:?}", arch_data_dir));
     }
 
     // Copy template files if they don't exist
@@ -5605,62 +10767,154 @@ pub fn ensure_global_config() -> Result<()> {
     Ok(())
 }
 
+/// Templates copied into the config directory, as (file name, bundled content)
+const BUNDLED_TEMPLATES: [(&str, &str); 9] = [
+    ("config.toml", include_str!("../templates/config.default.toml")),
+    ("init.sh", include_str!("../templates/init.sh")),
+    ("bootnode.sh", include_str!("../templates/bootnode.sh")),
+    ("arch-docker-compose.yml", include_str!("../templates/arch-docker-compose.yml")),
+    ("bitcoin-docker-compose.yml", include_str!("../templates/bitcoin-docker-compose.yml")),
+    ("btc-rpc-explorer.dockerfile", include_str!("../templates/btc-rpc-explorer.dockerfile")),
+    ("server-docker-compose.yml", include_str!("../templates/server-docker-compose.yml")),
+    ("leader.sh", include_str!("../templates/leader.sh")),
+    ("validator.sh", include_str!("../templates/validator.sh")),
+];
+
+/// Creates the bundled templates missing from the config directory. Existing files are left
+/// alone, even when a newer release bundles a different version: `templates sync` replaces them
 fn copy_template_files() -> Result<()> {
     let config_dir = get_config_dir()?;
-    // Files that should always be updated on new versions
-    let force_update_templates = [
-        "server-docker-compose.yml",
-    ];
 
-    let templates = [
-        ("config.default.toml", "config.toml"),
-        ("init.sh", "init.sh"),
-        ("bootnode.sh", "bootnode.sh"),
-        ("arch-docker-compose.yml", "arch-docker-compose.yml"),
-        ("bitcoin-docker-compose.yml", "bitcoin-docker-compose.yml"),
-        ("btc-rpc-explorer.dockerfile", "btc-rpc-explorer.dockerfile"),
-        ("server-docker-compose.yml", "server-docker-compose.yml"),
-        ("leader.sh", "leader.sh"),
-        ("validator.sh", "validator.sh"),
-    ];
+    for (dest, template_content) in BUNDLED_TEMPLATES.iter() {
+        let dest_path = config_dir.join(dest);
+        if !dest_path.exists() {
+            detail(format!("Creating {} at {:?}", dest, dest_path));
+            fs::write(&dest_path, template_content)?;
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn templates_sync(args: &TemplatesSyncArgs) -> Result<()> {
+    say("Syncing templates...".bold().green());
+
+    let config_dir = get_config_dir()?;
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs();
+    let (mut created, mut updated, mut differing) = (0, 0, 0);
 
-    for (template, dest) in templates.iter() {
+    // config.toml holds user settings and is managed with the config commands instead
+    for (dest, template_content) in BUNDLED_TEMPLATES.iter().filter(|(dest, _)| *dest != "config.toml") {
         let dest_path = config_dir.join(dest);
-        let should_update = !dest_path.exists() || force_update_templates.contains(dest);
-
-        if should_update {
-            let template_content = match *template {
-                "config.default.toml" => include_str!("../templates/config.default.toml"),
-                "init.sh" => include_str!("../templates/init.sh"),
-                "bootnode.sh" => include_str!("../templates/bootnode.sh"),
-                "arch-docker-compose.yml" => include_str!("../templates/arch-docker-compose.yml"),
-                "bitcoin-docker-compose.yml" => include_str!("../templates/bitcoin-docker-compose.yml"),
-                "btc-rpc-explorer.dockerfile" => include_str!("../templates/btc-rpc-explorer.dockerfile"),
-                "server-docker-compose.yml" => include_str!("../templates/server-docker-compose.yml"),
-                "leader.sh" => include_str!("../templates/leader.sh"),
-                "validator.sh" => include_str!("../templates/validator.sh"),
-                _ => return Err(anyhow!("Unknown template file: {}", template)),
-            };
 
-            if dest_path.exists() {
-                println!("Updating {} with new version", dest);
+        if !dest_path.exists() {
+            if args.diff {
+                println!("  {} {} is missing and would be created", "→".bold().blue(), dest.yellow());
             } else {
-                println!("Creating {} at {:?}", dest, dest_path);
+                fs::write(&dest_path, template_content)?;
+                say(format!("  {} Created {}", "✓".bold().green(), dest.yellow()));
             }
+            created += 1;
+            continue;
+        }
+
+        let current = fs::read_to_string(&dest_path)
+            .with_context(|| format!("Failed to read {}", dest_path.display()))?;
+        if current == *template_content {
+            say(format!("  {} {} is up to date", "ℹ".bold().blue(), dest));
+            continue;
+        }
+        differing += 1;
 
+        if args.diff {
+            println!("  {} {} differs from the bundled version:", "⚠".bold().yellow(), dest.yellow());
+            for line in line_diff(&current, template_content) {
+                println!("      {}", line);
+            }
+        } else if args.force {
+            let backup_path = config_dir.join(format!("{}.{}.bak", dest, timestamp));
+            fs::copy(&dest_path, &backup_path)
+                .with_context(|| format!("Failed to back up {}", dest_path.display()))?;
             fs::write(&dest_path, template_content)?;
+            say(format!(
+                "  {} Updated {} (previous version saved as {})",
+                "✓".bold().green(),
+                dest.yellow(),
+                backup_path.display()
+            ));
+            updated += 1;
+        } else {
+            println!(
+                "  {} {} differs from the bundled version and was left unchanged",
+                "ℹ".bold().blue(),
+                dest
+            );
+        }
+    }
+
+    if args.diff {
+        println!(
+            "  {} {} missing and {} changed file(s). Nothing was written",
+            "ℹ".bold().blue(),
+            created,
+            differing
+        );
+    } else {
+        say(format!("  {} {} created, {} updated", "✓".bold().green(), created, updated));
+        if differing > updated {
+            say(format!(
+                "  {} Use 'arch-cli templates sync --diff' to review the changed files and --force to replace them",
+                "→".bold().blue()
+            ));
         }
     }
 
     Ok(())
 }
 
+/// Line-based diff of two texts: unchanged lines are omitted, removed lines start with '-'
+/// and added lines with '+'
+fn line_diff(old: &str, new: &str) -> Vec<ColoredString> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // Longest common subsequence table, filled from the end
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            lines.push(format!("+ {}", new[j]).green());
+            j += 1;
+        } else {
+            lines.push(format!("- {}", old[i]).red());
+            i += 1;
+        }
+    }
+    lines
+}
+
 // Add after the start_gcp_validator function
-async fn setup_ssl_proxy(project_id: &str, region: &str, validator_ip: &str) -> Result<()> {
-    println!("  {} Setting up HTTPS proxy...", "→".bold().blue());
+async fn setup_ssl_proxy(project_id: &str, region: &str, registry: &str, validator_ip: &str) -> Result<()> {
+    say(format!("  {} Setting up HTTPS proxy...", "→".bold().blue()));
 
     // Create a temporary directory for the build
-    let temp_dir = tempfile::tempdir()?;
+    let temp_dir = settings::scratch_dir("the validator SSL proxy")?;
 
     // Create nginx.conf
     let nginx_conf = format!(r#"
@@ -5716,9 +10970,9 @@ EXPOSE 443
     fs::write(temp_dir.path().join("Dockerfile"), dockerfile_content)?;
 
     // Create and push the proxy image
-    let proxy_image = format!("gcr.io/{}/arch-validator-proxy:latest", project_id);
+    let proxy_image = format!("{}/arch-validator-proxy:latest", registry);
 
-    println!("  {} Building and pushing proxy image...", "→".bold().blue());
+    say(format!("  {} Building and pushing proxy image...", "→".bold().blue()));
     let build_status = Command::new("docker")
         .args([
             "build",
@@ -5742,7 +10996,7 @@ EXPOSE 443
     }
 
     // Create firewall rule for internal communication
-    println!("  {} Creating firewall rule for internal communication...", "→".bold().blue());
+    say(format!("  {} Creating firewall rule for internal communication...", "→".bold().blue()));
     let _ = ShellCommand::new("gcloud")
         .args([
             "compute", "firewall-rules", "create", "allow-validator-internal",
@@ -5755,7 +11009,7 @@ EXPOSE 443
         .output();
 
     // Create firewall rule for HTTPS
-    println!("  {} Creating firewall rule for HTTPS...", "→".bold().blue());
+    say(format!("  {} Creating firewall rule for HTTPS...", "→".bold().blue()));
     let _ = ShellCommand::new("gcloud")
         .args([
             "compute", "firewall-rules", "create", "allow-validator-https",
@@ -5767,7 +11021,7 @@ EXPOSE 443
         .output();
 
     // Deploy the proxy container
-    println!("  {} Deploying HTTPS proxy...", "→".bold().blue());
+    say(format!("  {} Deploying HTTPS proxy...", "→".bold().blue()));
     let create_proxy_output = ShellCommand::new("gcloud")
         .args([
             "compute", "instances", "create-with-container", "arch-validator-proxy",
@@ -5800,7 +11054,7 @@ EXPOSE 443
         .stdout).trim().to_string();
 
     // Add after getting the proxy's external IP
-    println!("\n{}", "Running connectivity tests...".bold().blue());
+    say(format!("\n{}", "Running connectivity tests...".bold().blue()));
 
     // Test if validator is reachable from proxy
     let test_connection = ShellCommand::new("gcloud")
@@ -5813,9 +11067,9 @@ EXPOSE 443
         .output()
         .context("Failed to test connection")?;
 
-    println!("Connection test result:");
-    println!("{}", String::from_utf8_lossy(&test_connection.stdout));
-    println!("{}", String::from_utf8_lossy(&test_connection.stderr));
+    detail("Connection test result:");
+    detail(String::from_utf8_lossy(&test_connection.stdout));
+    detail(String::from_utf8_lossy(&test_connection.stderr));
 
     // Check nginx logs
     let check_logs = ShellCommand::new("gcloud")
@@ -5828,11 +11082,11 @@ EXPOSE 443
         .output()
         .context("Failed to check nginx logs")?;
 
-    println!("\nNginx logs:");
-    println!("{}", String::from_utf8_lossy(&check_logs.stdout));
-    println!("{}", String::from_utf8_lossy(&check_logs.stderr));
+    detail("\nNginx logs:");
+    detail(String::from_utf8_lossy(&check_logs.stdout));
+    detail(String::from_utf8_lossy(&check_logs.stderr));
 
-    println!("\n{}", "HTTPS proxy setup complete!".bold().green());
+    say(format!("\n{}", "HTTPS proxy setup complete!".bold().green()));
     println!("Proxy IP: {}", proxy_ip);
     println!("HTTPS endpoint: {}", format!("https://{}", proxy_ip).yellow());
     println!("\nNote: Using self-signed certificate. You may need to accept the security warning in your browser.");
@@ -5841,7 +11095,7 @@ EXPOSE 443
 }
 
 pub async fn assign_ownership(args: &AssignOwnershipArgs, config: &Config) -> Result<()> {
-    println!("{}", "Assigning program ownership...".bold().green());
+    say("Assigning program ownership...".bold().green());
 
     // Get the keys file
     let keys_file = get_config_dir()?.join("keys.json");
@@ -5872,7 +11126,7 @@ pub async fn assign_ownership(args: &AssignOwnershipArgs, config: &Config) -> Re
 
     // Get RPC URL
     let rpc_url = get_rpc_url_with_fallback(args.rpc_url.clone(), config).unwrap();
-    println!("  {} RPC URL: {}", "ℹ".bold().blue(), rpc_url.yellow());
+    detail(format!("  {} RPC URL: {}", "ℹ".bold().blue(), rpc_url.yellow()));
 
     // Transfer ownership
     transfer_account_ownership(
@@ -5882,29 +11136,32 @@ pub async fn assign_ownership(args: &AssignOwnershipArgs, config: &Config) -> Re
         rpc_url,
     ).await?;
 
-    println!(
+    say(format!(
         "  {} Successfully transferred ownership to program: {}",
         "✓".bold().green(),
         args.program_id.bright_green()
-    );
+    ));
 
     Ok(())
 }
 
 pub async fn update_account(args: &UpdateAccountArgs, config: &Config) -> Result<()> {
-    println!("{}", "Updating account data...".bold().green());
+    say("Updating account data...".bold().green());
 
     // Get the keys file
     let keys_file = get_config_dir()?.join("keys.json");
 
-    // Get the keypair and pubkey for the account
-    let (caller_keypair, caller_pubkey) = if args.identifier.len() == 64 {
+    // Get the signers and pubkey for the account
+    let (signers, caller_pubkey) = if !args.signers.is_empty() {
+        let pubkey = Pubkey::from_slice(&hex::decode(resolve_pubkey_hex(&args.identifier)?)?);
+        (load_signer_files(&args.signers)?, pubkey)
+    } else if args.identifier.len() == 64 {
         // If identifier is a public key
         let key_name = find_key_name_by_pubkey(&keys_file, &args.identifier)?;
         let pubkey_bytes = hex::decode(&args.identifier)?;
         let pubkey = Pubkey::from_slice(&pubkey_bytes);
         (
-            get_keypair_from_name(&key_name, &keys_file)?,
+            vec![get_keypair_from_name(&key_name, &keys_file)?],
             pubkey,
         )
     } else {
@@ -5913,7 +11170,7 @@ pub async fn update_account(args: &UpdateAccountArgs, config: &Config) -> Result
         let pubkey_bytes = hex::decode(&pubkey)?;
         let pubkey = Pubkey::from_slice(&pubkey_bytes);
         (
-            get_keypair_from_name(&args.identifier, &keys_file)?,
+            vec![get_keypair_from_name(&args.identifier, &keys_file)?],
             pubkey,
         )
     };
@@ -5922,39 +11179,290 @@ pub async fn update_account(args: &UpdateAccountArgs, config: &Config) -> Result
     let data = fs::read(&args.data_file)
         .context(format!("Failed to read data file: {:?}", args.data_file))?;
 
+    if data.is_empty() {
+        return Err(anyhow!("Data file {:?} is empty", args.data_file));
+    }
+
     // Get RPC URL
-    let rpc_url = get_rpc_url_with_fallback(args.rpc_url.clone(), config).unwrap();
-    println!("  {} RPC URL: {}", "ℹ".bold().blue(), rpc_url.yellow());
+    let rpc_url = get_rpc_url_with_fallback(args.rpc_url.clone(), config)?;
+    detail(format!("  {} RPC URL: {}", "ℹ".bold().blue(), rpc_url.yellow()));
 
-    // Create the extend bytes instruction
-    let caller_keypair_clone = caller_keypair.clone();
-    let caller_pubkey_clone = caller_pubkey;
-    let rpc_url_clone = rpc_url.clone();
-    let data_clone = data.clone();
-
-    // Send the extend bytes instruction
-    let (txid, _) = tokio::task::spawn_blocking(move || {
-        sign_and_send_instruction(
-            SystemInstruction::new_extend_bytes_instruction(
-                data_clone,
-                caller_pubkey_clone,
-            ),
-            vec![caller_keypair_clone],
-            rpc_url_clone,
-        )
-    }).await??;
+    // Writes must start inside the current data or right after it, leaving no gap
+    let current_len = read_account_info_async(&rpc_url, caller_pubkey)
+        .await
+        .context("Failed to read the account")?
+        .data
+        .len();
+    if args.offset as usize > current_len {
+        return Err(anyhow!(
+            "Offset {} is past the end of the account data ({} bytes)",
+            args.offset,
+            current_len
+        ));
+    }
+
+    // Each extend bytes instruction carries the offset and length of its chunk
+    let mut txids = Vec::new();
+    for (index, chunk) in data.chunks(extend_bytes_max_len()).enumerate() {
+        let offset = args.offset as usize + index * extend_bytes_max_len();
+        let mut bytes = Vec::with_capacity(8 + chunk.len());
+        bytes.extend((offset as u32).to_le_bytes());
+        bytes.extend((chunk.len() as u32).to_le_bytes());
+        bytes.extend(chunk);
+
+        let instruction = SystemInstruction::new_extend_bytes_instruction(bytes, caller_pubkey);
+        check_signers(&instruction, &signers)?;
+        let (txid, _) =
+            sign_and_send_instruction_async(instruction, signers.clone(), rpc_url.clone()).await?;
+        // A chunk that grows the account must land before the next one starts at its end
+        if offset + chunk.len() > current_len {
+            get_processed_transaction_async(&rpc_url, txid.clone()).await?;
+        }
+        txids.push(txid);
+    }
 
     println!(
         "  {} Successfully updated account data. Transaction ID: {}",
         "✓".bold().green(),
-        txid.yellow()
+        txids.join(", ").yellow()
     );
-    println!(
-        "  {} Updated {} bytes",
+    say(format!(
+        "  {} Updated {} bytes at offset {}",
         "ℹ".bold().blue(),
-        data.len().to_string().bright_white()
-    );
+        data.len().to_string().bright_white(),
+        args.offset
+    ));
+
+    Ok(())
+}
+
+pub async fn account_balance(args: &AccountBalanceArgs, config: &Config) -> Result<()> {
+    show_account(&args.identifier, args.rpc_url.clone(), false, config).await
+}
+
+pub async fn account_info(args: &AccountInfoArgs, config: &Config) -> Result<()> {
+    show_account(&args.identifier, args.rpc_url.clone(), true, config).await
+}
+
+/// Prints the Bitcoin balance and Arch Network state of an account. `full` adds the key name
+/// and executable flag, as shown by `account info`.
+async fn show_account(identifier: &str, rpc_url: Option<String>, full: bool, config: &Config) -> Result<()> {
+    // Accept either a hex-encoded public key or a key name from keys.json
+    let pubkey_hex = resolve_pubkey_hex(identifier)?;
+    let pubkey = Pubkey::from_slice(&hex::decode(&pubkey_hex)?);
+    let name = (!identifier.eq_ignore_ascii_case(&pubkey_hex)).then_some(identifier);
+
+    let rpc_url = get_rpc_url_with_fallback(rpc_url, config)?;
+    probe_rpc_url(&rpc_url).await?;
+
+    let network = config
+        .get_string("bitcoin.network")
+        .unwrap_or_else(|_| "regtest".to_string());
+    let bitcoin_network =
+        Network::from_str(&network).context("Invalid Bitcoin network specified in config")?;
+    let account_address = generate_account_address(&rpc_url, pubkey).await?;
+    let address = Address::from_str(&account_address)
+        .context("The node returned an invalid account address")?
+        .require_network(bitcoin_network)
+        .context("The account address does not match the configured Bitcoin network")?;
+
+    let wallet_manager = WalletManager::new(config)?;
+    let client = &wallet_manager.client;
+
+    // Confirmed outputs are scanned from the UTXO set, so the address does not need to be in the wallet
+    let scan = client.scan_tx_out_set_blocking(&[bitcoincore_rpc::json::ScanTxOutRequest::Single(
+        format!("addr({})", address),
+    )])?;
+    let confirmed = scan.unspents.iter().map(|utxo| utxo.amount).sum::<Amount>();
+    // Unconfirmed outputs are only visible when the wallet tracks the address
+    let unconfirmed = client
+        .list_unspent(Some(0), Some(0), Some(&[&address]), Some(true), None)
+        .map(|utxos| utxos.iter().map(|utxo| utxo.amount).sum::<Amount>())
+        .unwrap_or(Amount::ZERO);
+
+    let account = read_account_info_async(&rpc_url, pubkey).await.ok();
+    // The account is anchored to a UTXO, reported as "txid:vout"
+    let anchor_value = account.as_ref().and_then(|account| {
+        let (txid, vout) = account.utxo.split_once(':')?;
+        let txid = Txid::from_str(txid).ok()?;
+        let out = client.get_tx_out(&txid, vout.parse().ok()?, Some(true)).ok()??;
+        Some(out.value)
+    });
+
+    if json_output() {
+        let mut report = json!({
+            "account": pubkey_hex,
+            "bitcoin": {
+                "address": address.to_string(),
+                "confirmed_sat": confirmed.to_sat(),
+                "unconfirmed_sat": unconfirmed.to_sat(),
+            },
+            "arch": account.as_ref().map(|account| json!({
+                "owner": hex::encode(account.owner.serialize()),
+                "data_len": account.data.len(),
+                "utxo": account.utxo,
+                "utxo_value_sat": anchor_value.map(|value| value.to_sat()),
+            })),
+        });
+        if full {
+            report["name"] = json!(name);
+            if let (Some(arch), Some(account)) = (report["arch"].as_object_mut(), &account) {
+                arch.insert("is_executable".to_string(), json!(account.is_executable));
+            }
+        }
+        return print_json(&report);
+    }
+
+    println!("{}", if full { "Account info:" } else { "Account balance:" }.bold().green());
+    if full {
+        if let Some(name) = name {
+            println!("  {} Name: {}", "ℹ".bold().blue(), name.yellow());
+        }
+    }
+    println!("  {} Account: {}", "ℹ".bold().blue(), pubkey_hex.yellow());
+    println!("{}", "Bitcoin:".bold().green());
+    println!("  {} Address: {}", "ℹ".bold().blue(), address.to_string().yellow());
+    println!("  {} Confirmed: {}", "ℹ".bold().blue(), confirmed);
+    println!("  {} Unconfirmed: {}", "ℹ".bold().blue(), unconfirmed);
+    println!("{}", "Arch Network:".bold().green());
+    match &account {
+        Some(account) => {
+            println!("  {} Owner: {}", "ℹ".bold().blue(), hex::encode(account.owner.serialize()));
+            println!("  {} Data length: {} bytes", "ℹ".bold().blue(), account.data.len());
+            if full {
+                println!("  {} Executable: {}", "ℹ".bold().blue(), account.is_executable);
+            }
+            println!(
+                "  {} Anchor UTXO: {}{}",
+                "ℹ".bold().blue(),
+                account.utxo.yellow(),
+                anchor_value.map_or_else(String::new, |value| format!(" ({})", value))
+            );
+        }
+        None => println!("  {} Account does not exist on the Arch Network yet", "⚠".bold().yellow()),
+    }
+
+    Ok(())
+}
+
+pub async fn dump_account(args: &DumpAccountArgs, config: &Config) -> Result<()> {
+    say("Dumping account data...".bold().green());
+
+    let (pubkey_hex, data_len, data) =
+        fetch_account_data(&args.identifier, &args.range, args.rpc_url.clone(), config).await?;
+
+    write_account_data(&pubkey_hex, data_len, args.range.offset, &data, &args.out)
+}
+
+/// Writes a slice of account data read with `fetch_account_data` to `out`, for `account dump`
+/// and `account read --out`
+fn write_account_data(pubkey_hex: &str, data_len: usize, offset: usize, data: &[u8], out: &Path) -> Result<()> {
+    fs::write(out, data).context(format!("Failed to write data file: {:?}", out))?;
+
+    if json_output() {
+        return print_json(&json!({
+            "account": pubkey_hex,
+            "data_len": data_len,
+            "offset": offset,
+            "length": data.len(),
+            "out": out,
+        }));
+    }
+
+    say(format!(
+        "  {} Wrote {} bytes from offset {} to {:?}",
+        "✓".bold().green(),
+        data.len().to_string().bright_white(),
+        offset,
+        out
+    ));
+
+    Ok(())
+}
 
+/// Reads an account and returns its hex ID, the full data length and the selected slice of its data
+async fn fetch_account_data(
+    identifier: &str,
+    range: &AccountDataRange,
+    rpc_url: Option<String>,
+    config: &Config,
+) -> Result<(String, usize, Vec<u8>)> {
+    // Accept either a hex-encoded public key or a key name from keys.json
+    let pubkey_hex = resolve_pubkey_hex(identifier)?;
+    let pubkey = Pubkey::from_slice(&hex::decode(&pubkey_hex)?);
+
+    let rpc_url = get_rpc_url_with_fallback(rpc_url, config)?;
+    detail(format!("  {} RPC URL: {}", "ℹ".bold().blue(), rpc_url.yellow()));
+
+    let mut data = read_account_info_async(&rpc_url, pubkey)
+        .await
+        .with_context(|| format!("Failed to read account {}", pubkey_hex))?
+        .data;
+
+    let data_len = data.len();
+    if range.offset > data_len {
+        return Err(anyhow!(
+            "Offset {} is past the end of the account data ({} bytes)",
+            range.offset,
+            data_len
+        ));
+    }
+    // A length reaching past the end reads up to the end, like dd
+    let end = range
+        .length
+        .map_or(data_len, |length| range.offset.saturating_add(length).min(data_len));
+    data.truncate(end);
+    data.drain(..range.offset);
+
+    Ok((pubkey_hex, data_len, data))
+}
+
+/// Formats bytes like `xxd`: the offset, 16 bytes in hex and their printable ASCII characters
+fn hex_dump(data: &[u8], start_offset: usize) -> String {
+    data.chunks(16)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let hex: Vec<String> = chunk.iter().map(|byte| format!("{:02x}", byte)).collect();
+            let ascii: String = chunk
+                .iter()
+                .map(|&byte| if byte.is_ascii_graphic() || byte == b' ' { byte as char } else { '.' })
+                .collect();
+            format!("{:08x}  {:<47}  |{}|", start_offset + index * 16, hex.join(" "), ascii)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub async fn read_account(args: &ReadAccountArgs, config: &Config) -> Result<()> {
+    let (pubkey_hex, data_len, data) =
+        fetch_account_data(&args.identifier, &args.range, args.rpc_url.clone(), config).await?;
+
+    if let Some(out) = &args.out {
+        return write_account_data(&pubkey_hex, data_len, args.range.offset, &data, out);
+    }
+
+    if json_output() {
+        return print_json(&json!({
+            "account": pubkey_hex,
+            "data_len": data_len,
+            "offset": args.range.offset,
+            "length": data.len(),
+            "data": hex::encode(&data),
+        }));
+    }
+
+    // Only the dump goes to stdout, so it can be piped
+    eprintln!(
+        "  {} Account {}: {} of {} bytes from offset {}",
+        "ℹ".bold().blue(),
+        pubkey_hex.yellow(),
+        data.len(),
+        data_len,
+        args.range.offset
+    );
+    if !data.is_empty() {
+        println!("{}", hex_dump(&data, args.range.offset));
+    }
     Ok(())
 }
 
@@ -6003,21 +11511,255 @@ pub fn load_and_update_config(config_path: &str) -> Result<Config> {
     load_config(config_path)
 }
 
+/// Directories, relative to a crate or workspace root, that `cargo build-sbf` writes binaries to
+const SO_OUTPUT_DIRS: [&str; 2] = ["target/deploy", "target/sbf-solana-solana/release"];
+
+/// Lists the .so files built for a program crate, newest first. Workspace members build
+/// into the workspace's target directory, so every ancestor with a Cargo.toml is searched
+fn find_program_so_files(program_dir: &Path) -> Vec<(PathBuf, fs::Metadata)> {
+    let mut files: Vec<(PathBuf, fs::Metadata)> = program_dir
+        .ancestors()
+        .filter(|dir| dir.join("Cargo.toml").is_file())
+        .flat_map(|dir| SO_OUTPUT_DIRS.iter().map(move |output| dir.join(output)))
+        .filter_map(|output_dir| fs::read_dir(output_dir).ok())
+        .flat_map(|entries| entries.filter_map(Result::ok))
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().map_or(false, |ext| ext == "so"))
+        .filter_map(|path| fs::metadata(&path).ok().map(|metadata| (path, metadata)))
+        .collect();
+    files.sort_by_key(|(_, metadata)| std::cmp::Reverse(metadata.modified().ok()));
+    files
+}
+
 fn find_program_so_file(path: &PathBuf) -> Result<PathBuf> {
     if path.extension().map_or(false, |ext| ext == "so") {
         // If path directly points to .so file
         Ok(path.clone())
     } else {
-        // Look for .so file in release directory
-        let release_dir = path.join("target/sbf-solana-solana/release");
-        fs::read_dir(&release_dir)
-            .with_context(|| format!("Failed to read release directory at {:?}", release_dir))?
-            .filter_map(|entry| entry.ok())
-            .find(|entry| {
-                entry.path().extension()
-                    .map_or(false, |ext| ext == "so")
+        find_program_so_files(path)
+            .into_iter()
+            .next()
+            .map(|(path, _)| path)
+            .ok_or_else(|| anyhow!("No .so file found in the build output of {}", path.display()))
+    }
+}
+
+/// Renders how long ago a file was modified, e.g. "5m ago"
+fn format_age(modified: std::time::SystemTime) -> String {
+    let secs = modified.elapsed().map_or(0, |age| age.as_secs());
+    match secs {
+        0..=59 => format!("{}s ago", secs),
+        60..=3599 => format!("{}m ago", secs / 60),
+        3600..=86399 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
+}
+
+pub async fn project_artifacts(args: &ProjectArtifactsArgs, config: &Config) -> Result<()> {
+    let project_dir = get_project_dir(config)?;
+    let project_path = project_dir.join("projects").join(&args.name);
+    if !project_path.is_dir() {
+        return Err(anyhow!("Project '{}' not found in {}", args.name, project_dir.join("projects").display()));
+    }
+    let program_dir = find_program_crate(&project_path)
+        .ok_or_else(|| anyhow!("No Arch program crate found in {}", project_path.display()))?;
+
+    let artifacts = find_program_so_files(&program_dir);
+
+    if json_output() {
+        let entries: Vec<Value> = artifacts
+            .iter()
+            .map(|(path, metadata)| {
+                json!({
+                    "path": path.display().to_string(),
+                    "size": metadata.len(),
+                    "modified": metadata
+                        .modified()
+                        .ok()
+                        .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+                        .map(|since_epoch| since_epoch.as_secs()),
+                })
             })
-            .map(|entry| entry.path())
-            .ok_or_else(|| anyhow!("No .so file found in release directory"))
+            .collect();
+        return print_json(&json!({
+            "project": args.name,
+            "program_dir": program_dir.display().to_string(),
+            "artifacts": entries,
+        }));
+    }
+
+    println!("{}", format!("Artifacts of {}:", args.name).bold().green());
+    if artifacts.is_empty() {
+        println!(
+            "  {} No .so files found. Build the program with 'cargo build-sbf' in {}",
+            "ℹ".bold().blue(),
+            program_dir.display()
+        );
+        return Ok(());
+    }
+    for (path, metadata) in &artifacts {
+        println!(
+            "  {} {}  {} bytes  {}",
+            "→".bold().blue(),
+            path.display().to_string().yellow(),
+            metadata.len(),
+            metadata.modified().map_or_else(|_| "unknown age".to_string(), format_age)
+        );
+    }
+    println!(
+        "  {} Pass one to 'arch-cli deploy --elf-path <path>'",
+        "ℹ".bold().blue()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        arch_pubkey_from_secp, compose_port, deployable_projects, funding_tx_vsize, hex_dump,
+        is_not_found_error, migrate_keys, select_funding_inputs,
+    };
+    use bitcoin::Amount;
+    use serde_json::json;
+    use std::fs;
+    use std::path::Path;
+
+    fn write_program_crate(dir: &Path) {
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(
+            dir.join("Cargo.toml"),
+            "[package]\nname = \"program\"\n\n[dependencies]\narch_program = { path = \"../program\" }\n\n[lib]\ncrate-type = [\"cdylib\", \"lib\"]\n",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_resolves_app_program_layout() {
+        let projects_dir = tempfile::tempdir().unwrap();
+        let project = projects_dir.path().join("demo");
+        write_program_crate(&project.join("app/program"));
+
+        let projects = deployable_projects(projects_dir.path()).unwrap();
+        assert_eq!(projects, vec![("demo".to_string(), project.join("app/program"))]);
+    }
+
+    #[test]
+    fn test_resolves_program_layout() {
+        let projects_dir = tempfile::tempdir().unwrap();
+        let project = projects_dir.path().join("my_project");
+        write_program_crate(&project.join("program"));
+        // A project without a program crate is not deployable
+        fs::create_dir_all(projects_dir.path().join("notes/program")).unwrap();
+
+        let projects = deployable_projects(projects_dir.path()).unwrap();
+        assert_eq!(projects, vec![("my_project".to_string(), project.join("program"))]);
+    }
+
+    #[test]
+    fn test_arch_pubkey_ignores_key_encoding() {
+        let secp = secp256k1::Secp256k1::new();
+        let secret_key = secp256k1::SecretKey::from_slice(&[7u8; 32]).unwrap();
+        let public_key = secret_key.public_key(&secp);
+
+        let compressed = secp256k1::PublicKey::from_slice(&public_key.serialize()).unwrap();
+        let uncompressed = secp256k1::PublicKey::from_slice(&public_key.serialize_uncompressed()).unwrap();
+        let x_only = public_key.x_only_public_key().0.serialize();
+
+        assert_eq!(arch_pubkey_from_secp(&compressed).serialize(), x_only);
+        assert_eq!(arch_pubkey_from_secp(&uncompressed).serialize(), x_only);
+    }
+
+    #[test]
+    fn test_migrates_accounts_file_entries() {
+        let secp = secp256k1::Secp256k1::new();
+        let secret_key = secp256k1::SecretKey::from_slice(&[9u8; 32]).unwrap();
+        let public_key = secret_key.public_key(&secp);
+        let secret_hex = hex::encode(secret_key.secret_bytes());
+        let pubkey_hex = hex::encode(arch_pubkey_from_secp(&public_key).serialize());
+        let legacy_id = hex::encode(public_key.serialize());
+
+        let mut keys = json!({
+            "program": { "public_key": "ab", "secret_key": "cd" },
+            legacy_id.clone(): {
+                "name": "alice",
+                "private_key": secret_hex,
+                "public_key": legacy_id,
+            },
+        });
+
+        assert_eq!(migrate_keys(&mut keys), 1);
+        assert_eq!(keys["alice"], json!({ "public_key": pubkey_hex, "secret_key": secret_hex }));
+        assert_eq!(keys["program"], json!({ "public_key": "ab", "secret_key": "cd" }));
+        assert_eq!(keys.as_object().unwrap().len(), 2);
+
+        // Already upgraded files are left alone
+        assert_eq!(migrate_keys(&mut keys), 0);
+
+        // Entries without a secret key are kept rather than failing the whole file
+        keys["bob_id"] = json!({ "name": "bob", "public_key": "ef" });
+        assert_eq!(migrate_keys(&mut keys), 0);
+        assert_eq!(keys["bob_id"], json!({ "name": "bob", "public_key": "ef" }));
+    }
+
+    #[test]
+    fn test_only_not_found_errors_mean_a_missing_account() {
+        let not_found = anyhow::anyhow!(
+            "{:?}",
+            json!({ "code": 404, "message": "account is not in database" })
+        );
+        assert!(is_not_found_error(&not_found));
+        assert!(!is_not_found_error(&anyhow::anyhow!("error sending request: connection refused")));
+    }
+
+    #[test]
+    fn test_hex_dump_offsets_and_ascii() {
+        let data: Vec<u8> = (0x41..0x41 + 18).collect();
+        assert_eq!(
+            hex_dump(&data, 0x20),
+            "00000020  41 42 43 44 45 46 47 48 49 4a 4b 4c 4d 4e 4f 50  |ABCDEFGHIJKLMNOP|\n\
+             00000030  51 52                                            |QR|"
+        );
+        assert_eq!(hex_dump(&[0x00, 0x7f, b' '], 0), "00000000  00 7f 20                                         |.. |");
+    }
+
+    #[test]
+    fn test_selects_inputs_until_amount_and_fee_are_covered() {
+        let values = [Amount::from_sat(3_000), Amount::from_sat(4_000), Amount::from_sat(10_000)];
+
+        // One input covers neither 5000 nor its fee, two cover both
+        let (count, total, fee) = select_funding_inputs(&values, Amount::from_sat(5_000), 2).unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(total, Amount::from_sat(7_000));
+        assert_eq!(fee, Amount::from_sat(2 * funding_tx_vsize(2)));
+    }
+
+    #[test]
+    fn test_funding_fee_counts_against_the_inputs() {
+        let values = [Amount::from_sat(5_000)];
+
+        // The output alone matches the amount, but nothing is left for the fee
+        assert_eq!(select_funding_inputs(&values, Amount::from_sat(5_000), 1), None);
+        let fee = Amount::from_sat(funding_tx_vsize(1));
+        assert_eq!(
+            select_funding_inputs(&values, Amount::from_sat(5_000) - fee, 1),
+            Some((1, Amount::from_sat(5_000), fee))
+        );
+    }
+
+    #[test]
+    fn test_funding_fails_without_inputs() {
+        assert_eq!(select_funding_inputs(&[], Amount::from_sat(330), 1), None);
+    }
+
+    #[test]
+    fn test_compose_port_reads_variables_and_defaults() {
+        let compose = "ports:\n  - \"${BITCOIN_RPC_PORT}:${BITCOIN_RPC_PORT}\"\n  - \"${VALIDATOR_RPC_PORT:-9001}:${VALIDATOR_RPC_PORT:-9001}\"\n";
+
+        assert_eq!(compose_port(compose, "BITCOIN_RPC_PORT", Some("18443".to_string())), Some(18443));
+        assert_eq!(compose_port(compose, "BITCOIN_RPC_PORT", None), None);
+        assert_eq!(compose_port(compose, "VALIDATOR_RPC_PORT", None), Some(9001));
+        assert_eq!(compose_port(compose, "VALIDATOR_RPC_PORT", Some("9101".to_string())), Some(9101));
+        assert_eq!(compose_port(compose, "ORD_PORT", Some("3032".to_string())), None);
     }
 }
@@ -8,58 +8,150 @@ use dotenv::dotenv;
 async fn main() -> Result<()> {
     dotenv().ok();
 
-    println!("{}", "Welcome to the Arch Network CLI".bold().green());
+    // Parse command-line arguments
+    let cli = Cli::parse();
+
+    // The ci preset turns on everything needed for unattended runs
+    let ci = cli.preset == Some(settings::Preset::Ci);
+
+    // Decide on colored output before printing anything
+    configure_color_output(cli.no_color || ci);
+    output::set_output_format(if ci { output::OutputFormat::Json } else { cli.output });
+    output::set_verbosity(if cli.quiet || ci {
+        output::Verbosity::Quiet
+    } else if cli.verbose {
+        output::Verbosity::Verbose
+    } else {
+        output::Verbosity::Normal
+    });
+    output::set_progress_format(cli.progress);
+    settings::set_non_interactive(cli.non_interactive || ci);
+    settings::set_strict_timeouts(ci);
+    settings::set_keep_temp(cli.keep_temp);
+    settings::set_leader_rpc(cli.leader_rpc.clone())?;
+    settings::set_config_profile(cli.profile.clone())?;
+    output::set_timings(cli.timings);
+
+    let key_seed = cli
+        .seed
+        .clone()
+        .or_else(|| std::env::var(settings::KEY_SEED_ENV).ok());
+    if let Some(seed) = key_seed {
+        settings::set_key_seed(&seed)?;
+        if !output::quiet() {
+            eprintln!(
+                "{} Generating keys from a fixed seed; never use these keys outside tests",
+                "⚠".bold().yellow()
+            );
+        }
+    }
+
+    // Keep stdout clean for machine-readable output
+    if !output::json_output() && !output::quiet() {
+        println!("{}", "Welcome to the Arch Network CLI".bold().green());
+    }
 
+    output::timing_phase("loading the configuration");
     if let Err(e) = ensure_global_config() {
         eprintln!("Failed to initialize global configuration: {}", e);
         std::process::exit(1);
     }
 
-    // Parse command-line arguments
-    let cli = Cli::parse();
-
     // Load configuration
-    let config = load_config(&cli.network)?;
+    let config = match &cli.network_from_url {
+        Some(rpc_url) => load_config_from_url(rpc_url)?,
+        None => load_config(&cli.network.clone().unwrap_or_else(default_network))?,
+    };
 
-    // Set verbose mode if flag is present
-    if cli.verbose {
-        // Set up verbose logging or output here
-    }
+    // Isolate the compose stacks of this invocation under their own project name
+    let project_name = cli
+        .project_name
+        .clone()
+        .or_else(|| config.get_string("docker.project_name").ok())
+        .filter(|name| !name.is_empty());
+    settings::set_compose_project_name(project_name)?;
+
+    output::timing_phase("running the command");
 
     // Match on the subcommand
     let result = match &cli.command {
-        Commands::Init => init().await,
-        Commands::Server(ServerCommands::Start) => server_start(&config).await,
-        Commands::Server(ServerCommands::Stop) => server_stop(&config).await,
+        Commands::Init(args) => init(args).await,
+        Commands::Server(ServerCommands::Start(args)) => server_start(args, &config).await,
+        Commands::Server(ServerCommands::Stop(args)) => server_stop(args, &config).await,
+        Commands::Server(ServerCommands::Restart(args)) => server_restart(args, &config).await,
         Commands::Server(ServerCommands::Status) => server_status(&config).await,
-        Commands::Server(ServerCommands::Logs { service }) => server_logs(service, &config).await,
+        Commands::Server(ServerCommands::Logs { service, since, follow, tail }) => {
+            server_logs(service, since.as_deref(), *follow, *tail, &config).await
+        }
         Commands::Server(ServerCommands::Clean) => server_clean(&config).await,
         Commands::Deploy(args) => deploy(args, &config).await,
-        Commands::Dkg(DkgCommands::Start) => start_dkg(&config).await,
+        Commands::Dkg(DkgCommands::Start(args)) => start_dkg(args, &config).await,
+        Commands::Dkg(DkgCommands::Status) => dkg_status(&config).await,
         Commands::Bitcoin(BitcoinCommands::SendCoins(args)) => send_coins(args, &config).await,
+        Commands::Bitcoin(BitcoinCommands::Utxos(args)) => bitcoin_utxos(args, &config).await,
+        Commands::Bitcoin(BitcoinCommands::Balance(args)) => bitcoin_balance(args, &config).await,
+        Commands::Bitcoin(BitcoinCommands::Info(args)) => bitcoin_info(args, &config).await,
+        Commands::Bitcoin(BitcoinCommands::Mine(args)) => bitcoin_mine(args, &config).await,
+        Commands::Bitcoin(BitcoinCommands::Address) => bitcoin_address(&config).await,
         Commands::Demo(DemoCommands::Start(args)) => demo_start(args, &config).await,
         Commands::Demo(DemoCommands::Stop) => demo_stop(&config).await,
+        Commands::Demo(DemoCommands::LoadTest(args)) => demo_load_test(args, &config).await,
         Commands::Account(AccountCommands::Create(args)) => create_account(args, &config).await,
-        Commands::Account(AccountCommands::List) => list_accounts().await,
+        Commands::Account(AccountCommands::List(args)) => list_accounts(args, &config).await,
         Commands::Account(AccountCommands::Delete(args)) => delete_account(args).await,
         Commands::Account(AccountCommands::AssignOwnership(args)) => assign_ownership(args, &config).await,
         Commands::Account(AccountCommands::Update(args)) => update_account(args, &config).await,
+        Commands::Account(AccountCommands::Dump(args)) => dump_account(args, &config).await,
+        Commands::Account(AccountCommands::Read(args)) => read_account(args, &config).await,
+        Commands::Account(AccountCommands::Export(args)) => export_account(args).await,
+        Commands::Account(AccountCommands::Import(args)) => import_account(args).await,
+        Commands::Account(AccountCommands::Rename(args)) => rename_account(args).await,
+        Commands::Account(AccountCommands::Balance(args)) => account_balance(args, &config).await,
+        Commands::Account(AccountCommands::Info(args)) => account_info(args, &config).await,
+        Commands::Account(AccountCommands::Encrypt) => encrypt_keys().await,
         Commands::Config(ConfigCommands::View) => config_view(&config).await,
         Commands::Config(ConfigCommands::Edit) => config_edit().await,
         Commands::Config(ConfigCommands::Reset) => config_reset().await,
-        Commands::Start => server_start(&config).await,
-        Commands::Stop => server_stop(&config).await,
+        Commands::Config(ConfigCommands::Validate) => config_validate().await,
+        Commands::Templates(TemplatesCommands::Sync(args)) => templates_sync(args).await,
+        Commands::Config(ConfigCommands::SetNetwork { name }) => config_set_network(name).await,
+        Commands::Config(ConfigCommands::GetNetwork) => config_get_network().await,
+        Commands::Config(ConfigCommands::List) => config_list().await,
+        Commands::Config(ConfigCommands::Get { key }) => config_get(key).await,
+        Commands::Config(ConfigCommands::Set { key, value }) => config_set(key, value).await,
+        Commands::Start(args) => server_start(args, &config).await,
+        Commands::Stop(args) => server_stop(args, &config).await,
+        Commands::Status => status(&config).await,
+        Commands::Doctor => doctor().await,
         Commands::Indexer(IndexerCommands::Start(args)) => indexer_start(args, &config).await,
         Commands::Indexer(IndexerCommands::Stop(args)) => indexer_stop(args, &config).await,
+        Commands::Indexer(IndexerCommands::Status(args)) => indexer_status(args).await,
+        Commands::Indexer(IndexerCommands::Logs(args)) => indexer_logs(args).await,
         Commands::Indexer(IndexerCommands::Clean) => indexer_clean(&config).await,
         Commands::Project(ProjectCommands::Create(args)) => create_project(args, &config).await,
         Commands::Project(ProjectCommands::Deploy) => project_deploy(&config).await,
+        Commands::Project(ProjectCommands::Artifacts(args)) => project_artifacts(args, &config).await,
         Commands::Validator(ValidatorCommands::Start(args)) => validator_start(args, &config).await,
         Commands::Validator(ValidatorCommands::Stop(args)) => validator_stop(&args).await,
+        Commands::Validator(ValidatorCommands::Logs(args)) => validator_logs(args).await,
+        Commands::Validator(ValidatorCommands::Status(args)) => validator_status(args, &config).await,
+        Commands::Network(NetworkCommands::Blockhash(args)) => network_blockhash(args, &config).await,
+        Commands::Program(ProgramCommands::Show(args)) => program_show(args, &config).await,
+        Commands::Program(ProgramCommands::Describe(args)) => program_describe(args).await,
+        Commands::Program(ProgramCommands::Rotate(args)) => program_rotate(args, &config).await,
+        Commands::SelfUpdate(args) => self_update(args).await,
     };
 
+    // Report the timings of failed runs too, they show where the time went before the failure
+    output::print_timings();
+
     if let Err(e) = result {
-        println!("Error: {}", e);
+        // With --output json, stdout still carries a single JSON document. Commands such as
+        // doctor print their report before failing, and that report already describes the failure
+        if output::json_output() && !output::json_printed() {
+            output::print_json(&serde_json::json!({ "error": e.to_string() }))?;
+        }
+        eprintln!("Error: {}", e);
         std::process::exit(1);
     }
 
@@ -0,0 +1,175 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Output format selected with the global `--output` flag
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+/// Progress style selected with the global `--progress` flag
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProgressFormat {
+    /// Human-readable progress bars
+    Bar,
+    /// Newline-delimited JSON events on stderr
+    Json,
+}
+
+/// How much progress output is printed, selected with the global `--quiet` and `--verbose` flags
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Only errors and final results
+    Quiet,
+    /// Progress messages as well
+    Normal,
+    /// Also per-file and other detailed progress
+    Verbose,
+}
+
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+static JSON_PROGRESS: AtomicBool = AtomicBool::new(false);
+static TIMINGS: AtomicBool = AtomicBool::new(false);
+static JSON_PRINTED: AtomicBool = AtomicBool::new(false);
+
+/// Name and start time of every phase timed so far, in order. A phase ends when the next starts
+static PHASES: Mutex<Vec<(String, Instant)>> = Mutex::new(Vec::new());
+
+pub fn set_output_format(format: OutputFormat) {
+    JSON_OUTPUT.store(format == OutputFormat::Json, Ordering::Relaxed);
+}
+
+/// Returns true when commands should print machine-readable JSON instead of text
+pub fn json_output() -> bool {
+    JSON_OUTPUT.load(Ordering::Relaxed)
+}
+
+pub fn set_verbosity(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+pub fn verbosity() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Verbosity::Quiet,
+        1 => Verbosity::Normal,
+        _ => Verbosity::Verbose,
+    }
+}
+
+/// Returns true when decorative output is suppressed with `--quiet`
+pub fn quiet() -> bool {
+    verbosity() == Verbosity::Quiet
+}
+
+/// Prints a JSON document to stdout
+pub fn print_json(value: &Value) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    JSON_PRINTED.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Returns true once a JSON document has been printed, such as a report printed before failing
+pub fn json_printed() -> bool {
+    JSON_PRINTED.load(Ordering::Relaxed)
+}
+
+/// Prints a line of progress output unless `--quiet` is set: on stdout normally, on stderr
+/// with `--output json`, so that stdout only carries the final JSON document
+pub fn say(line: impl std::fmt::Display) {
+    if quiet() {
+        return;
+    }
+    if json_output() {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+}
+
+/// Prints a line of detailed progress, such as each extracted file, only with `--verbose`
+pub fn detail(line: impl std::fmt::Display) {
+    if verbosity() == Verbosity::Verbose {
+        say(line);
+    }
+}
+
+pub fn set_progress_format(format: ProgressFormat) {
+    JSON_PROGRESS.store(format == ProgressFormat::Json, Ordering::Relaxed);
+}
+
+/// Returns true when progress is reported as JSON events instead of progress bars
+pub fn progress_json() -> bool {
+    JSON_PROGRESS.load(Ordering::Relaxed)
+}
+
+/// Emits a progress event on stderr when `--progress json` is active
+pub fn progress_event(phase: &str, step: usize, total: usize, message: &str) {
+    if !progress_json() {
+        return;
+    }
+
+    let percent = if total == 0 {
+        100.0
+    } else {
+        step as f64 * 100.0 / total as f64
+    };
+    eprintln!(
+        "{}",
+        json!({
+            "phase": phase,
+            "step": step,
+            "total": total,
+            "percent": percent,
+            "message": message,
+        })
+    );
+}
+
+pub fn set_timings(enabled: bool) {
+    TIMINGS.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns true when phase timings are recorded with `--timings`
+pub fn timings() -> bool {
+    TIMINGS.load(Ordering::Relaxed)
+}
+
+/// Starts timing a phase for `--timings`, ending the one in progress
+pub fn timing_phase(name: &str) {
+    if timings() {
+        PHASES.lock().unwrap().push((name.to_string(), Instant::now()));
+    }
+}
+
+/// Ends the phase in progress and prints how long each phase took on stderr,
+/// so the summary does not mix with JSON output
+pub fn print_timings() {
+    if !timings() {
+        return;
+    }
+
+    let end = Instant::now();
+    let phases = PHASES.lock().unwrap();
+    let durations: Vec<(&str, Duration)> = phases
+        .iter()
+        .enumerate()
+        .map(|(index, (name, start))| {
+            let next = phases.get(index + 1).map_or(end, |(_, next)| *next);
+            (name.as_str(), next.duration_since(*start))
+        })
+        .collect();
+    let total = phases.first().map_or(Duration::ZERO, |(_, start)| end.duration_since(*start));
+
+    let width = durations.iter().map(|(name, _)| name.len()).max().unwrap_or(0).max("total".len());
+    eprintln!("Timings:");
+    for (name, duration) in &durations {
+        eprintln!("  {:<width$}  {:>8.2}s", name, duration.as_secs_f64(), width = width);
+    }
+    eprintln!("  {:<width$}  {:>8.2}s", "total", total.as_secs_f64(), width = width);
+}
@@ -0,0 +1,232 @@
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use bitcoin::hashes::{sha256, Hash};
+use colored::*;
+use dialoguer::Confirm;
+use rand::rngs::OsRng;
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use secp256k1::{PublicKey, Secp256k1, SecretKey, Signing};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Presets selected with the global `--preset` flag
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Preset {
+    /// Interactive, colorful defaults for local development
+    Dev,
+    /// Non-interactive, uncolored JSON output with strict timeouts
+    Ci,
+}
+
+static NON_INTERACTIVE: AtomicBool = AtomicBool::new(false);
+static STRICT_TIMEOUTS: AtomicBool = AtomicBool::new(false);
+static KEEP_TEMP: AtomicBool = AtomicBool::new(false);
+static KEY_RNG: Mutex<Option<ChaCha20Rng>> = Mutex::new(None);
+static KEY_SEED: Mutex<Option<[u8; 32]>> = Mutex::new(None);
+static COMPOSE_PROJECT: Mutex<Option<String>> = Mutex::new(None);
+static LEADER_RPC: Mutex<Option<String>> = Mutex::new(None);
+static CONFIG_PROFILE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Environment variable read when `--seed` is not given
+pub const KEY_SEED_ENV: &str = "ARCH_CLI_KEY_SEED";
+
+pub fn set_non_interactive(enabled: bool) {
+    NON_INTERACTIVE.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns true when prompts are disabled with `--non-interactive`
+pub fn non_interactive() -> bool {
+    NON_INTERACTIVE.load(Ordering::Relaxed)
+}
+
+pub fn set_strict_timeouts(enabled: bool) {
+    STRICT_TIMEOUTS.store(enabled, Ordering::Relaxed);
+}
+
+/// Selects the named configuration profile read from `config.<name>.toml`
+pub fn set_config_profile(name: Option<String>) -> Result<()> {
+    if let Some(name) = &name {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(anyhow!(
+                "Invalid profile name '{}': use letters, digits, '-' and '_'",
+                name
+            ));
+        }
+    }
+    // `default` names the plain config.toml
+    *CONFIG_PROFILE.lock().unwrap() = name.filter(|name| name != "default");
+    Ok(())
+}
+
+/// Returns the configuration profile selected with `--profile`, None for the default config.toml
+pub fn config_profile() -> Option<String> {
+    CONFIG_PROFILE.lock().unwrap().clone()
+}
+
+/// Sets the Docker Compose project name passed with `-p` to every compose invocation
+pub fn set_compose_project_name(name: Option<String>) -> Result<()> {
+    if let Some(name) = &name {
+        let valid = name.chars().next().map_or(false, |c| c.is_ascii_lowercase() || c.is_ascii_digit())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+        if !valid {
+            return Err(anyhow!(
+                "Invalid project name '{}': use lowercase letters, digits, '-' and '_', starting with a letter or digit",
+                name
+            ));
+        }
+    }
+    *COMPOSE_PROJECT.lock().unwrap() = name;
+    Ok(())
+}
+
+/// Returns the Docker Compose project name of one arch-cli stack (`server`, `demo` or `indexer`)
+/// derived from `--project-name` or `docker.project_name`. Each stack is its own project, so
+/// taking one down leaves the others running
+pub fn compose_project_name(stack: &str) -> Option<String> {
+    COMPOSE_PROJECT
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|name| format!("{}-{}", name, stack))
+}
+
+/// Arguments selecting the Docker Compose project of `stack`, empty when no project name is set
+pub fn compose_project_args(stack: &str) -> Vec<String> {
+    compose_project_name(stack)
+        .map(|name| vec!["-p".to_string(), name])
+        .unwrap_or_default()
+}
+
+pub fn set_leader_rpc(endpoint: Option<String>) -> Result<()> {
+    if let Some(endpoint) = &endpoint {
+        if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+            return Err(anyhow!("--leader-rpc must be an http(s) URL: {}", endpoint));
+        }
+    }
+    *LEADER_RPC.lock().unwrap() = endpoint;
+    Ok(())
+}
+
+/// Returns the leader RPC endpoint given with `--leader-rpc`, which overrides the configured one
+pub fn leader_rpc() -> Option<String> {
+    LEADER_RPC.lock().unwrap().clone()
+}
+
+pub fn set_keep_temp(enabled: bool) {
+    KEEP_TEMP.store(enabled, Ordering::Relaxed);
+}
+
+/// Directory holding generated build files such as Dockerfiles and cloudbuild configs
+pub struct ScratchDir {
+    path: PathBuf,
+    // Removes the directory on drop, unless it is kept with --keep-temp
+    _dir: Option<tempfile::TempDir>,
+}
+
+impl ScratchDir {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Creates a temporary directory for the files generated for `purpose`. With `--keep-temp`
+/// the directory is left in place and its path printed, so a failed build can be inspected.
+pub fn scratch_dir(purpose: &str) -> Result<ScratchDir> {
+    let dir = tempfile::Builder::new().prefix("arch-cli-").tempdir()?;
+    if !KEEP_TEMP.load(Ordering::Relaxed) {
+        return Ok(ScratchDir { path: dir.path().to_path_buf(), _dir: Some(dir) });
+    }
+
+    let path = dir.into_path();
+    eprintln!(
+        "  {} Keeping the generated files for {} in {}",
+        "ℹ".bold().blue(),
+        purpose,
+        path.display().to_string().yellow()
+    );
+    Ok(ScratchDir { path, _dir: None })
+}
+
+/// Fails with a clear error instead of prompting when running non-interactively
+pub fn require_interactive(what: &str) -> Result<()> {
+    if non_interactive() {
+        return Err(anyhow!(
+            "{} requires interactive input, which is disabled by --non-interactive",
+            what
+        ));
+    }
+    Ok(())
+}
+
+/// Asks a yes/no question, answering with `default` when running non-interactively
+pub fn confirm(prompt: &str, default: bool) -> Result<bool> {
+    if non_interactive() {
+        return Ok(default);
+    }
+    Ok(Confirm::new().with_prompt(prompt).default(default).interact()?)
+}
+
+/// How long to wait for a funding transaction before giving up
+pub fn funding_timeout() -> Duration {
+    if STRICT_TIMEOUTS.load(Ordering::Relaxed) {
+        Duration::from_secs(300)
+    } else {
+        Duration::from_secs(3600)
+    }
+}
+
+/// Seeds key generation from a hex string. A 32-byte seed is used as-is;
+/// any other length is hashed down to 32 bytes with SHA-256.
+pub fn set_key_seed(seed_hex: &str) -> Result<()> {
+    let bytes = hex::decode(seed_hex.trim())
+        .map_err(|e| anyhow!("Invalid key seed '{}': expected hex ({})", seed_hex, e))?;
+    if bytes.is_empty() {
+        return Err(anyhow!("Key seed must not be empty"));
+    }
+    let seed: [u8; 32] = match <[u8; 32]>::try_from(bytes.as_slice()) {
+        Ok(seed) => seed,
+        Err(_) => sha256::Hash::hash(&bytes).to_byte_array(),
+    };
+    *KEY_RNG.lock().unwrap() = Some(ChaCha20Rng::from_seed(seed));
+    *KEY_SEED.lock().unwrap() = Some(seed);
+    Ok(())
+}
+
+/// Derives the secret key at `index` from the `--seed` value. Unlike
+/// [`generate_keypair`], the result does not depend on how many keys were generated before.
+pub fn derive_secret_key(index: u32) -> Result<SecretKey> {
+    let seed = KEY_SEED
+        .lock()
+        .unwrap()
+        .ok_or_else(|| anyhow!("Deterministic keys need a seed: pass --seed or set {}", KEY_SEED_ENV))?;
+
+    // Hash seed, a domain tag and the index; retry with a counter in the unlikely case
+    // that the digest is not a valid secret key
+    (0u8..=255)
+        .find_map(|attempt| {
+            let mut data = Vec::with_capacity(32 + 16 + 5);
+            data.extend_from_slice(&seed);
+            data.extend_from_slice(b"arch-cli/account");
+            data.extend_from_slice(&index.to_le_bytes());
+            data.push(attempt);
+            SecretKey::from_slice(&sha256::Hash::hash(&data).to_byte_array()).ok()
+        })
+        .ok_or_else(|| anyhow!("Failed to derive a key at index {}", index))
+}
+
+/// Generates a keypair from the seeded RNG when `--seed` is set, and from the OS otherwise
+pub fn generate_keypair<C: Signing>(secp: &Secp256k1<C>) -> (SecretKey, PublicKey) {
+    match KEY_RNG.lock().unwrap().as_mut() {
+        Some(rng) => secp.generate_keypair(rng),
+        None => secp.generate_keypair(&mut OsRng),
+    }
+}
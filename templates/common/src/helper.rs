@@ -20,6 +20,7 @@ use std::fs;
 use std::str::FromStr;
 
 use crate::processed_transaction::ProcessedTransaction;
+use futures::StreamExt;
 
 use crate::constants::{
     BITCOIN_NETWORK, BITCOIN_NODE_ENDPOINT, BITCOIN_NODE_PASSWORD, BITCOIN_NODE_USERNAME,
@@ -33,12 +34,99 @@ use crate::signature::Signature;
 use arch_program::message::Message;
 use arch_program::pubkey::Pubkey;
 
+/// Number of attempts made for an RPC call that hits a 502/503/504 from a proxy
+const RPC_ATTEMPTS: u32 = 4;
+
+/// Returns the first characters of a response body on a single line, for error messages
+fn body_snippet(body: &str) -> String {
+    let snippet: String = body.split_whitespace().collect::<Vec<_>>().join(" ");
+    if snippet.chars().count() > 200 {
+        format!("{}...", snippet.chars().take(200).collect::<String>())
+    } else {
+        snippet
+    }
+}
+
+/// Fails with a readable error when a response is not a JSON-RPC payload, which typically
+/// means a proxy or load balancer answered with an HTML error page
+fn ensure_json_body(status: Option<u16>, body: &str) -> Result<()> {
+    if body.trim_start().starts_with('{') || body.trim_start().starts_with('[') {
+        return Ok(());
+    }
+
+    let status = status.map_or_else(String::new, |status| format!("HTTP {} ", status));
+    Err(anyhow!(
+        "RPC endpoint returned {}a non-JSON response (is the node or its proxy ready?): {}",
+        status,
+        body_snippet(body)
+    ))
+}
+
+/// How long to wait before retrying a request answered with `status`, or None when the
+/// response is final: anything but a 502/503/504 from a proxy, or the last attempt
+fn retry_delay(status: u16, attempt: u32) -> Option<std::time::Duration> {
+    let unavailable = matches!(status, 502 | 503 | 504);
+    (unavailable && attempt < RPC_ATTEMPTS)
+        .then(|| std::time::Duration::from_millis(500 << (attempt - 1)))
+}
+
+/// Sends a JSON-RPC request, retrying with backoff while a proxy reports the node unavailable
+fn send_rpc(client: &reqwest::blocking::Client, url: &str, request: &Value) -> Result<String> {
+    let mut attempt = 1;
+    loop {
+        let res = client
+            .post(url)
+            .header("content-type", "application/json")
+            .json(request)
+            .send()
+            .map_err(|e| anyhow!("Failed to reach RPC endpoint {}: {}", url, e))?;
+
+        let status = res.status().as_u16();
+        let body = res.text()?;
+        match retry_delay(status, attempt) {
+            Some(delay) => std::thread::sleep(delay),
+            None => {
+                ensure_json_body(Some(status), &body)?;
+                return Ok(body);
+            }
+        }
+        attempt += 1;
+    }
+}
+
+/// Async counterpart of `send_rpc`
+async fn send_rpc_async(client: &reqwest::Client, url: &str, request: &Value) -> Result<String> {
+    let mut attempt = 1;
+    loop {
+        let res = client
+            .post(url)
+            .header("content-type", "application/json")
+            .json(request)
+            .send()
+            .await
+            .map_err(|e| anyhow!("Failed to reach RPC endpoint {}: {}", url, e))?;
+
+        let status = res.status().as_u16();
+        let body = res.text().await?;
+        match retry_delay(status, attempt) {
+            Some(delay) => tokio::time::sleep(delay).await,
+            None => {
+                ensure_json_body(Some(status), &body)?;
+                return Ok(body);
+            }
+        }
+        attempt += 1;
+    }
+}
+
 pub fn process_result(response: String) -> Result<Value> {
-    let result = from_str::<Value>(&response).expect("result should be Value parseable");
+    ensure_json_body(None, &response)?;
+    let result = from_str::<Value>(&response)
+        .map_err(|e| anyhow!("result should be Value parseable: {}", e))?;
 
     let result = match result {
         Value::Object(object) => object,
-        _ => panic!("unexpected output"),
+        _ => return Err(anyhow!("unexpected output: {}", response)),
     };
 
     if let Some(err) = result.get("error") {
@@ -49,11 +137,13 @@ pub fn process_result(response: String) -> Result<Value> {
 }
 
 pub fn process_get_transaction_result(response: String) -> Result<Value> {
-    let result = from_str::<Value>(&response).expect("result should be string parseable");
+    ensure_json_body(None, &response)?;
+    let result = from_str::<Value>(&response)
+        .map_err(|e| anyhow!("result should be Value parseable: {}", e))?;
 
     let result = match result {
         Value::Object(object) => object,
-        _ => panic!("unexpected output"),
+        _ => return Err(anyhow!("unexpected output: {}", body_snippet(&response))),
     };
 
     if let Some(err) = result.get("error") {
@@ -68,42 +158,60 @@ pub fn process_get_transaction_result(response: String) -> Result<Value> {
     Ok(result["result"].clone())
 }
 
-pub fn post(url: &str, method: &str) -> String {
+pub fn post(url: &str, method: &str) -> Result<String> {
     let client = reqwest::blocking::Client::new();
-    let res = client
-        .post(url)
-        .header("content-type", "application/json")
-        .json(&json!({
-            "jsonrpc": "2.0",
-            "id": "curlycurl",
-            "method": method,
-        }))
-        .send()
-        .expect("post method should not fail");
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": "curlycurl",
+        "method": method,
+    });
 
-    res.text().expect("result should be text decodable")
+    send_rpc(&client, url, &request)
 }
 
-pub fn post_data<T: Serialize + std::fmt::Debug>(url: &str, method: &str, params: T) -> String {
+pub fn post_data<T: Serialize + std::fmt::Debug>(url: &str, method: &str, params: T) -> Result<String> {
     let client = reqwest::blocking::Client::builder()
         .danger_accept_invalid_certs(true) // Ignore SSL certificate validation
-        .build()
-        .expect("client should be built");
+        .build()?;
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": "curlycurl",
+        "method": method,
+        "params": params,
+    });
+
+    send_rpc(&client, url, &request)
+}
 
-    let res = client
-        .post(url)
-        .header("content-type", "application/json")
-        .json(&json!({
-            "jsonrpc": "2.0",
-            "id": "curlycurl",
-            "method": method,
-            "params": params,
-        }))
-        .send();
+/// Async counterpart of `post`
+pub async fn post_async(url: &str, method: &str) -> Result<String> {
+    let client = reqwest::Client::new();
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": "curlycurl",
+        "method": method,
+    });
+
+    send_rpc_async(&client, url, &request).await
+}
 
-    res.expect("post method should not fail")
-        .text()
-        .expect("result should be text decodable")
+/// Async counterpart of `post_data`, for callers that already run on a tokio runtime
+pub async fn post_data_async<T: Serialize + std::fmt::Debug>(
+    url: &str,
+    method: &str,
+    params: T,
+) -> Result<String> {
+    let client = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true) // Ignore SSL certificate validation
+        .build()?;
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": "curlycurl",
+        "method": method,
+        "params": params,
+    });
+
+    send_rpc_async(&client, url, &request).await
 }
 
 /// Returns a caller information using the secret key file specified
@@ -155,13 +263,11 @@ pub fn extend_bytes_max_len() -> usize {
         .len()
 }
 
-/// Creates an instruction, signs it as a message
-/// and sends the signed message as a transaction
-pub fn sign_and_send_instruction(
-    instruction: Instruction,
-    signers: Vec<Keypair>,
-    rpc_url: String,
-) -> Result<(String, String)> {
+/// Builds and signs a runtime transaction carrying a single instruction
+fn build_instruction_transaction(
+    instruction: &Instruction,
+    signers: &[Keypair],
+) -> RuntimeTransaction {
     // Get public keys from signers
     let pubkeys = signers
         .iter()
@@ -192,21 +298,30 @@ pub fn sign_and_send_instruction(
     //println!("Message signed by {} signers",signatures.len());
 
     // Step 6: Create transaction parameters
-    let params = RuntimeTransaction {
+    RuntimeTransaction {
         version: 0,
         signatures: signatures.clone(), // Clone for logging purposes
         message: message.clone(),       // Clone for logging purposes
-    };
+    }
+}
+
+/// Creates an instruction, signs it as a message
+/// and sends the signed message as a transaction
+pub fn sign_and_send_instruction(
+    instruction: Instruction,
+    signers: Vec<Keypair>,
+    rpc_url: String,
+) -> Result<(String, String)> {
+    let params = build_instruction_transaction(&instruction, &signers);
 
     // Correcting the error by using the `unwrap_or` method instead of `unwrap_or_else`
     let url = rpc_url;
 
     //println!("Runtime Transaction constructed : {:?} ",params);
     // Step 7: Send transaction to node for processing
-    let result = process_result(post_data(&url, "send_transaction", params))
-        .expect("send_transaction should not fail")
+    let result = post_data(&url, "send_transaction", params).and_then(process_result)?
         .as_str()
-        .expect("cannot convert result to string")
+        .ok_or_else(|| anyhow!("cannot convert result to string"))?
         .to_string();
 
     //println!("Arch transaction ID: {:?}", result);
@@ -217,6 +332,24 @@ pub fn sign_and_send_instruction(
     Ok((result, hashed_instruction))
 }
 
+/// Async counterpart of `sign_and_send_instruction`. Send failures are returned instead of
+/// panicking, so many of these can be in flight at once.
+pub async fn sign_and_send_instruction_async(
+    instruction: Instruction,
+    signers: Vec<Keypair>,
+    rpc_url: String,
+) -> Result<(String, String)> {
+    let params = build_instruction_transaction(&instruction, &signers);
+
+    let response = post_data_async(&rpc_url, "send_transaction", params).await?;
+    let result = process_result(response)?
+        .as_str()
+        .ok_or_else(|| anyhow!("cannot convert result to string"))?
+        .to_string();
+
+    Ok((result, instruction.hash()))
+}
+
 use arch_program::instruction::Instruction;
 
 pub fn sign_and_send_transaction(
@@ -245,24 +378,29 @@ pub fn sign_and_send_transaction(
         signatures,
         message,
     };
-    let result = process_result(post_data(NODE1_ADDRESS, "send_transaction", params))
-        .expect("send_transaction should not fail")
+    let result = post_data(NODE1_ADDRESS, "send_transaction", params).and_then(process_result)?
         .as_str()
-        .expect("cannot convert result to string")
+        .ok_or_else(|| anyhow!("cannot convert result to string"))?
         .to_string();
 
     Ok(result)
 }
 
-pub fn deploy_program_txs(program_keypair: UntweakedKeypair, elf_path: &str) {
+/// Splits the ELF into extend-bytes transactions signed by the program key, starting
+/// with chunk `first_chunk`
+fn build_deploy_txs(
+    program_keypair: &UntweakedKeypair,
+    elf: &[u8],
+    network: Network,
+    first_chunk: usize,
+) -> Vec<RuntimeTransaction> {
     let program_pubkey =
-        Pubkey::from_slice(&XOnlyPublicKey::from_keypair(&program_keypair).0.serialize());
+        Pubkey::from_slice(&XOnlyPublicKey::from_keypair(program_keypair).0.serialize());
 
-    let elf = fs::read(elf_path).expect("elf path should be available");
-
-    let txs = elf
+    elf
         .chunks(extend_bytes_max_len())
         .enumerate()
+        .skip(first_chunk)
         .map(|(i, chunk)| {
             let mut bytes = vec![];
 
@@ -286,12 +424,18 @@ pub fn deploy_program_txs(program_keypair: UntweakedKeypair, elf_path: &str) {
             RuntimeTransaction {
                 version: 0,
                 signatures: vec![Signature(
-                    sign_message_bip322(&program_keypair, &digest_slice, BITCOIN_NETWORK).to_vec(),
+                    sign_message_bip322(program_keypair, &digest_slice, network).to_vec(),
                 )],
                 message,
             }
         })
-        .collect::<Vec<RuntimeTransaction>>();
+        .collect::<Vec<RuntimeTransaction>>()
+}
+
+pub fn deploy_program_txs(program_keypair: UntweakedKeypair, elf_path: &str) {
+    let elf = fs::read(elf_path).expect("elf path should be available");
+
+    let txs = build_deploy_txs(&program_keypair, &elf, BITCOIN_NETWORK, 0);
 
     /*println!(
         "Program deployment split into {} Chunks, sending {} runtime transactions",
@@ -299,7 +443,7 @@ pub fn deploy_program_txs(program_keypair: UntweakedKeypair, elf_path: &str) {
         txs.len()
     );
      */
-    let txids = process_result(post_data(NODE1_ADDRESS, "send_transactions", txs))
+    let txids = post_data(NODE1_ADDRESS, "send_transactions", txs).and_then(process_result)
         .expect("send_transaction should not fail")
         .as_array()
         .expect("cannot convert result to array")
@@ -331,7 +475,7 @@ pub fn deploy_program_txs(program_keypair: UntweakedKeypair, elf_path: &str) {
     // for tx_batch in txs.chunks(12) {
     //     let mut txids = vec![];
     //     for tx in tx_batch {
-    //         let txid = process_result(post_data(NODE1_ADDRESS, "send_transaction", tx))
+    //         let txid = post_data(NODE1_ADDRESS, "send_transaction", tx).and_then(process_result)
     //             .expect("send_transaction should not fail")
     //             .as_str()
     //             .expect("cannot convert result to string")
@@ -350,9 +494,97 @@ pub fn deploy_program_txs(program_keypair: UntweakedKeypair, elf_path: &str) {
     // }
 }
 
+/// How many deployment transactions are polled for confirmation at once, unless configured
+pub const DEFAULT_DEPLOY_CONCURRENCY: usize = 8;
+
+/// Async counterpart of `deploy_program_txs`. Sends the ELF chunks to `rpc_url` and waits for
+/// the resulting transactions to be processed, polling several of them at once.
+/// `on_progress` is called with (processed, total) as transactions are confirmed.
+pub async fn deploy_program_txs_async(
+    rpc_url: &str,
+    program_keypair: &UntweakedKeypair,
+    elf: &[u8],
+    network: Network,
+    on_progress: impl Fn(usize, usize),
+) -> Result<Vec<String>> {
+    deploy_program_txs_from_async(
+        rpc_url,
+        program_keypair,
+        elf,
+        network,
+        0,
+        DEFAULT_DEPLOY_CONCURRENCY,
+        on_progress,
+        |_| {},
+    )
+    .await
+}
+
+/// Like `deploy_program_txs_async`, but only sends the chunks from `start_offset` on, which is
+/// rounded down to a chunk boundary. `on_checkpoint` is called with the offset below which every
+/// chunk has been confirmed, each time that offset advances. At most `concurrency` transactions
+/// are polled for confirmation at once.
+#[allow(clippy::too_many_arguments)]
+pub async fn deploy_program_txs_from_async(
+    rpc_url: &str,
+    program_keypair: &UntweakedKeypair,
+    elf: &[u8],
+    network: Network,
+    start_offset: usize,
+    concurrency: usize,
+    on_progress: impl Fn(usize, usize),
+    on_checkpoint: impl Fn(usize),
+) -> Result<Vec<String>> {
+    let chunk_len = extend_bytes_max_len();
+    let first_chunk = start_offset / chunk_len;
+    let txs = build_deploy_txs(program_keypair, elf, network, first_chunk);
+    if txs.is_empty() {
+        on_progress(0, 0);
+        return Ok(Vec::new());
+    }
+
+    let response = post_data_async(rpc_url, "send_transactions", txs).await?;
+    let txids = process_result(response)?
+        .as_array()
+        .ok_or_else(|| anyhow!("cannot convert result to array"))?
+        .iter()
+        .filter_map(|r| r.as_str().map(String::from))
+        .collect::<Vec<String>>();
+
+    let total = txids.len();
+    on_progress(0, total);
+
+    let mut pending = futures::stream::iter(txids.clone().into_iter().enumerate())
+        .map(|(index, txid)| async move {
+            get_processed_transaction_async(rpc_url, txid).await.map(|_| index)
+        })
+        .buffer_unordered(concurrency.max(1));
+
+    // Confirmations arrive out of order, so only the leading run of confirmed chunks counts
+    // towards the checkpoint
+    let mut confirmed = vec![false; total];
+    let mut confirmed_prefix = 0;
+    let mut processed = 0;
+    while let Some(index) = pending.next().await {
+        confirmed[index?] = true;
+        processed += 1;
+        on_progress(processed, total);
+
+        let previous_prefix = confirmed_prefix;
+        while confirmed_prefix < total && confirmed[confirmed_prefix] {
+            confirmed_prefix += 1;
+        }
+        if confirmed_prefix > previous_prefix {
+            on_checkpoint(((first_chunk + confirmed_prefix) * chunk_len).min(elf.len()));
+        }
+    }
+
+    Ok(txids)
+}
+
 /// Starts Key Exchange by calling the RPC method
 pub fn start_key_exchange() {
-    match process_result(post(NODE1_ADDRESS, "start_key_exchange")) {
+    match post(NODE1_ADDRESS, "start_key_exchange").and_then(process_result) {
         Err(err) => println!("Error starting Key Exchange: {:?}", err),
         Ok(val) => assert!(val.as_bool().unwrap()),
     };
@@ -360,7 +592,7 @@ pub fn start_key_exchange() {
 
 /// Starts a Distributed Key Generation round by calling the RPC method
 pub fn start_dkg() {
-    if let Err(err) = process_result(post(NODE1_ADDRESS, "start_dkg")) {
+    if let Err(err) = post(NODE1_ADDRESS, "start_dkg").and_then(process_result) {
         println!("Error starting DKG: {:?}", err);
     };
 }
@@ -376,7 +608,13 @@ pub struct AccountInfoResult {
 
 /// Read Utxo given the utxo ID
 pub fn read_account_info(url: &str, pubkey: Pubkey) -> Result<AccountInfoResult> {
-    let result = process_result(post_data(url, READ_ACCOUNT_INFO, pubkey))?;
+    let result = post_data(url, READ_ACCOUNT_INFO, pubkey).and_then(process_result)?;
+    serde_json::from_value(result).map_err(|_| anyhow!("Unable to decode read_account_info result"))
+}
+
+/// Async counterpart of `read_account_info`
+pub async fn read_account_info_async(url: &str, pubkey: Pubkey) -> Result<AccountInfoResult> {
+    let result = process_result(post_data_async(url, READ_ACCOUNT_INFO, pubkey).await?)?;
     serde_json::from_value(result).map_err(|_| anyhow!("Unable to decode read_account_info result"))
 }
 /*
@@ -403,34 +641,83 @@ pub async fn get_program_accounts(
 }
 */
 
+/// Async counterpart of `get_program`
+pub async fn get_program_async(url: &str, program_id: String) -> Result<String> {
+    process_result(post_data_async(url, GET_PROGRAM, program_id).await?)?
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("cannot convert result to string"))
+}
+
 /// Returns a program given the program ID
 pub fn get_program(url: &str, program_id: String) -> String {
-    process_result(post_data(url, GET_PROGRAM, program_id))
+    post_data(url, GET_PROGRAM, program_id).and_then(process_result)
         .expect("get_program should not fail")
         .as_str()
         .expect("cannot convert result to string")
         .to_string()
 }
 
+/// Returns the best block hash reported by the node at `url`
+pub fn get_best_block_hash(url: &str) -> Result<String> {
+    post(url, GET_BEST_BLOCK_HASH).and_then(process_result)?
+        .as_str()
+        .map(|hash| hash.to_string())
+        .ok_or_else(|| anyhow!("cannot convert result to string"))
+}
+
+/// Async counterpart of `get_best_block_hash`
+pub async fn get_best_block_hash_async(url: &str) -> Result<String> {
+    process_result(post_async(url, GET_BEST_BLOCK_HASH).await?)?
+        .as_str()
+        .map(|hash| hash.to_string())
+        .ok_or_else(|| anyhow!("cannot convert result to string"))
+}
+
 /// Returns the best block
 fn _get_best_block() -> String {
-    let best_block_hash = process_result(post(NODE1_ADDRESS, GET_BEST_BLOCK_HASH))
+    let best_block_hash = post(NODE1_ADDRESS, GET_BEST_BLOCK_HASH).and_then(process_result)
         .expect("best_block_hash should not fail")
         .as_str()
         .expect("cannot convert result to string")
         .to_string();
-    process_result(post_data(NODE1_ADDRESS, GET_BLOCK, best_block_hash))
+    post_data(NODE1_ADDRESS, GET_BLOCK, best_block_hash).and_then(process_result)
         .expect("get_block should not fail")
         .as_str()
         .expect("cannot convert result to string")
         .to_string()
 }
 
+/// Async counterpart of `get_processed_transaction`.
+/// Keeps trying for a maximum of 60 seconds if the processed transaction is not available or not yet finalized
+pub async fn get_processed_transaction_async(
+    url: &str,
+    tx_id: String,
+) -> Result<ProcessedTransaction> {
+    for _ in 0..60 {
+        let response = post_data_async(url, GET_PROCESSED_TRANSACTION, tx_id.clone()).await?;
+        let processed_tx = process_get_transaction_result(response)?;
+
+        let status = &processed_tx["status"];
+        let finalized = matches!(status.as_str(), Some("Processed") | Some("Failed"))
+            || status.get("Failed").is_some();
+
+        if finalized {
+            return serde_json::from_value(processed_tx)
+                .map_err(|e| anyhow!("Unable to decode processed transaction: {}", e));
+        }
+
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    }
+
+    Err(anyhow!("Failed to retrieve processed transaction {}", tx_id))
+}
+
 /// Returns a processed transaction given the txid
 /// Keeps trying for a maximum of 60 seconds if the processed transaction is not available
 pub fn get_processed_transaction<'a>(url: &str, tx_id: String) -> Result<ProcessedTransaction> {
     let mut processed_tx =
-        process_get_transaction_result(post_data(url, GET_PROCESSED_TRANSACTION, tx_id.clone()));
+        post_data(url, GET_PROCESSED_TRANSACTION, tx_id.clone()).and_then(process_get_transaction_result);
     if let Err(e) = processed_tx {
         return Err(anyhow!("{}", e));
     }
@@ -438,11 +725,11 @@ pub fn get_processed_transaction<'a>(url: &str, tx_id: String) -> Result<Process
     let mut wait_time = 1;
     while let Ok(Value::Null) = processed_tx {
         std::thread::sleep(std::time::Duration::from_secs(wait_time));
-        processed_tx = process_get_transaction_result(post_data(
+        processed_tx = post_data(
             url,
             GET_PROCESSED_TRANSACTION,
             tx_id.clone(),
-        ));
+        ).and_then(process_get_transaction_result);
         wait_time += 1;
         if wait_time >= 60 {
             println!("get_processed_transaction has run for more than 60 seconds");
@@ -472,12 +759,11 @@ pub fn get_processed_transaction<'a>(url: &str, tx_id: String) -> Result<Process
         {
             println!("Processed transaction is not yet finalized. Retrying...");
             std::thread::sleep(std::time::Duration::from_secs(wait_time));
-            p = process_get_transaction_result(post_data(
+            p = post_data(
                 url,
                 GET_PROCESSED_TRANSACTION,
                 tx_id.clone(),
-            ))
-            .unwrap();
+            ).and_then(process_get_transaction_result)?;
             wait_time += 10;
             if wait_time >= 60 {
                 println!("get_processed_transaction has run for more than 60 seconds");
@@ -677,7 +963,7 @@ pub fn send_utxo_2(url: &str, pubkey: Pubkey) -> (Txid, u32) {
 }
 
 pub fn get_account_address(url: &str, pubkey: Pubkey) -> String {
-    process_result(post_data(url, GET_ACCOUNT_ADDRESS, pubkey.serialize()))
+    post_data(url, GET_ACCOUNT_ADDRESS, pubkey.serialize()).and_then(process_result)
         .expect("get_account_address should not fail")
         .as_str()
         .expect("cannot convert result to string")